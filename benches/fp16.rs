@@ -0,0 +1,80 @@
+// Compares GGML's row converters with the `half` crate's slice conversions.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use ggml_sys_bleedingedge::fp16;
+use half::{bf16, f16, slice::HalfFloatSliceExt};
+
+const SIZES: [usize; 3] = [256, 4096, 65536];
+
+fn input(len: usize) -> Vec<f32> {
+    (0..len).map(|i| (i as f32 * 0.37).sin() * 100.0).collect()
+}
+
+fn bench_f16(c: &mut Criterion) {
+    let mut group = c.benchmark_group("f32_to_f16");
+    for len in SIZES {
+        let src = input(len);
+        let mut dst = vec![f16::ZERO; len];
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("ggml", len), &src, |b, src| {
+            b.iter(|| fp16::f32_to_f16_slice(src, &mut dst))
+        });
+        group.bench_with_input(BenchmarkId::new("half", len), &src, |b, src| {
+            b.iter(|| dst.convert_from_f32_slice(src))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("f16_to_f32");
+    for len in SIZES {
+        let src = input(len)
+            .into_iter()
+            .map(f16::from_f32)
+            .collect::<Vec<_>>();
+        let mut dst = vec![0.0; len];
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("ggml", len), &src, |b, src| {
+            b.iter(|| fp16::f16_to_f32_slice(src, &mut dst))
+        });
+        group.bench_with_input(BenchmarkId::new("half", len), &src, |b, src| {
+            b.iter(|| src.convert_to_f32_slice(&mut dst))
+        });
+    }
+    group.finish();
+}
+
+fn bench_bf16(c: &mut Criterion) {
+    let mut group = c.benchmark_group("f32_to_bf16");
+    for len in SIZES {
+        let src = input(len);
+        let mut dst = vec![bf16::ZERO; len];
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("ggml", len), &src, |b, src| {
+            b.iter(|| fp16::f32_to_bf16_slice(src, &mut dst))
+        });
+        group.bench_with_input(BenchmarkId::new("half", len), &src, |b, src| {
+            b.iter(|| dst.convert_from_f32_slice(src))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("bf16_to_f32");
+    for len in SIZES {
+        let src = input(len)
+            .into_iter()
+            .map(bf16::from_f32)
+            .collect::<Vec<_>>();
+        let mut dst = vec![0.0; len];
+        group.throughput(Throughput::Elements(len as u64));
+        group.bench_with_input(BenchmarkId::new("ggml", len), &src, |b, src| {
+            b.iter(|| fp16::bf16_to_f32_slice(src, &mut dst))
+        });
+        group.bench_with_input(BenchmarkId::new("half", len), &src, |b, src| {
+            b.iter(|| src.convert_to_f32_slice(&mut dst))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_f16, bench_bf16);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+// Prints the round trip error of every quantization type, see `ggml_sys_bleedingedge::accuracy`.
+//
+// `cargo bench --bench quant_accuracy -- [--json] [model.gguf [tensor name filter]]`
+
+use ggml_sys_bleedingedge::accuracy::{report_types, Report};
+
+fn main() {
+    // `cargo bench` passes `--bench` to every bench binary.
+    let args = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--bench")
+        .collect::<Vec<_>>();
+    let json = args.iter().any(|arg| arg == "--json");
+    let mut args = args.iter().filter(|arg| !arg.starts_with("--"));
+    let (path, filter) = (args.next(), args.next());
+
+    let types = report_types();
+    let mut report = Report::new();
+    report
+        .add_distributions(4096, 64, &types)
+        .expect("Failed to measure reference distributions");
+    if let Some(path) = path {
+        report
+            .add_gguf(path, &types, |name| {
+                filter
+                    .as_ref()
+                    .map_or(true, |filter| name.contains(filter.as_str()))
+            })
+            .expect("Failed to measure GGUF tensors");
+    }
+
+    if json {
+        print!("{}", report.to_json());
+    } else {
+        print!("{report}");
+    }
+}
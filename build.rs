@@ -1,13 +1,95 @@
 // Build script and bindings generation modified from https://github.com/rustformers/llama-rs
 
-use std::{collections::HashSet, env, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
-const GGML_SOURCE_DIR: &str = "ggml-src";
+const GGML_SOURCE_DIR: &str = if cfg!(feature = "ggml_previous") {
+    "ggml-src-previous"
+} else {
+    "ggml-src"
+};
+const BINDINGS_FILE: &str = if cfg!(feature = "ggml_previous") {
+    "bindings_previous.rs"
+} else {
+    "bindings.rs"
+};
 const GGML_HEADER: &str = "ggml.h";
+const WHISPER_SOURCE_DIR: &str = "whisper-src";
+const WHISPER_HEADER: &str = "whisper.h";
+// The whisper.cpp release known to build against the GGML in `ggml-tag-current.txt`.
+const WHISPER_TAG_FILE: &str = "whisper-tag-current.txt";
+// wasi-libc only exposes these POSIX bits when explicitly opted into.
+const WASI_EMULATED_LIBS: &[&str] = &["process-clocks"];
 
-fn generate_bindings() {
-    let ggml_header_path = PathBuf::from(GGML_SOURCE_DIR).join(GGML_HEADER);
-    let librs_path = PathBuf::from("src").join("lib.rs");
+/// Which upstream project the sources in `GGML_SOURCE_DIR` come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceLayout {
+    /// `llama.cpp`: GGML headers and sources at the top level, `LLAMA_*` cmake options.
+    LlamaCpp,
+    /// The standalone `ggml` repo: `include/` and `src/` directories, `GGML_*` cmake options.
+    Ggml,
+}
+
+impl SourceLayout {
+    fn detect() -> Self {
+        if PathBuf::from(GGML_SOURCE_DIR)
+            .join("include")
+            .join(GGML_HEADER)
+            .is_file()
+        {
+            Self::Ggml
+        } else {
+            Self::LlamaCpp
+        }
+    }
+
+    fn include_dir(self) -> PathBuf {
+        match self {
+            Self::LlamaCpp => PathBuf::from(GGML_SOURCE_DIR),
+            Self::Ggml => PathBuf::from(GGML_SOURCE_DIR).join("include"),
+        }
+    }
+
+    fn source_dir(self) -> PathBuf {
+        match self {
+            Self::LlamaCpp => PathBuf::from(GGML_SOURCE_DIR),
+            Self::Ggml => PathBuf::from(GGML_SOURCE_DIR).join("src"),
+        }
+    }
+
+    /// Cmake option name for `option`, which uses the `llama.cpp` spelling without the prefix.
+    fn cmake_option(self, option: &str) -> String {
+        match self {
+            Self::LlamaCpp => format!("LLAMA_{option}"),
+            Self::Ggml if option == "CUBLAS" => String::from("GGML_CUDA"),
+            Self::Ggml => format!("GGML_{option}"),
+        }
+    }
+
+    fn cmake_target(self) -> &'static str {
+        match self {
+            Self::LlamaCpp => "ggml_static",
+            Self::Ggml => "ggml",
+        }
+    }
+
+    /// Where the static library ends up, relative to the cmake output directory.
+    fn cmake_lib_dir(self) -> PathBuf {
+        match self {
+            Self::LlamaCpp => PathBuf::from("build"),
+            Self::Ggml => PathBuf::from("build").join("src"),
+        }
+    }
+}
+
+fn generate_bindings(layout: SourceLayout) {
+    let ggml_header_path = layout.include_dir().join(GGML_HEADER);
+    let bindings_path = PathBuf::from("src").join(BINDINGS_FILE);
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
 
     let mut bbuilder = bindgen::Builder::default()
         .derive_copy(true)
@@ -30,23 +112,29 @@ fn generate_bindings() {
         .raw_line("pub const GGMLSYS_VERSION: Option<&str> = option_env!(\"CARGO_PKG_VERSION\");")
         // Do not generate code for ggml's includes (stdlib)
         .allowlist_file(ggml_header_path.to_string_lossy());
+    if target_os == "wasi" {
+        bbuilder = bbuilder.clang_arg(format!("--sysroot={}", wasi_sysroot().display()));
+    }
     if cfg!(feature = "use_cmake") {
         if cfg!(feature = "cublas") || cfg!(feature = "hipblas") {
-            let hfn = PathBuf::from(GGML_SOURCE_DIR).join("ggml-cuda.h");
+            let hfn = layout.include_dir().join("ggml-cuda.h");
             let hfn = hfn.to_string_lossy();
             bbuilder = bbuilder.header(hfn.clone()).allowlist_file(hfn);
         }
         if cfg!(feature = "clblast") {
-            let hfn = PathBuf::from(GGML_SOURCE_DIR).join("ggml-opencl.h");
+            let hfn = layout.include_dir().join("ggml-opencl.h");
             let hfn = hfn.to_string_lossy();
             bbuilder = bbuilder.header(hfn.clone()).allowlist_file(hfn);
         }
         if cfg!(feature = "metal") {
-            let hfn = PathBuf::from(GGML_SOURCE_DIR).join("ggml-metal.h");
+            let hfn = layout.include_dir().join("ggml-metal.h");
             let hfn = hfn.to_string_lossy();
             bbuilder = bbuilder.header(hfn.clone()).allowlist_file(hfn);
         }
         if cfg!(feature = "llamacpp_api") {
+            if layout != SourceLayout::LlamaCpp {
+                panic!("Feature llamacpp_api requires llama.cpp sources in {GGML_SOURCE_DIR}!");
+            }
             let hfn = PathBuf::from(GGML_SOURCE_DIR).join("llama.h");
             let hfn = hfn.to_string_lossy();
             bbuilder = bbuilder
@@ -54,11 +142,20 @@ fn generate_bindings() {
                 .allowlist_file(hfn)
                 .clang_args(["-x", "c++", "-std=c++11"]);
         }
+        if cfg!(feature = "whisper_api") {
+            let (whisper_include_dir, _) = whisper_dirs();
+            let hfn = whisper_include_dir.join(WHISPER_HEADER);
+            let hfn = hfn.to_string_lossy();
+            bbuilder = bbuilder
+                .header(hfn.clone())
+                .allowlist_file(hfn)
+                .clang_arg(format!("-I{}", layout.include_dir().display()));
+        }
     }
 
     let bindings = bbuilder.generate().expect("Unable to generate bindings");
     bindings
-        .write_to_file(librs_path)
+        .write_to_file(bindings_path)
         .expect("Couldn't write bindings");
 }
 
@@ -66,7 +163,10 @@ fn main() {
     // By default, this crate will attempt to compile ggml with the features of your host system if
     // the host and target are the same. If they are not, it will turn off auto-feature-detection,
     // and you will need to manually specify target features through target-features.
-    println!("cargo:rerun-if-changed=ggml-src");
+    println!("cargo:rerun-if-changed={GGML_SOURCE_DIR}");
+    if cfg!(feature = "whisper_api") {
+        println!("cargo:rerun-if-changed={WHISPER_SOURCE_DIR}");
+    }
 
     // If running on docs.rs, the filesystem is readonly so we can't actually generate
     // anything. This package should have been fetched with the bindings already generated
@@ -74,20 +174,28 @@ fn main() {
     if env::var("DOCS_RS").is_ok() {
         return;
     }
+    let layout = SourceLayout::detect();
     if cfg!(not(feature = "use_cmake")) {
-        return build_simple();
+        return build_simple(layout);
     }
-    build_cmake();
+    build_cmake(layout);
 }
 
-fn build_cmake() {
+fn build_cmake(layout: SourceLayout) {
     let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap();
+    let use_openmp = cfg!(not(feature = "no_openmp")) && target_os != "wasi";
 
-    generate_bindings();
+    generate_bindings(layout);
 
     // This silliness is necessary to get the cc crate to discover and
     // spit out the necessary stuff to link with C++ (and CUDA if enabled).
     let mut build = cc::Build::new();
+    if target_os == "wasi" {
+        build
+            .compiler(wasi_sdk_path().join("bin").join("clang++"))
+            .flag(format!("--sysroot={}", wasi_sysroot().display()));
+    }
     build.cpp(true).file("dummy/dummy.c");
 
     if cfg!(feature = "cublas") {
@@ -106,29 +214,38 @@ fn build_cmake() {
         None
     };
 
-    let mut cmbuild = cmake::Config::new("ggml-src");
-    cmbuild.build_target("ggml_static");
-    if cfg!(feature = "no_k_quants") {
-        cmbuild.define("LLAMA_K_QUANTS", "OFF");
+    let mut cmbuild = cmake::Config::new(GGML_SOURCE_DIR);
+    cmbuild.build_target(layout.cmake_target());
+    cmbuild.define(
+        layout.cmake_option("OPENMP"),
+        if use_openmp { "ON" } else { "OFF" },
+    );
+    if layout == SourceLayout::Ggml {
+        cmbuild.define("BUILD_SHARED_LIBS", "OFF");
+        cmbuild.define("GGML_BUILD_TESTS", "OFF");
+        cmbuild.define("GGML_BUILD_EXAMPLES", "OFF");
+    }
+    if cfg!(feature = "no_k_quants") && layout == SourceLayout::LlamaCpp {
+        cmbuild.define(layout.cmake_option("K_QUANTS"), "OFF");
     }
     if cfg!(feature = "cublas") {
-        cmbuild.define("LLAMA_CUBLAS", "ON");
+        cmbuild.define(layout.cmake_option("CUBLAS"), "ON");
     } else if cfg!(feature = "hipblas") {
         let rocm_path = rocm_path.as_ref().expect("Impossible: rocm_path not set!");
         let rocm_llvm_path = rocm_path.join("llvm").join("bin");
-        cmbuild.define("LLAMA_HIPBLAS", "ON");
+        cmbuild.define(layout.cmake_option("HIPBLAS"), "ON");
         cmbuild.define("CMAKE_PREFIX_PATH", rocm_path);
         cmbuild.define("CMAKE_C_COMPILER", rocm_llvm_path.join("clang"));
         cmbuild.define("CMAKE_CXX_COMPILER", rocm_llvm_path.join("clang++"));
     } else if cfg!(feature = "clblast") {
-        cmbuild.define("LLAMA_CLBLAST", "ON");
+        cmbuild.define(layout.cmake_option("CLBLAST"), "ON");
     } else if cfg!(feature = "openblas") {
-        cmbuild.define("LLAMA_BLAS", "ON");
-        cmbuild.define("LLAMA_BLAS_VENDOR", "OpenBLAS");
+        cmbuild.define(layout.cmake_option("BLAS"), "ON");
+        cmbuild.define(layout.cmake_option("BLAS_VENDOR"), "OpenBLAS");
     }
     if target_os == "macos" {
         cmbuild.define(
-            "LLAMA_ACCELERATE",
+            layout.cmake_option("ACCELERATE"),
             if cfg!(feature = "no_accelerate") {
                 "OFF"
             } else {
@@ -136,9 +253,25 @@ fn build_cmake() {
             },
         );
         cmbuild.define(
-            "LLAMA_METAL",
+            layout.cmake_option("METAL"),
             if cfg!(feature = "metal") { "ON" } else { "OFF" },
         );
+    } else if target_os == "wasi" {
+        let wasi_sdk_path = wasi_sdk_path();
+        cmbuild.define(
+            "CMAKE_TOOLCHAIN_FILE",
+            wasi_sdk_path
+                .join("share")
+                .join("cmake")
+                .join("wasi-sdk.cmake"),
+        );
+        cmbuild.define("WASI_SDK_PREFIX", &wasi_sdk_path);
+        cmbuild.define(layout.cmake_option("NATIVE"), "OFF");
+        cmbuild.cflag("-msimd128").cxxflag("-msimd128");
+        WASI_EMULATED_LIBS.iter().for_each(|lib| {
+            let def = format!("-D_WASI_EMULATED_{}", lib.replace('-', "_").to_uppercase());
+            cmbuild.cflag(&def).cxxflag(&def);
+        });
     }
     let dst = cmbuild.build();
     if cfg!(feature = "cublas") {
@@ -152,15 +285,18 @@ fn build_cmake() {
         println!("cargo:rustc-link-lib=hipblas");
         println!("cargo:rustc-link-lib=amdhip64");
         println!("cargo:rustc-link-lib=rocblas");
-        let mut build = cc::Build::new();
-        build.cpp(true).file("dummy/dummy.c").object(
-            PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set!"))
-                .join("build")
-                .join("CMakeFiles")
-                .join("ggml-rocm.dir")
-                .join("ggml-cuda.cu.o"),
-        );
-        build.compile("dummy");
+        // llama.cpp builds the ROCM kernels as a separate object library.
+        if layout == SourceLayout::LlamaCpp {
+            let mut build = cc::Build::new();
+            build.cpp(true).file("dummy/dummy.c").object(
+                PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR not set!"))
+                    .join("build")
+                    .join("CMakeFiles")
+                    .join("ggml-rocm.dir")
+                    .join("ggml-cuda.cu.o"),
+            );
+            build.compile("dummy");
+        }
     } else if cfg!(feature = "clblast") {
         println!("cargo:rustc-link-lib=clblast");
         println!(
@@ -185,35 +321,132 @@ fn build_cmake() {
             println!("cargo:rustc-link-lib=framework=MetalPerformanceShaders");
         }
     }
-    println!("cargo:rustc-link-search=native={}/build", dst.display());
-    println!("cargo:rustc-link-lib=static=ggml_static");
+    if target_os == "wasi" {
+        WASI_EMULATED_LIBS.iter().for_each(|lib| {
+            println!("cargo:rustc-link-lib=wasi-emulated-{lib}");
+        });
+    }
+    // OpenMP is only asked for: GGML falls back to its own threads when CMake can't find it
+    // (i.e. with Apple's clang).
+    let openmp_libs = if use_openmp {
+        cmake_openmp(&dst.join("build"))
+    } else {
+        None
+    };
+    // MSVC pulls in its OpenMP runtime by itself.
+    if target_env != "msvc" {
+        for lib in openmp_libs.iter().flatten() {
+            println!("cargo:rustc-link-lib={lib}");
+        }
+    }
+    report_threading(&target_os, openmp_libs.is_some());
+    // This has to come before linking GGML since whisper.cpp depends on it.
+    if cfg!(feature = "whisper_api") {
+        build_whisper(layout);
+    }
+    println!(
+        "cargo:rustc-link-search=native={}",
+        dst.join(layout.cmake_lib_dir()).display()
+    );
+    println!("cargo:rustc-link-lib=static={}", layout.cmake_target());
+}
+
+/// Returns the include and source directories for whisper.cpp, which moved into `include/` and
+/// `src/` like the standalone `ggml` repo at some point.
+fn whisper_dirs() -> (PathBuf, PathBuf) {
+    let whisper_dir = PathBuf::from(WHISPER_SOURCE_DIR);
+    if whisper_dir.join("include").join(WHISPER_HEADER).is_file() {
+        (whisper_dir.join("include"), whisper_dir.join("src"))
+    } else {
+        (whisper_dir.clone(), whisper_dir)
+    }
+}
+
+/// Warns if `whisper-src` is a git checkout of some other revision than the pinned release,
+/// since whisper.cpp isn't built against the GGML it was released with.
+fn check_whisper_pin() {
+    println!("cargo:rerun-if-changed={WHISPER_TAG_FILE}");
+    let Ok(pin) = std::fs::read_to_string(WHISPER_TAG_FILE) else {
+        return;
+    };
+    let output = Command::new("git")
+        .args(["describe", "--tags", "--exact-match"])
+        .current_dir(WHISPER_SOURCE_DIR)
+        .output();
+    let tag = match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        Ok(_) => String::from("an untagged revision"),
+        // Not a git checkout (i.e. the packaged crate).
+        Err(_) => return,
+    };
+    if tag.trim() != pin.trim() {
+        println!(
+            "cargo:warning={WHISPER_SOURCE_DIR} is at {}, not {} from {WHISPER_TAG_FILE}",
+            tag.trim(),
+            pin.trim()
+        );
+    }
 }
 
-fn build_simple() {
+/// Builds only `whisper.cpp` itself: it gets compiled against the same GGML as everything else
+/// rather than the copy vendored in whisper.cpp.
+fn build_whisper(layout: SourceLayout) {
+    check_whisper_pin();
+    let (whisper_include_dir, whisper_source_dir) = whisper_dirs();
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .std("c++11")
+        .file(whisper_source_dir.join("whisper.cpp"))
+        .include(&whisper_include_dir)
+        .include(&whisper_source_dir)
+        .include(layout.include_dir());
+    if cfg!(feature = "cublas") || cfg!(feature = "hipblas") {
+        build.define("GGML_USE_CUDA", None);
+    } else if cfg!(feature = "metal") {
+        build.define("GGML_USE_METAL", None);
+    }
+    if env::var("PROFILE").unwrap() == "release" {
+        build.define("NDEBUG", None);
+    }
+    build.warnings(false);
+    build.compile("whisper");
+}
+
+fn build_simple(layout: SourceLayout) {
     if cfg!(feature = "cublas") || cfg!(feature = "clblast") || cfg!(feature = "hipblas") {
         panic!("Must build with feature use_cmake when enabling BLAS!");
     }
-    generate_bindings();
+    generate_bindings(layout);
+
+    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let is_release = env::var("PROFILE").unwrap() == "release";
 
     let mut builder = cc::Build::new();
+    if target_os == "wasi" {
+        builder
+            .compiler(wasi_sdk_path().join("bin").join("clang"))
+            .flag(format!("--sysroot={}", wasi_sysroot().display()));
+    }
     let build = builder
         .files([
-            PathBuf::from(GGML_SOURCE_DIR).join("ggml.c"),
-            PathBuf::from(GGML_SOURCE_DIR).join("ggml-alloc.c"),
-            PathBuf::from(GGML_SOURCE_DIR).join("ggml-backend.c"),
+            layout.source_dir().join("ggml.c"),
+            layout.source_dir().join("ggml-alloc.c"),
+            layout.source_dir().join("ggml-backend.c"),
             #[cfg(not(feature = "no_k_quants"))]
-            PathBuf::from(GGML_SOURCE_DIR).join("ggml-quants.c"),
+            layout.source_dir().join("ggml-quants.c"),
         ])
-        .include(PathBuf::from(GGML_SOURCE_DIR))
+        .include(layout.include_dir())
+        .include(layout.source_dir())
         .include("include");
     #[cfg(not(feature = "no_k_quants"))]
     build.define("GGML_USE_K_QUANTS", None);
 
     // This is a very basic heuristic for applying compile flags.
     // Feel free to update this to fit your operating system.
-    let target_arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
-    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
-    let is_release = env::var("PROFILE").unwrap() == "release";
     let compiler = build.get_compiler();
 
     match target_arch.as_str() {
@@ -245,9 +478,23 @@ fn build_simple() {
                 build.flag("-pthread");
             }
         }
+        "wasm32" => {
+            // No -pthread here: wasm32-wasip1 is single threaded, so only SIMD is enabled.
+            build.flag("-msimd128");
+        }
         _ => (),
     }
 
+    if &target_os == "wasi" {
+        WASI_EMULATED_LIBS.iter().for_each(|lib| {
+            build.define(
+                &format!("_WASI_EMULATED_{}", lib.replace('-', "_").to_uppercase()),
+                None,
+            );
+            println!("cargo:rustc-link-lib=wasi-emulated-{lib}");
+        });
+    }
+
     if &target_os == "macos" {
         build.define("GGML_USE_ACCELERATE", None);
         println!("cargo:rustc-link-lib=framework=Accelerate");
@@ -258,6 +505,78 @@ fn build_simple() {
     }
     build.warnings(false);
     build.compile(GGML_SOURCE_DIR);
+    report_threading(&target_os, false);
+}
+
+/// Whether CMake found OpenMP according to the cache in `build_dir` and if so, the libraries
+/// it uses for C.
+fn cmake_openmp(build_dir: &Path) -> Option<Vec<String>> {
+    let cache = std::fs::read_to_string(build_dir.join("CMakeCache.txt")).ok()?;
+    let vars = cache
+        .lines()
+        .filter(|line| !line.starts_with(['#', '/']))
+        .filter_map(|line| {
+            let (key, val) = line.split_once('=')?;
+            Some((key.split(':').next()?, val.trim()))
+        })
+        .collect::<HashMap<_, _>>();
+    // CMake's truthy values, anything else (including `*-NOTFOUND`) is false.
+    let truthy = |val: &str| {
+        ["1", "ON", "YES", "TRUE", "Y"].contains(&val.to_uppercase().as_str())
+            || val.parse::<f64>().is_ok_and(|val| val != 0.0)
+    };
+    let found = match ["OpenMP_C_FOUND", "OpenMP_FOUND"]
+        .iter()
+        .find_map(|key| vars.get(key))
+    {
+        Some(val) => truthy(val),
+        // `FindOpenMP` only caches the flags, which are `NOTFOUND` if it failed.
+        None => vars
+            .get("OpenMP_C_FLAGS")
+            .is_some_and(|flags| !flags.is_empty() && !flags.ends_with("NOTFOUND")),
+    };
+    if !found {
+        return None;
+    }
+    let libs = vars
+        .get("OpenMP_C_LIB_NAMES")
+        .map(|names| {
+            names
+                .split(';')
+                .filter(|name| !name.is_empty() && !name.ends_with("NOTFOUND"))
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .filter(|libs| !libs.is_empty());
+    let target_os = env::var("CARGO_CFG_TARGET_OS").unwrap();
+    Some(libs.unwrap_or_else(|| {
+        vec![String::from(if target_os == "macos" {
+            "omp"
+        } else {
+            "gomp"
+        })]
+    }))
+}
+
+/// Makes the threading backend GGML was built with available as `GGMLSYS_THREADING`.
+fn report_threading(target_os: &str, use_openmp: bool) {
+    let threading = if use_openmp {
+        "openmp"
+    } else if target_os == "wasi" {
+        "none"
+    } else {
+        "pthreads"
+    };
+    println!("cargo:rustc-env=GGMLSYS_THREADING={threading}");
+}
+
+fn wasi_sdk_path() -> PathBuf {
+    println!("cargo:rerun-if-env-changed=WASI_SDK_PATH");
+    PathBuf::from(env::var("WASI_SDK_PATH").unwrap_or_else(|_| String::from("/opt/wasi-sdk")))
+}
+
+fn wasi_sysroot() -> PathBuf {
+    wasi_sdk_path().join("share").join("wasi-sysroot")
 }
 
 fn get_supported_target_features() -> HashSet<String> {
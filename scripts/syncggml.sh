@@ -1,5 +1,30 @@
 #!/bin/bash
 set -euo pipefail
+
+# Regenerates src/bindings.rs from ggml-src and src/bindings_previous.rs from ggml-src-previous
+# (the build script writes them), making sure neither is left stale or empty.
+regenerate_bindings() {
+  rm -f src/bindings.rs src/bindings_previous.rs
+  cargo build
+  cargo build --features ggml_previous
+  for BINDINGS in src/bindings.rs src/bindings_previous.rs; do
+    if ! test -s "$BINDINGS"; then
+      echo "Failed to generate $BINDINGS" >&2
+      exit 1
+    fi
+  done
+}
+
+# Only regenerate the bindings for the releases in ggml-tag-current.txt and ggml-tag-previous.txt.
+if test "${1:-}" = "bindings"; then
+  git submodule update --init && \
+    ( cd ggml-src && git checkout "$(cat ../ggml-tag-current.txt)" ) && \
+    ( cd ggml-src-previous && git checkout "$(cat ../ggml-tag-previous.txt)" ) && \
+    ( cd whisper-src && git checkout "$(cat ../whisper-tag-current.txt)" )
+  regenerate_bindings
+  exit 0
+fi
+
 LATEST_GGML_RELEASE=$(curl -sL https://api.github.com/repos/ggerganov/llama.cpp/releases/latest | jq -er '.tag_name')
 OUR_GGML_RELEASE=$(cat ./ggml-tag-current.txt 2>/dev/null || echo -n 'xx-no-current-release')
 
@@ -25,7 +50,9 @@ if test "${1:-}" = "only-check"; then
 fi
 echo "New release tag. Latest [${LATEST_GGML_RELEASE}], ours: [${OUR_GGML_RELEASE}]"
 git submodule update --init && \
-  ( cd ggml-src && git checkout "$LATEST_GGML_RELEASE" )
+  ( cd ggml-src && git checkout "$LATEST_GGML_RELEASE" ) && \
+  ( cd ggml-src-previous && git checkout "$OUR_GGML_RELEASE" ) && \
+  ( cd whisper-src && git checkout "$(cat ../whisper-tag-current.txt)" )
 
 ( cd ggml-src && \
   git log "${OUR_GGML_RELEASE}..${LATEST_GGML_RELEASE}" -- \
@@ -45,22 +72,29 @@ VERSION="${STAMP}.0.0+llamacpp-release.${LATEST_GGML_RELEASE}"
 
 sed -i "s@^version =.*@version = \"${VERSION}\"@" ./Cargo.toml
 
-if ! test -f src/lib.rs; then
-  mkdir -p src/
-  touch src/lib.rs
-fi
+regenerate_bindings
 
-# Make sure it actually builds.
+# Make sure it actually builds. The default features are built last so the committed bindings
+# include the llama.cpp API.
+cargo clean
 cargo build --no-default-features
 cargo test --no-default-features
 cargo clean
+cargo build --features ggml_previous
+cargo test --features ggml_previous
+cargo clean
+# whisper.cpp is pinned in whisper-tag-current.txt but built against the new GGML, so a sync
+# that breaks it fails here. Bump the pin to a whisper.cpp release that has synced GGML.
+cargo build --features whisper_api
+cargo test --features whisper_api
+cargo clean
 cargo build
 cargo test
 
 echo "$VERSION" > ./VERSION.txt
 echo "$OUR_GGML_RELEASE" > ./ggml-tag-previous.txt
 echo "$LATEST_GGML_RELEASE" > ./ggml-tag-current.txt
-git add Cargo.toml VERSION.txt ggml-tag-current.txt ggml-tag-previous.txt src/lib.rs
+git add Cargo.toml VERSION.txt ggml-tag-current.txt ggml-tag-previous.txt src/bindings.rs src/bindings_previous.rs
 git config user.name github-actions
 git config user.email github-actions@github.com
 ( echo -e "[auto] Sync version ${VERSION}\n\n== Relevant log messages from source repo:\n" ; \
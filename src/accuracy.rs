@@ -0,0 +1,371 @@
+//! Measuring how much precision is lost by quantizing.
+//!
+//! [`measure`] quantizes data to a type and back and compares the result with the original.
+//! A [`Report`] does that for a list of types (by default [`report_types`], every type with
+//! `from_float` and `to_float`) with samples of reference [`Distribution`]s and tensors from
+//! GGUF files, and formats the results as a table or JSON. Types that can't quantize without
+//! an importance matrix get one from [`synthetic_imatrix`].
+//!
+//! `cargo bench --bench quant_accuracy` prints a report for the reference distributions, pass
+//! `-- model.gguf` to include a model's tensors and `-- --json` for JSON.
+
+use std::{
+    fmt::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    context,
+    dump::json_string,
+    error::{Error, Result},
+    gguf::GgufFile,
+    quant::{can_quantize, check_blocks, type_name, type_traits, QuantizedBuffer},
+    *,
+};
+
+/// Types [`measure`] works with: those with both `from_float` and `to_float`, plus F32.
+pub fn report_types() -> Vec<ggml_type> {
+    (0..ggml_type_GGML_TYPE_COUNT)
+        .filter(|type_| match type_traits(*type_) {
+            Some(traits) => {
+                *type_ == ggml_type_GGML_TYPE_F32
+                    || (traits.from_float.is_some() && traits.to_float.is_some())
+            }
+            None => false,
+        })
+        .collect()
+}
+
+/// An importance matrix for `src` (rows of `n_per_row` elements) in place of one collected
+/// from activations: the mean square of each column, so large weights are given more weight.
+pub fn synthetic_imatrix(src: &[f32], n_per_row: usize) -> Vec<f32> {
+    let mut imatrix = vec![0.0; n_per_row];
+    let nrows = src.len() / n_per_row.max(1);
+    for row in src.chunks_exact(n_per_row) {
+        for (sum, x) in imatrix.iter_mut().zip(row) {
+            *sum += x * x;
+        }
+    }
+    for sum in &mut imatrix {
+        // Quantizers for the IQ types don't cope with all zero weights.
+        *sum = (*sum / nrows.max(1) as f32).max(1e-6);
+    }
+    imatrix
+}
+
+/// Quantizes `src` to `type_` and converts it back to `f32`. Types [`quantize`] supports go
+/// through it (and so are validated and can use `imatrix`), others through their
+/// `from_float` directly.
+pub fn round_trip(
+    src: &[f32],
+    shape: &[i64],
+    type_: ggml_type,
+    imatrix: Option<&[f32]>,
+) -> Result<Vec<f32>> {
+    if can_quantize(type_) {
+        return Ok(dequantize(&quantize(src, shape, type_, imatrix)?));
+    }
+    let traits = type_traits(type_).ok_or(Error::UnsupportedType(type_))?;
+    let (Some(from_float), Some(to_float)) = (traits.from_float, traits.to_float) else {
+        return Err(Error::UnsupportedType(type_));
+    };
+    let ne = check_blocks(type_, shape)?;
+    let nelements = ne.iter().product::<i64>() as usize;
+    if src.len() != nelements {
+        return Err(Error::LengthMismatch {
+            expected: nelements,
+            actual: src.len(),
+        });
+    }
+    let nbytes = unsafe { ggml_row_size(type_, ne[0]) } * (nelements / ne[0].max(1) as usize);
+    let mut data = vec![0u64; nbytes.div_ceil(std::mem::size_of::<u64>())];
+    let mut dst = vec![0.0; nelements];
+    context::init_tables();
+    unsafe {
+        from_float(src.as_ptr(), data.as_mut_ptr().cast(), nelements as i64);
+        to_float(data.as_ptr().cast(), dst.as_mut_ptr(), nelements as i64);
+    }
+    Ok(dst)
+}
+
+/// How close a round trip through a type got to the original data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub type_: ggml_type,
+    /// Storage cost from `ggml_type_sizef`, including block scales.
+    pub bits_per_weight: f64,
+    pub rmse: f64,
+    /// Largest absolute difference of any element.
+    pub max_error: f64,
+    /// Cosine similarity of the data before and after, as a whole.
+    pub cosine: f64,
+}
+
+impl Stats {
+    /// Compares `original` with the result of a round trip through `type_`.
+    pub fn compare(type_: ggml_type, original: &[f32], round_trip: &[f32]) -> Self {
+        assert_eq!(
+            original.len(),
+            round_trip.len(),
+            "Original and round trip data have different lengths"
+        );
+        let (mut sum_sq, mut max_error) = (0.0, 0.0f64);
+        let (mut dot, mut norm_a, mut norm_b) = (0.0, 0.0, 0.0);
+        for (a, b) in original.iter().zip(round_trip) {
+            let (a, b) = (*a as f64, *b as f64);
+            sum_sq += (a - b) * (a - b);
+            max_error = max_error.max((a - b).abs());
+            dot += a * b;
+            norm_a += a * a;
+            norm_b += b * b;
+        }
+        let cosine = if norm_a == 0.0 && norm_b == 0.0 {
+            1.0
+        } else {
+            dot / (norm_a.sqrt() * norm_b.sqrt())
+        };
+        Self {
+            type_,
+            bits_per_weight: unsafe { ggml_type_sizef(type_) } * 8.0,
+            rmse: (sum_sq / original.len().max(1) as f64).sqrt(),
+            max_error,
+            cosine,
+        }
+    }
+}
+
+/// Measures a round trip of `src` through `type_`, see [`round_trip`].
+pub fn measure(
+    src: &[f32],
+    shape: &[i64],
+    type_: ggml_type,
+    imatrix: Option<&[f32]>,
+) -> Result<Stats> {
+    let result = round_trip(src, shape, type_, imatrix)?;
+    Ok(Stats::compare(type_, src, &result))
+}
+
+/// Reference weight distributions, sampled with a fixed seed so reports are reproducible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniform between -1 and 1.
+    Uniform,
+    /// Standard normal.
+    Normal,
+    /// Laplace with unit scale, which has heavier tails than the normal distribution.
+    Laplace,
+    /// Standard normal with one in a hundred values scaled by 20, like the outliers found in
+    /// some LLM weights.
+    Outliers,
+}
+
+impl Distribution {
+    pub const ALL: [Self; 4] = [Self::Uniform, Self::Normal, Self::Laplace, Self::Outliers];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Uniform => "uniform",
+            Self::Normal => "normal",
+            Self::Laplace => "laplace",
+            Self::Outliers => "outliers",
+        }
+    }
+
+    /// `len` values drawn from the distribution.
+    pub fn sample(&self, len: usize, seed: u64) -> Vec<f32> {
+        let mut rng = SplitMix64(seed);
+        (0..len)
+            .map(|_| match self {
+                Self::Uniform => rng.next_f64() * 2.0 - 1.0,
+                Self::Normal => rng.next_normal(),
+                Self::Laplace => {
+                    let u = rng.next_f64() - 0.5;
+                    -u.signum() * (1.0 - 2.0 * u.abs()).max(f64::MIN_POSITIVE).ln()
+                }
+                Self::Outliers => {
+                    let scale = if rng.next_f64() < 0.01 { 20.0 } else { 1.0 };
+                    rng.next_normal() * scale
+                }
+            } as f32)
+            .collect()
+    }
+}
+
+/// Small deterministic generator so sampling doesn't need a dependency.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Box-Muller.
+    fn next_normal(&mut self) -> f64 {
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+        (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    }
+}
+
+/// One line of a [`Report`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The distribution or tensor name the data came from.
+    pub source: String,
+    pub stats: Stats,
+}
+
+/// Round trip statistics for a number of types and data sources.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Report {
+    pub entries: Vec<Entry>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Measures `src` with each of `types`, giving types that need one a
+    /// [`synthetic_imatrix`]. Types that `src` can't be quantized to because its rows aren't
+    /// a multiple of their block size are skipped.
+    pub fn add(
+        &mut self,
+        source: &str,
+        src: &[f32],
+        shape: &[i64],
+        types: &[ggml_type],
+    ) -> Result<()> {
+        let n_per_row = shape.first().copied().unwrap_or(0).max(0) as usize;
+        let mut imatrix = None;
+        for &type_ in types {
+            let needs_imatrix = unsafe { ggml_quantize_requires_imatrix(type_) };
+            if needs_imatrix && imatrix.is_none() {
+                imatrix = Some(synthetic_imatrix(src, n_per_row));
+            }
+            let imatrix = imatrix.as_deref().filter(|_| needs_imatrix);
+            match measure(src, shape, type_, imatrix) {
+                Ok(stats) => self.entries.push(Entry {
+                    source: source.to_owned(),
+                    stats,
+                }),
+                Err(Error::InvalidShape { .. }) => continue,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds `nrows` rows of `n_per_row` values from each of [`Distribution::ALL`].
+    pub fn add_distributions(
+        &mut self,
+        n_per_row: usize,
+        nrows: usize,
+        types: &[ggml_type],
+    ) -> Result<()> {
+        for (seed, dist) in Distribution::ALL.iter().enumerate() {
+            let src = dist.sample(n_per_row * nrows, seed as u64);
+            self.add(dist.name(), &src, &[n_per_row as i64, nrows as i64], types)?;
+        }
+        Ok(())
+    }
+
+    /// Adds the tensors in the GGUF file at `path` for which `filter` returns `true` when
+    /// called with the tensor name. Tensors stored as a type [`dequantize`] can't read (i.e.
+    /// integers) are skipped, as are ones with NaN or infinite values.
+    pub fn add_gguf(
+        &mut self,
+        path: impl AsRef<Path>,
+        types: &[ggml_type],
+        mut filter: impl FnMut(&str) -> bool,
+    ) -> Result<()> {
+        let file = GgufFile::open(path.as_ref())?;
+        for (name, tensor) in file.tensors() {
+            if !filter(&name) || !can_quantize(tensor.type_()) {
+                continue;
+            }
+            let data = unsafe {
+                std::slice::from_raw_parts(tensor.data_ptr().cast::<u8>(), tensor.nbytes())
+            };
+            let src = match QuantizedBuffer::from_bytes(tensor.type_(), tensor.shape(), data) {
+                Ok(buffer) => dequantize(&buffer),
+                Err(Error::InvalidData(_)) => continue,
+                Err(err) => return Err(err),
+            };
+            self.add(&name, &src, tensor.shape(), types)?;
+        }
+        Ok(())
+    }
+
+    /// The report as a plain text table.
+    pub fn to_table(&self) -> String {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.source.len())
+            .chain(Some(6))
+            .max()
+            .unwrap_or(6);
+        let mut out = format!(
+            "{:width$}  {:8}  {:>6}  {:>12}  {:>12}  {:>10}\n",
+            "source", "type", "bpw", "rmse", "max error", "cosine"
+        );
+        for Entry { source, stats } in &self.entries {
+            let _ = writeln!(
+                out,
+                "{source:width$}  {:8}  {:>6.3}  {:>12.6e}  {:>12.6e}  {:>10.6}",
+                type_name(stats.type_),
+                stats.bits_per_weight,
+                stats.rmse,
+                stats.max_error,
+                stats.cosine,
+            );
+        }
+        out
+    }
+
+    /// The report as a JSON array with one object per entry.
+    pub fn to_json(&self) -> String {
+        // Non-finite numbers (i.e. the cosine of all zero data) aren't valid JSON.
+        fn number(val: f64) -> String {
+            if val.is_finite() {
+                format!("{val}")
+            } else {
+                "null".to_owned()
+            }
+        }
+
+        let mut out = String::from("[");
+        for (i, Entry { source, stats }) in self.entries.iter().enumerate() {
+            let _ = write!(
+                out,
+                "{}\n  {{\"source\": {}, \"type\": {}, \"bits_per_weight\": {}, \
+                 \"rmse\": {}, \"max_error\": {}, \"cosine\": {}}}",
+                if i == 0 { "" } else { "," },
+                json_string(source),
+                json_string(type_name(stats.type_)),
+                number(stats.bits_per_weight),
+                number(stats.rmse),
+                number(stats.max_error),
+                number(stats.cosine),
+            );
+        }
+        out.push_str("\n]\n");
+        out
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
@@ -0,0 +1,172 @@
+//! Conversions between tensors and [`ndarray`] arrays.
+//!
+//! GGML lists dimensions fastest changing first (`ne[0]` is the length of a row) while
+//! `ndarray`'s default layout has the last axis changing fastest, so axes are reversed in both
+//! directions: a tensor with `ne = [4, 3]` is a `3x4` array and indexing `[i, j]` reads
+//! `ne[1]` index `i` and `ne[0]` index `j`.
+
+use ndarray::{ArrayBase, ArrayViewD, ArrayViewMutD, Data, Dimension, IxDyn, ShapeBuilder};
+
+use crate::{
+    context::Context,
+    error::{Error, Result},
+    shape::full_ne,
+    tensor::{DataBorrow, Tensor, TensorElement},
+    GGML_MAX_DIMS,
+};
+
+/// Tensor data borrowed by [`Tensor::as_array`], see [`TensorSlice`](crate::TensorSlice).
+pub struct TensorArray<'a, T> {
+    array: ArrayViewD<'a, T>,
+    _borrow: DataBorrow,
+}
+
+impl<T> TensorArray<'_, T> {
+    /// The borrowed data. Views can't outlive `self`, so the borrow covers them.
+    pub fn view(&self) -> ArrayViewD<'_, T> {
+        self.array.view()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for TensorArray<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.array.fmt(f)
+    }
+}
+
+/// Tensor data mutably borrowed by [`Tensor::as_array_mut`]. As long as it's alive, anything
+/// else reading or writing the same memory fails with [`Error::DataBorrowed`] instead.
+pub struct TensorArrayMut<'a, T> {
+    array: ArrayViewMutD<'a, T>,
+    _borrow: DataBorrow,
+}
+
+impl<T> TensorArrayMut<'_, T> {
+    /// The borrowed data, see [`TensorArray::view`].
+    pub fn view(&self) -> ArrayViewD<'_, T> {
+        self.array.view()
+    }
+
+    /// The borrowed data, which can be written through.
+    pub fn view_mut(&mut self) -> ArrayViewMutD<'_, T> {
+        self.array.view_mut()
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for TensorArrayMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.array.fmt(f)
+    }
+}
+
+impl<'ctx> Tensor<'ctx> {
+    /// Array shape and element strides along with the number of elements between the first
+    /// and last element, inclusive.
+    fn array_layout<T: TensorElement>(&self) -> Result<(IxDyn, IxDyn, usize)> {
+        if self.type_() != T::TYPE {
+            return Err(Error::TypeMismatch {
+                expected: T::TYPE,
+                actual: self.type_(),
+            });
+        }
+        if self.data_ptr().is_null() {
+            return Err(Error::NoData);
+        }
+        let n_dims = self.n_dims();
+        let size = std::mem::size_of::<T>();
+        let (ne, nb) = (self.ne(), self.nb());
+        if nb[..n_dims].iter().any(|nb| nb % size != 0) {
+            return Err(Error::NotContiguous);
+        }
+        let shape = ne[..n_dims].iter().rev().map(|ne| *ne as usize);
+        let strides = nb[..n_dims].iter().rev().map(|nb| nb / size);
+        let len = if ne.contains(&0) {
+            0
+        } else {
+            shape
+                .clone()
+                .zip(strides.clone())
+                .map(|(ne, st)| (ne - 1) * st)
+                .sum::<usize>()
+                + 1
+        };
+        Ok((
+            IxDyn(&shape.collect::<Vec<_>>()),
+            IxDyn(&strides.collect::<Vec<_>>()),
+            len,
+        ))
+    }
+
+    /// Borrows the tensor data as an array without copying. Strided tensors (i.e. transposes
+    /// and other views) work as long as the strides are a multiple of the element size and
+    /// don't overlap, otherwise this fails with [`Error::NotContiguous`].
+    pub fn as_array<T: TensorElement>(&self) -> Result<TensorArray<'_, T>> {
+        let (shape, strides, len) = self.array_layout::<T>()?;
+        let data = unsafe { std::slice::from_raw_parts(self.data_ptr().cast::<T>(), len) };
+        let array = ArrayViewD::from_shape(shape.strides(strides), data)
+            .map_err(|_| Error::NotContiguous)?;
+        let start = self.data_ptr() as usize;
+        Ok(TensorArray {
+            array,
+            _borrow: DataBorrow::new(start..start + len * std::mem::size_of::<T>())?,
+        })
+    }
+
+    /// Mutably borrows the tensor data as an array, see [`Tensor::as_array`]. Tensor handles
+    /// are `Copy`, so this fails with [`Error::DataBorrowed`] if the data is borrowed already,
+    /// and other borrows of it fail as long as the result is alive.
+    pub fn as_array_mut<T: TensorElement>(&self) -> Result<TensorArrayMut<'_, T>> {
+        let (shape, strides, len) = self.array_layout::<T>()?;
+        let start = self.data_ptr() as usize;
+        let borrow = DataBorrow::new_mut(start..start + len * std::mem::size_of::<T>())?;
+        // Nothing else can read or write the data while `borrow` is alive.
+        let data = unsafe { std::slice::from_raw_parts_mut(self.data_ptr().cast::<T>(), len) };
+        let array = ArrayViewMutD::from_shape(shape.strides(strides), data)
+            .map_err(|_| Error::NotContiguous)?;
+        Ok(TensorArrayMut {
+            array,
+            _borrow: borrow,
+        })
+    }
+
+    /// Copies `array` into the tensor, which must be contiguous, of type `T` and have the
+    /// same shape with the axes reversed.
+    pub fn copy_from_array<T, S, D>(&self, array: &ArrayBase<S, D>) -> Result<()>
+    where
+        T: TensorElement,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+        let shape = array_ne(array);
+        if shape.len() > GGML_MAX_DIMS as usize || full_ne(&shape) != self.ne() {
+            return Err(Error::InvalidShape {
+                type_: self.type_(),
+                shape,
+            });
+        }
+        match array.as_slice() {
+            Some(data) => self.copy_from_slice(data),
+            None => self.copy_from_slice(&array.iter().copied().collect::<Vec<_>>()),
+        }
+    }
+}
+
+/// The `ne` of a tensor matching `array`'s shape.
+fn array_ne<S: Data, D: Dimension>(array: &ArrayBase<S, D>) -> Vec<i64> {
+    array.shape().iter().rev().map(|len| *len as i64).collect()
+}
+
+impl Context {
+    /// Creates a tensor with the same shape as `array` (axes reversed) and copies the array's
+    /// elements into it. The array must have between one and `GGML_MAX_DIMS` axes.
+    pub fn new_tensor_from_array<T, S, D>(&self, array: &ArrayBase<S, D>) -> Result<Tensor<'_>>
+    where
+        T: TensorElement,
+        S: Data<Elem = T>,
+        D: Dimension,
+    {
+        let tensor = self.new_tensor(T::TYPE, &array_ne(array))?;
+        tensor.copy_from_array(array)?;
+        Ok(tensor)
+    }
+}
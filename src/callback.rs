@@ -0,0 +1,253 @@
+//! Trampolines for the C callback types, so they can be implemented with Rust closures.
+//!
+//! Unwinding out of an `extern "C"` function is undefined behavior (or at best an abort), so
+//! every trampoline catches panics from the closure, returns something sensible to the C side
+//! (i.e. asking it to stop) and records the panic in the [`Callback`] so it can be re-raised
+//! with [`Callback::resume_panic`] once the C function that invoked it has returned.
+//!
+//! A closure is passed to C as userdata by boxing it in a [`Callback`] and handing over
+//! [`Callback::userdata`] along with the matching trampoline. The `Callback` must stay alive
+//! for as long as C may call it. The custom operation trampolines live in `custom.rs`.
+
+use std::{
+    any::Any,
+    ffi::CStr,
+    fmt,
+    os::raw::{c_char, c_int, c_void},
+    panic::{self, AssertUnwindSafe},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::*;
+
+/// Holds the payload of the first panic caught in a trampoline until it's re-raised.
+#[derive(Default)]
+pub struct PanicSlot(Mutex<Option<Box<dyn Any + Send>>>);
+
+impl PanicSlot {
+    /// Runs `f`, returning `on_panic` instead if it panics.
+    pub fn catch<R>(&self, on_panic: R, f: impl FnOnce() -> R) -> R {
+        match panic::catch_unwind(AssertUnwindSafe(f)) {
+            Ok(result) => result,
+            Err(payload) => {
+                let mut slot = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+                slot.get_or_insert(payload);
+                on_panic
+            }
+        }
+    }
+
+    pub fn take(&self) -> Option<Box<dyn Any + Send>> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    /// Re-raises the recorded panic, if any.
+    pub fn resume(&self) {
+        if let Some(payload) = self.take() {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+impl fmt::Debug for PanicSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let panicked = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_some();
+        f.debug_tuple("PanicSlot").field(&panicked).finish()
+    }
+}
+
+/// A boxed closure passed to C as callback userdata.
+pub struct Callback<F: ?Sized> {
+    fun: Box<F>,
+    panic: PanicSlot,
+}
+
+impl<F: ?Sized> Callback<F> {
+    /// The callback is boxed so the userdata pointer stays valid when it's moved around.
+    pub fn new(fun: Box<F>) -> Box<Self> {
+        Box::new(Self {
+            fun,
+            panic: PanicSlot::default(),
+        })
+    }
+
+    /// The pointer to pass as userdata along with the trampoline for `F`.
+    pub fn userdata(&mut self) -> *mut c_void {
+        self as *mut Self as *mut c_void
+    }
+
+    /// Takes the payload of a panic in the closure, if there was one.
+    pub fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.panic.take()
+    }
+
+    /// Re-raises a panic in the closure, if there was one.
+    pub fn resume_panic(&self) {
+        self.panic.resume()
+    }
+
+    /// # Safety
+    /// `data` must come from [`Callback::userdata`] and the callback must still be alive.
+    unsafe fn from_userdata<'a>(data: *mut c_void) -> &'a mut Self {
+        &mut *(data as *mut Self)
+    }
+
+    /// Like [`Callback::from_userdata`], for closures that may be called from several threads.
+    unsafe fn from_userdata_shared<'a>(data: *mut c_void) -> &'a Self {
+        &*(data as *const Self)
+    }
+}
+
+impl<F: ?Sized> fmt::Debug for Callback<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Callback")
+            .field("panic", &self.panic)
+            .finish_non_exhaustive()
+    }
+}
+
+/// `ggml_log_callback`: called with the level and the text, which may be a partial line.
+pub type LogFn = dyn Fn(ggml_log_level, &str) + Send + Sync;
+/// `ggml_abort_callback`: returning `true` stops the computation.
+pub type AbortFn<'a> = dyn Fn() -> bool + Send + Sync + 'a;
+/// `ggml_opt_callback`: called with the accumulation step, the learning rate schedule and the
+/// cancel flag.
+pub type OptFn<'a> = dyn FnMut(i32, &mut f32, &mut bool) + 'a;
+/// `llama_progress_callback`: called with the progress from 0 to 1, returning `false` stops
+/// loading.
+#[cfg(feature = "llamacpp_api")]
+pub type ProgressFn<'a> = dyn FnMut(f32) -> bool + 'a;
+/// `ggml_backend_sched_eval_callback`: see `ggml-backend.h` for the meaning of `ask` and the
+/// return value.
+#[cfg(feature = "llamacpp_api")]
+pub type SchedEvalFn<'a> = dyn FnMut(*mut ggml_tensor, bool) -> bool + 'a;
+
+/// Trampoline for [`LogFn`]. Panics drop the message.
+///
+/// # Safety
+/// `user_data` must be the [`Callback::userdata`] of a live `Callback<LogFn>`.
+pub unsafe extern "C" fn log_trampoline(
+    level: ggml_log_level,
+    text: *const c_char,
+    user_data: *mut c_void,
+) {
+    let cb = Callback::<LogFn>::from_userdata_shared(user_data);
+    if text.is_null() {
+        return;
+    }
+    let text = CStr::from_ptr(text).to_string_lossy();
+    cb.panic.catch((), || (cb.fun)(level, &text))
+}
+
+/// Trampoline for [`AbortFn`]. Panics abort the computation.
+///
+/// # Safety
+/// `data` must be the [`Callback::userdata`] of a live `Callback<AbortFn>`.
+pub unsafe extern "C" fn abort_trampoline(data: *mut c_void) -> bool {
+    let cb = Callback::<AbortFn>::from_userdata_shared(data);
+    cb.panic.catch(true, || (cb.fun)())
+}
+
+/// Trampoline for [`OptFn`]. Panics cancel the optimization.
+///
+/// # Safety
+/// `data` must be the [`Callback::userdata`] of a live `Callback<OptFn>`, `sched` and `cancel`
+/// must be valid.
+pub unsafe extern "C" fn opt_trampoline(
+    data: *mut c_void,
+    accum_step: c_int,
+    sched: *mut f32,
+    cancel: *mut bool,
+) {
+    let cb = Callback::<OptFn>::from_userdata(data);
+    let cancel = &mut *cancel;
+    let panicked = cb.panic.catch(true, || {
+        (cb.fun)(accum_step, &mut *sched, cancel);
+        false
+    });
+    if panicked {
+        *cancel = true;
+    }
+}
+
+/// Trampoline for [`ProgressFn`]. Panics stop loading.
+///
+/// # Safety
+/// `user_data` must be the [`Callback::userdata`] of a live `Callback<ProgressFn>`.
+#[cfg(feature = "llamacpp_api")]
+pub unsafe extern "C" fn progress_trampoline(progress: f32, user_data: *mut c_void) -> bool {
+    let cb = Callback::<ProgressFn>::from_userdata(user_data);
+    cb.panic.catch(false, || (cb.fun)(progress))
+}
+
+/// Trampoline for [`SchedEvalFn`]. Panics stop the computation.
+///
+/// # Safety
+/// `user_data` must be the [`Callback::userdata`] of a live `Callback<SchedEvalFn>`.
+#[cfg(feature = "llamacpp_api")]
+pub unsafe extern "C" fn sched_eval_trampoline(
+    t: *mut ggml_tensor,
+    ask: bool,
+    user_data: *mut c_void,
+) -> bool {
+    let cb = Callback::<SchedEvalFn>::from_userdata(user_data);
+    cb.panic.catch(false, || (cb.fun)(t, ask))
+}
+
+#[cfg(feature = "llamacpp_api")]
+struct LlamaLog {
+    current: Option<Box<Callback<LogFn>>>,
+    // Callbacks that have been replaced. Another thread may still be logging with one right
+    // after `llama_log_set` returns, so they're kept alive for the rest of the process.
+    #[allow(clippy::vec_box)]
+    replaced: Vec<Box<Callback<LogFn>>>,
+}
+
+#[cfg(feature = "llamacpp_api")]
+static LLAMA_LOG: Mutex<LlamaLog> = Mutex::new(LlamaLog {
+    current: None,
+    replaced: Vec::new(),
+});
+
+/// Sends `llama.cpp` log output to `fun` instead of stderr (`llama_log_set`). GGML's Metal and
+/// CUDA backends log through it too, the rest of GGML prints to stderr regardless.
+#[cfg(feature = "llamacpp_api")]
+pub fn set_llama_log_callback(fun: impl Fn(ggml_log_level, &str) + Send + Sync + 'static) {
+    let mut log = LLAMA_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut cb = Callback::<LogFn>::new(Box::new(fun));
+    unsafe { llama_log_set(Some(log_trampoline), cb.userdata()) };
+    if let Some(old) = log.current.replace(cb) {
+        log.replaced.push(old);
+    }
+}
+
+/// Restores logging to stderr.
+#[cfg(feature = "llamacpp_api")]
+pub fn clear_llama_log_callback() {
+    let mut log = LLAMA_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+    unsafe { llama_log_set(None, std::ptr::null_mut()) };
+    if let Some(old) = log.current.take() {
+        log.replaced.push(old);
+    }
+}
+
+/// Re-raises a panic in the callback set with [`set_llama_log_callback`] (or one it replaced),
+/// if there was one. Logging happens during all sorts of calls, so this has to be done
+/// manually.
+#[cfg(feature = "llamacpp_api")]
+pub fn resume_llama_log_panic() {
+    let payload = {
+        let log = LLAMA_LOG.lock().unwrap_or_else(PoisonError::into_inner);
+        log.current
+            .iter()
+            .chain(&log.replaced)
+            .find_map(|cb| cb.take_panic())
+    };
+    if let Some(payload) = payload {
+        panic::resume_unwind(payload);
+    }
+}
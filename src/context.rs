@@ -0,0 +1,249 @@
+use std::{
+    alloc::{self, Layout},
+    cell::RefCell,
+    ffi::CString,
+    os::raw::c_void,
+    ptr::NonNull,
+    sync::Once,
+};
+
+use crate::{
+    custom::CustomOpData,
+    error::{Error, Result},
+    tensor::Tensor,
+    *,
+};
+
+/// An owned `ggml_context` along with the memory buffer backing it. Tensors created from the
+/// context borrow it, so they can't outlive the arena they live in.
+#[derive(Debug)]
+pub struct Context {
+    ptr: NonNull<ggml_context>,
+    // `None` if GGML allocated the buffer, in which case `ggml_free` frees it.
+    buffer: Option<(NonNull<u8>, Layout)>,
+    // Userdata of custom operations on tensors in this context, boxed since GGML holds on to
+    // their addresses.
+    #[allow(clippy::vec_box)]
+    custom_ops: RefCell<Vec<Box<CustomOpData>>>,
+}
+
+// Contexts aren't tied to a thread, they just can't be used from several at once.
+unsafe impl Send for Context {}
+
+impl Context {
+    /// Creates a context with a `mem_size` byte buffer for tensor metadata and data.
+    pub fn new(mem_size: usize) -> Result<Self> {
+        Self::init(mem_size, false)
+    }
+
+    /// Creates a context that only holds tensor metadata: tensor data must be allocated
+    /// elsewhere (i.e. with `ggml-alloc` or a backend buffer).
+    pub fn new_no_alloc(mem_size: usize) -> Result<Self> {
+        Self::init(mem_size, true)
+    }
+
+    fn init(mem_size: usize, no_alloc: bool) -> Result<Self> {
+        let align = GGML_MEM_ALIGN as usize;
+        let layout = Layout::from_size_align(mem_size.max(align), align).map_err(|_| {
+            Error::OutOfMemory {
+                needed: mem_size,
+                available: isize::MAX as usize,
+            }
+        })?;
+        let buffer = NonNull::new(unsafe { alloc::alloc(layout) })
+            .unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        let ctx = unsafe {
+            ggml_init(ggml_init_params {
+                mem_size: layout.size(),
+                mem_buffer: buffer.as_ptr().cast(),
+                no_alloc,
+            })
+        };
+        match NonNull::new(ctx) {
+            Some(ptr) => Ok(Self {
+                ptr,
+                buffer: Some((buffer, layout)),
+                custom_ops: RefCell::default(),
+            }),
+            None => {
+                unsafe { alloc::dealloc(buffer.as_ptr(), layout) };
+                Err(Error::ContextInit)
+            }
+        }
+    }
+
+    /// Takes ownership of a context GGML allocated itself (i.e. for `gguf_init_from_file` or
+    /// `ggml_graph_import`), which is freed with `ggml_free` on drop.
+    ///
+    /// # Safety
+    /// `ptr` must be a context whose buffer GGML allocated, and nothing else may free it.
+    pub(crate) unsafe fn from_raw(ptr: *mut ggml_context) -> Self {
+        Self {
+            ptr: NonNull::new(ptr).expect("NULL context"),
+            buffer: None,
+            custom_ops: RefCell::default(),
+        }
+    }
+
+    pub fn as_ptr(&self) -> *mut ggml_context {
+        self.ptr.as_ptr()
+    }
+
+    /// Bytes of the buffer in use (`ggml_used_mem`).
+    pub fn used_mem(&self) -> usize {
+        unsafe { ggml_used_mem(self.as_ptr()) }
+    }
+
+    /// Total size of the buffer (`ggml_get_mem_size`).
+    pub fn mem_size(&self) -> usize {
+        unsafe { ggml_get_mem_size(self.as_ptr()) }
+    }
+
+    pub fn no_alloc(&self) -> bool {
+        unsafe { ggml_get_no_alloc(self.as_ptr()) }
+    }
+
+    /// Creates a tensor with between one and `GGML_MAX_DIMS` dimensions, `ne[0]` first.
+    pub fn new_tensor(&self, type_: ggml_type, shape: &[i64]) -> Result<Tensor<'_>> {
+        if shape.is_empty() || shape.len() > GGML_MAX_DIMS as usize {
+            return Err(Error::InvalidShape {
+                type_,
+                shape: shape.to_vec(),
+            });
+        }
+        self.ensure_room(type_, shape, false)?;
+        let ptr = unsafe {
+            ggml_new_tensor(
+                self.as_ptr(),
+                type_,
+                shape.len() as std::os::raw::c_int,
+                shape.as_ptr(),
+            )
+        };
+        Ok(unsafe { Tensor::from_raw(self, ptr) }.expect("ggml_new_tensor returned NULL"))
+    }
+
+    /// Checks that a tensor of the given type and shape is valid and that there's room for it,
+    /// which would otherwise be a GGML assertion failure. Views only need room for the tensor
+    /// metadata.
+    pub(crate) fn ensure_room(&self, type_: ggml_type, shape: &[i64], view: bool) -> Result<()> {
+        self.ensure_room_all(&[(type_, shape, view)])
+    }
+
+    /// Like [`Context::ensure_room`] for several tensors created one after the other.
+    pub(crate) fn ensure_room_all(&self, tensors: &[(ggml_type, &[i64], bool)]) -> Result<()> {
+        let needed = tensors
+            .iter()
+            .try_fold(0usize, |needed, (type_, shape, view)| {
+                Ok::<_, Error>(needed.saturating_add(self.tensor_size(*type_, shape, *view)?))
+            })?;
+        let available = self.mem_size() - self.used_mem();
+        if needed > available {
+            return Err(Error::OutOfMemory { needed, available });
+        }
+        Ok(())
+    }
+
+    /// The room a tensor takes up in the context, see [`Context::ensure_room`].
+    fn tensor_size(&self, type_: ggml_type, shape: &[i64], view: bool) -> Result<usize> {
+        let invalid = || Error::InvalidShape {
+            type_,
+            shape: shape.to_vec(),
+        };
+        if type_ >= ggml_type_GGML_TYPE_COUNT || shape.iter().any(|ne| *ne < 0) {
+            return Err(invalid());
+        }
+        let blck_size = unsafe { ggml_blck_size(type_) } as i64;
+        let ne0 = shape.first().copied().unwrap_or(1);
+        // Views (i.e. transposes) of quantized tensors needn't have whole blocks along ne[0].
+        if blck_size == 0 || (!view && ne0 % blck_size != 0) {
+            return Err(invalid());
+        }
+
+        let align = GGML_MEM_ALIGN as usize;
+        let data_size = if view || self.no_alloc() {
+            0
+        } else {
+            shape
+                .iter()
+                .skip(1)
+                .try_fold(unsafe { ggml_row_size(type_, ne0) }, |acc, ne| {
+                    acc.checked_mul(*ne as usize)
+                })
+                .and_then(|size| size.checked_next_multiple_of(align))
+                .ok_or_else(invalid)?
+        };
+        Ok(unsafe { ggml_tensor_overhead() }.saturating_add(data_size))
+    }
+
+    pub fn new_tensor_1d(&self, type_: ggml_type, ne0: i64) -> Result<Tensor<'_>> {
+        self.new_tensor(type_, &[ne0])
+    }
+
+    pub fn new_tensor_2d(&self, type_: ggml_type, ne0: i64, ne1: i64) -> Result<Tensor<'_>> {
+        self.new_tensor(type_, &[ne0, ne1])
+    }
+
+    pub fn new_tensor_3d(
+        &self,
+        type_: ggml_type,
+        ne0: i64,
+        ne1: i64,
+        ne2: i64,
+    ) -> Result<Tensor<'_>> {
+        self.new_tensor(type_, &[ne0, ne1, ne2])
+    }
+
+    pub fn new_tensor_4d(
+        &self,
+        type_: ggml_type,
+        ne0: i64,
+        ne1: i64,
+        ne2: i64,
+        ne3: i64,
+    ) -> Result<Tensor<'_>> {
+        self.new_tensor(type_, &[ne0, ne1, ne2, ne3])
+    }
+
+    /// Looks up a tensor in this context by name (`ggml_get_tensor`).
+    pub fn get_tensor(&self, name: &str) -> Option<Tensor<'_>> {
+        let name = CString::new(name).ok()?;
+        unsafe { Tensor::from_raw(self, ggml_get_tensor(self.as_ptr(), name.as_ptr())) }
+    }
+
+    /// Iterates over every tensor allocated in this context.
+    pub fn tensors(&self) -> impl Iterator<Item = Tensor<'_>> + '_ {
+        let first = unsafe { Tensor::from_raw(self, ggml_get_first_tensor(self.as_ptr())) };
+        std::iter::successors(first, move |t| unsafe {
+            Tensor::from_raw(self, ggml_get_next_tensor(self.as_ptr(), t.as_ptr()))
+        })
+    }
+}
+
+impl Context {
+    /// Keeps the userdata of a custom operation alive as long as the context, returning the
+    /// pointer to pass to GGML.
+    pub(crate) fn keep_custom_op(&self, data: Box<CustomOpData>) -> *mut c_void {
+        let ptr = &*data as *const CustomOpData as *mut c_void;
+        self.custom_ops.borrow_mut().push(data);
+        ptr
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        unsafe { ggml_free(self.as_ptr()) };
+        if let Some((buffer, layout)) = self.buffer {
+            unsafe { alloc::dealloc(buffer.as_ptr(), layout) };
+        }
+    }
+}
+
+/// GGML converts F16 with a lookup table on some platforms, which is filled in by the first
+/// `ggml_init`. Functions that don't need a context call this instead.
+pub(crate) fn init_tables() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        Context::new_no_alloc(1024).expect("Failed to initialize GGML");
+    });
+}
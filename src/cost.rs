@@ -0,0 +1,367 @@
+//! Estimating what computing a graph costs, i.e. to compare the compute per token of model
+//! variants before deploying them.
+//!
+//! [`Graph::cost`] walks the nodes in evaluation order and estimates, for each, the floating
+//! point operations (see [`flops`]) and the bytes read from its operands and written to its
+//! result, using the sizes of their types so quantized weights count as what they occupy.
+//! It also tracks which results are still needed to find the peak memory the intermediate
+//! results take, assuming each is freed right after its last use like `ggml-alloc` does.
+//! These are estimates: the kernels GGML actually runs may convert, repack or skip data.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Write},
+};
+
+use crate::{dump::json_string, graph::Graph, tensor::Tensor, *};
+
+/// FLOPs per element of the operations that take a few to compute each (`exp`, the
+/// normalization, the rotation...).
+fn per_element(op: ggml_op) -> Option<u64> {
+    match op {
+        op if op == ggml_op_GGML_OP_SOFT_MAX => Some(5),
+        op if op == ggml_op_GGML_OP_NORM || op == ggml_op_GGML_OP_GROUP_NORM => Some(5),
+        op if op == ggml_op_GGML_OP_RMS_NORM => Some(3),
+        op if op == ggml_op_GGML_OP_ROPE => Some(3),
+        _ => None,
+    }
+}
+
+/// Operations that only move or reinterpret data.
+fn moves_data(op: ggml_op) -> bool {
+    [
+        ggml_op_GGML_OP_NONE,
+        ggml_op_GGML_OP_DUP,
+        ggml_op_GGML_OP_CPY,
+        ggml_op_GGML_OP_CONT,
+        ggml_op_GGML_OP_RESHAPE,
+        ggml_op_GGML_OP_VIEW,
+        ggml_op_GGML_OP_PERMUTE,
+        ggml_op_GGML_OP_TRANSPOSE,
+        ggml_op_GGML_OP_GET_ROWS,
+        ggml_op_GGML_OP_SET,
+        ggml_op_GGML_OP_CONCAT,
+        ggml_op_GGML_OP_REPEAT,
+        ggml_op_GGML_OP_IM2COL,
+        ggml_op_GGML_OP_PAD,
+        ggml_op_GGML_OP_ARGSORT,
+    ]
+    .contains(&op)
+}
+
+/// Operations whose result is a view of their operand, which don't compute anything.
+fn is_view(op: ggml_op) -> bool {
+    op == ggml_op_GGML_OP_RESHAPE
+        || op == ggml_op_GGML_OP_VIEW
+        || op == ggml_op_GGML_OP_PERMUTE
+        || op == ggml_op_GGML_OP_TRANSPOSE
+}
+
+fn nelements(t: Tensor) -> u64 {
+    t.nelements() as u64
+}
+
+/// Estimated floating point operations to compute `tensor`, counting a multiply-add as two.
+///
+/// - `MUL_MAT`, `MUL_MAT_ID` and `OUT_PROD` take a multiply-add per term of each dot product.
+///   Convolutions built with `ggml_conv_1d`/`ggml_conv_2d` are an `IM2COL` followed by a
+///   `MUL_MAT`, so that's where their cost shows up.
+/// - `FLASH_ATTN_EXT` takes the two products of attention, `QK^T` and the weighted sum of
+///   `V`, plus the softmax of the scores.
+/// - `CONV_TRANSPOSE_1D`/`2D` take a multiply-add per kernel element and input element
+///   of the same channel, and pooling one operation per element of each window.
+/// - Reductions take one per element they read, operations that only move data none, and
+///   anything else one (or a few, i.e. for `SOFT_MAX` and the norms) per element written.
+pub fn flops(tensor: &Tensor) -> u64 {
+    let op = tensor.op();
+    let src = tensor.sources().collect::<Vec<_>>();
+    let src0 = |i: usize| src[0].ne()[i] as u64;
+    let param = |i: usize| unsafe { (*tensor.as_ptr()).op_params[i] } as u64;
+    match op {
+        _ if moves_data(op) || src.is_empty() => 0,
+        op if op == ggml_op_GGML_OP_MUL_MAT || op == ggml_op_GGML_OP_MUL_MAT_ID => {
+            2 * src0(0) * nelements(*tensor)
+        }
+        op if op == ggml_op_GGML_OP_OUT_PROD => 2 * src0(1) * nelements(*tensor),
+        op if op == ggml_op_GGML_OP_FLASH_ATTN_EXT => {
+            // A score per query row (of each head and batch) and key, each a dot product with
+            // the query, then softmaxed and multiplied by the value.
+            let scores = src0(1) * src0(2) * src0(3) * src[1].ne()[1] as u64;
+            let softmax = per_element(ggml_op_GGML_OP_SOFT_MAX).unwrap();
+            scores * (2 * src0(0) + 2 * src[2].ne()[0] as u64 + softmax)
+        }
+        op if op == ggml_op_GGML_OP_CONV_TRANSPOSE_1D => {
+            2 * nelements(src[0]) * nelements(src[1]) / src[1].ne()[1] as u64
+        }
+        op if op == ggml_op_GGML_OP_CONV_TRANSPOSE_2D => {
+            2 * nelements(src[0]) * nelements(src[1]) / src[1].ne()[2] as u64
+        }
+        // The parameters are the pooling operation followed by the kernel size.
+        op if op == ggml_op_GGML_OP_POOL_1D => param(1) * nelements(*tensor),
+        op if op == ggml_op_GGML_OP_POOL_2D => param(1) * param(2) * nelements(*tensor),
+        op if op == ggml_op_GGML_OP_SUM
+            || op == ggml_op_GGML_OP_SUM_ROWS
+            || op == ggml_op_GGML_OP_MEAN
+            || op == ggml_op_GGML_OP_ARGMAX =>
+        {
+            nelements(src[0])
+        }
+        op => per_element(op).unwrap_or(1) * nelements(*tensor),
+    }
+}
+
+/// Bytes `tensor`'s operation reads from its operands.
+fn bytes_read(tensor: &Tensor) -> u64 {
+    let op = tensor.op();
+    let src = tensor.sources().collect::<Vec<_>>();
+    if is_view(op) || src.is_empty() {
+        return 0;
+    }
+    let all = src.iter().map(|t| t.nbytes() as u64).sum::<u64>();
+    match op {
+        // Only the selected rows of the table.
+        op if op == ggml_op_GGML_OP_GET_ROWS => {
+            let row = unsafe { ggml_row_size(src[0].type_(), src[0].ne()[0]) } as u64;
+            all - src[0].nbytes() as u64 + row * nelements(src[1])
+        }
+        // Only the experts in use, at most one per selected id.
+        op if op == ggml_op_GGML_OP_MUL_MAT_ID => {
+            let experts = src[0].ne()[2] as u64;
+            let used = nelements(src[2]).min(experts);
+            all - src[0].nbytes() as u64 + src[0].nbytes() as u64 / experts * used
+        }
+        _ => all,
+    }
+}
+
+/// The estimated cost of a node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeCost {
+    pub name: String,
+    /// `ggml_op_desc`, which is the unary operation for `UNARY` nodes.
+    pub op: &'static str,
+    pub flops: u64,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+    /// Bytes of intermediate results live while the node is computed, its own included.
+    pub live_memory: u64,
+}
+
+/// The costs of the nodes with the same operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpCost {
+    pub op: &'static str,
+    pub count: usize,
+    pub flops: u64,
+    pub bytes_read: u64,
+    pub bytes_written: u64,
+}
+
+/// The estimated costs of computing a graph, built by [`Graph::cost`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostReport {
+    /// One entry per node, in evaluation order.
+    pub nodes: Vec<NodeCost>,
+    /// Bytes of the leafs and parameters, which are live for the whole computation.
+    pub leaf_memory: u64,
+    /// The most bytes of intermediate results live at once.
+    pub peak_memory: u64,
+    /// Index in `nodes` of the first node computed at the peak, if there are any nodes.
+    pub peak_node: Option<usize>,
+}
+
+impl Graph<'_> {
+    /// Estimates the cost of computing the graph, see the [module docs](crate::cost).
+    pub fn cost(&self) -> CostReport {
+        let nodes = self.nodes().collect::<Vec<_>>();
+        // Results are owned by the tensor whose memory they live in.
+        let owner = |t: &Tensor| t.view_src().unwrap_or(*t).as_ptr();
+        let allocates = |t: &Tensor| t.view_src().is_none() && t.op() != ggml_op_GGML_OP_NONE;
+
+        // The last node using each owner's memory. Results nothing reads are the graph's
+        // outputs, which stay live until the end.
+        let mut last_use = HashMap::new();
+        let mut read = HashSet::new();
+        for (i, node) in nodes.iter().enumerate() {
+            for t in node.sources().chain(Some(*node)) {
+                last_use.insert(owner(&t), i);
+            }
+            for src in node.sources() {
+                read.insert(src.as_ptr());
+            }
+        }
+        for node in &nodes {
+            let output = unsafe { (*node.as_ptr()).flags }
+                & ggml_tensor_flag_GGML_TENSOR_FLAG_OUTPUT as i32
+                != 0;
+            if output || !read.contains(&node.as_ptr()) {
+                last_use.insert(owner(node), usize::MAX);
+            }
+        }
+
+        let mut freed = HashMap::<usize, u64>::new();
+        for node in nodes.iter().filter(|node| allocates(node)) {
+            *freed.entry(last_use[&node.as_ptr()]).or_default() += node.nbytes() as u64;
+        }
+        let leaf_memory = self
+            .leafs()
+            .chain(
+                nodes
+                    .iter()
+                    .copied()
+                    .filter(|node| node.op() == ggml_op_GGML_OP_NONE),
+            )
+            .map(|t| t.nbytes() as u64)
+            .sum();
+
+        let (mut live, mut peak_memory, mut peak_node) = (0, 0, None);
+        let mut costs = Vec::with_capacity(nodes.len());
+        for (i, node) in nodes.iter().enumerate() {
+            let computes = !is_view(node.op()) && node.op() != ggml_op_GGML_OP_NONE;
+            if allocates(node) {
+                live += node.nbytes() as u64;
+            }
+            if live > peak_memory || peak_node.is_none() {
+                (peak_memory, peak_node) = (live, Some(i));
+            }
+            costs.push(NodeCost {
+                name: node.name().into_owned(),
+                op: node.op_desc(),
+                flops: flops(node),
+                bytes_read: bytes_read(node),
+                bytes_written: if computes { node.nbytes() as u64 } else { 0 },
+                live_memory: live,
+            });
+            live -= freed.get(&i).copied().unwrap_or(0);
+        }
+        CostReport {
+            nodes: costs,
+            leaf_memory,
+            peak_memory,
+            peak_node,
+        }
+    }
+}
+
+impl CostReport {
+    pub fn flops(&self) -> u64 {
+        self.nodes.iter().map(|node| node.flops).sum()
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.nodes.iter().map(|node| node.bytes_read).sum()
+    }
+
+    pub fn bytes_written(&self) -> u64 {
+        self.nodes.iter().map(|node| node.bytes_written).sum()
+    }
+
+    /// The costs summed per operation, in order of appearance.
+    pub fn by_op(&self) -> Vec<OpCost> {
+        let mut ops = Vec::<OpCost>::new();
+        for node in &self.nodes {
+            let cost = match ops.iter_mut().find(|cost| cost.op == node.op) {
+                Some(cost) => cost,
+                None => {
+                    ops.push(OpCost {
+                        op: node.op,
+                        count: 0,
+                        flops: 0,
+                        bytes_read: 0,
+                        bytes_written: 0,
+                    });
+                    ops.last_mut().unwrap()
+                }
+            };
+            cost.count += 1;
+            cost.flops += node.flops;
+            cost.bytes_read += node.bytes_read;
+            cost.bytes_written += node.bytes_written;
+        }
+        ops
+    }
+
+    /// The report as a table with one row per operation and their share of the FLOPs, followed
+    /// by the totals and memory.
+    pub fn to_table(&self) -> String {
+        let ops = self.by_op();
+        let width = ops
+            .iter()
+            .map(|cost| cost.op.len())
+            .chain(Some(5))
+            .max()
+            .unwrap_or(5);
+        let total = self.flops();
+        let mut out = format!(
+            "{:width$}  {:>6}  {:>16}  {:>6}  {:>14}  {:>14}\n",
+            "op", "count", "flops", "share", "read", "written"
+        );
+        let mut row = |op: &str, count: usize, flops: u64, read: u64, written: u64| {
+            let share = if total == 0 {
+                0.0
+            } else {
+                flops as f64 / total as f64 * 100.0
+            };
+            let _ = writeln!(
+                out,
+                "{op:width$}  {count:>6}  {flops:>16}  {share:>5.1}%  {read:>14}  {written:>14}",
+            );
+        };
+        for cost in &ops {
+            row(
+                cost.op,
+                cost.count,
+                cost.flops,
+                cost.bytes_read,
+                cost.bytes_written,
+            );
+        }
+        row(
+            "total",
+            self.nodes.len(),
+            total,
+            self.bytes_read(),
+            self.bytes_written(),
+        );
+        let _ = writeln!(
+            out,
+            "\nleaf memory: {} bytes, peak intermediate memory: {} bytes",
+            self.leaf_memory, self.peak_memory
+        );
+        out
+    }
+
+    /// The totals, memory and per operation costs as a JSON object.
+    pub fn to_json(&self) -> String {
+        let mut out = format!(
+            "{{\"flops\": {}, \"bytes_read\": {}, \"bytes_written\": {}, \"leaf_memory\": {}, \
+             \"peak_memory\": {}, \"ops\": [",
+            self.flops(),
+            self.bytes_read(),
+            self.bytes_written(),
+            self.leaf_memory,
+            self.peak_memory,
+        );
+        for (i, cost) in self.by_op().iter().enumerate() {
+            let _ = write!(
+                out,
+                "{}\n  {{\"op\": {}, \"count\": {}, \"flops\": {}, \"bytes_read\": {}, \
+                 \"bytes_written\": {}}}",
+                if i == 0 { "" } else { "," },
+                json_string(cost.op),
+                cost.count,
+                cost.flops,
+                cost.bytes_read,
+                cost.bytes_written,
+            );
+        }
+        out.push_str("\n]}\n");
+        out
+    }
+}
+
+impl fmt::Display for CostReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
@@ -0,0 +1,370 @@
+//! Custom operations written in Rust (`ggml_map_custom1` through `ggml_map_custom3`).
+//!
+//! The closure is called from each of GGML's compute threads with `ith` and `nth` (the thread's
+//! index and the number of threads) and is expected to split the work between them.
+//! [`TensorMut::as_mut_slice`] only hands out the rows belonging to the calling thread so
+//! threads can't write over each other, and the operands are [`TensorRef`]s, which can only be
+//! read. That's also why the `_inplace` variants aren't wrapped: their result is a view of the
+//! first operand, so it could be read through a [`TensorRef`] while another thread writes it.
+
+use std::{
+    any::Any,
+    fmt,
+    marker::PhantomData,
+    ops::Range,
+    os::raw::{c_int, c_void},
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use crate::{
+    callback::PanicSlot,
+    error::{Error, Result},
+    shape::Output,
+    tensor::{Tensor, TensorElement},
+    *,
+};
+
+/// `fn(dst, a, ith, nth)`
+pub type CustomOp1 = Arc<dyn Fn(&mut TensorMut, &TensorRef, usize, usize) + Send + Sync>;
+/// `fn(dst, a, b, ith, nth)`
+pub type CustomOp2 =
+    Arc<dyn Fn(&mut TensorMut, &TensorRef, &TensorRef, usize, usize) + Send + Sync>;
+/// `fn(dst, a, b, c, ith, nth)`
+pub type CustomOp3 =
+    Arc<dyn Fn(&mut TensorMut, &TensorRef, &TensorRef, &TensorRef, usize, usize) + Send + Sync>;
+
+pub(crate) enum CustomFn {
+    Op1(CustomOp1),
+    Op2(CustomOp2),
+    Op3(CustomOp3),
+}
+
+/// The userdata passed to GGML. It's owned by the [`Context`](crate::Context) the operation's
+/// tensor lives in, so it outlives any graph using the tensor.
+pub(crate) struct CustomOpData {
+    fun: CustomFn,
+    panic: PanicSlot,
+}
+
+impl fmt::Debug for CustomOpData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CustomOpData").finish_non_exhaustive()
+    }
+}
+
+impl CustomOpData {
+    pub(crate) fn new(fun: CustomFn) -> Box<Self> {
+        Box::new(Self {
+            fun,
+            panic: PanicSlot::default(),
+        })
+    }
+
+    /// Takes the payload of a panic from the closure, if there was one.
+    pub(crate) fn take_panic(&self) -> Option<Box<dyn Any + Send>> {
+        self.panic.take()
+    }
+}
+
+/// An operand of a custom operation. Unlike a [`Tensor`] it can't be written to or used to
+/// build operations, since the closure runs on several threads at once.
+pub struct TensorRef<'a> {
+    ptr: NonNull<ggml_tensor>,
+    _tensor: PhantomData<&'a ggml_tensor>,
+}
+
+impl<'a> TensorRef<'a> {
+    /// # Safety
+    /// `ptr` must be a valid tensor that isn't written to during `'a`.
+    unsafe fn from_raw(ptr: *const ggml_tensor) -> Option<Self> {
+        NonNull::new(ptr.cast_mut()).map(|ptr| Self {
+            ptr,
+            _tensor: PhantomData,
+        })
+    }
+
+    fn raw(&self) -> &ggml_tensor {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    pub fn as_ptr(&self) -> *const ggml_tensor {
+        self.ptr.as_ptr()
+    }
+
+    pub fn type_(&self) -> ggml_type {
+        self.raw().type_
+    }
+
+    pub fn ne(&self) -> [i64; GGML_MAX_DIMS as usize] {
+        self.raw().ne
+    }
+
+    pub fn nb(&self) -> [usize; GGML_MAX_DIMS as usize] {
+        self.raw().nb
+    }
+
+    pub fn nelements(&self) -> i64 {
+        unsafe { ggml_nelements(self.as_ptr()) }
+    }
+
+    pub fn nrows(&self) -> i64 {
+        unsafe { ggml_nrows(self.as_ptr()) }
+    }
+
+    pub fn is_contiguous(&self) -> bool {
+        unsafe { ggml_is_contiguous(self.as_ptr()) }
+    }
+
+    /// Borrows the tensor data, which must be contiguous and of type `T`.
+    pub fn as_slice<T: TensorElement>(&self) -> Result<&'a [T]> {
+        check_element::<T>(self.raw())?;
+        Ok(
+            unsafe {
+                std::slice::from_raw_parts(self.raw().data.cast(), self.nelements() as usize)
+            },
+        )
+    }
+}
+
+fn check_element<T: TensorElement>(tensor: &ggml_tensor) -> Result<()> {
+    if tensor.type_ != T::TYPE {
+        return Err(Error::TypeMismatch {
+            expected: T::TYPE,
+            actual: tensor.type_,
+        });
+    }
+    if !unsafe { ggml_is_contiguous(tensor) } {
+        return Err(Error::NotContiguous);
+    }
+    if tensor.data.is_null() {
+        return Err(Error::NoData);
+    }
+    Ok(())
+}
+
+/// The destination of a custom operation, restricted to the rows the current thread is
+/// responsible for.
+pub struct TensorMut<'a> {
+    ptr: NonNull<ggml_tensor>,
+    ith: usize,
+    nth: usize,
+    _tensor: PhantomData<&'a mut ggml_tensor>,
+}
+
+impl<'a> TensorMut<'a> {
+    fn raw(&self) -> &ggml_tensor {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    pub fn as_ptr(&self) -> *mut ggml_tensor {
+        self.ptr.as_ptr()
+    }
+
+    pub fn type_(&self) -> ggml_type {
+        self.raw().type_
+    }
+
+    pub fn ne(&self) -> [i64; GGML_MAX_DIMS as usize] {
+        self.raw().ne
+    }
+
+    pub fn nb(&self) -> [usize; GGML_MAX_DIMS as usize] {
+        self.raw().nb
+    }
+
+    pub fn nrows(&self) -> i64 {
+        unsafe { ggml_nrows(self.as_ptr()) }
+    }
+
+    /// The rows this thread should write, split as evenly as possible between threads the
+    /// same way GGML's own operations do.
+    pub fn rows(&self) -> Range<usize> {
+        let nrows = self.nrows() as usize;
+        let per_thread = nrows.div_ceil(self.nth);
+        let start = (per_thread * self.ith).min(nrows);
+        start..(start + per_thread).min(nrows)
+    }
+
+    /// Mutably borrows the data of [`TensorMut::rows`]. The tensor must be contiguous and of
+    /// type `T`.
+    pub fn as_mut_slice<T: TensorElement>(&mut self) -> Result<&mut [T]> {
+        check_element::<T>(self.raw())?;
+        let row_len = self.ne()[0] as usize;
+        let rows = self.rows();
+        Ok(unsafe {
+            std::slice::from_raw_parts_mut(
+                self.raw().data.cast::<T>().add(rows.start * row_len),
+                rows.len() * row_len,
+            )
+        })
+    }
+}
+
+/// Calls the closure in `data` with views of `dst` and `srcs`. Panics are stored so they can be
+/// raised again once the computation is done.
+unsafe fn call(
+    data: *mut c_void,
+    dst: *mut ggml_tensor,
+    srcs: [*const ggml_tensor; 3],
+    ith: c_int,
+    nth: c_int,
+) {
+    let data = &*(data as *const CustomOpData);
+    let (ith, nth) = (ith as usize, nth as usize);
+    let mut dst = TensorMut {
+        ptr: NonNull::new(dst).expect("NULL custom op destination"),
+        ith,
+        nth,
+        _tensor: PhantomData,
+    };
+    let [a, b, c] = srcs.map(|src| TensorRef::from_raw(src));
+    data.panic.catch((), || match &data.fun {
+        CustomFn::Op1(f) => f(&mut dst, &a.unwrap(), ith, nth),
+        CustomFn::Op2(f) => f(&mut dst, &a.unwrap(), &b.unwrap(), ith, nth),
+        CustomFn::Op3(f) => f(&mut dst, &a.unwrap(), &b.unwrap(), &c.unwrap(), ith, nth),
+    })
+}
+
+unsafe extern "C" fn custom1_trampoline(
+    dst: *mut ggml_tensor,
+    a: *const ggml_tensor,
+    ith: c_int,
+    nth: c_int,
+    userdata: *mut c_void,
+) {
+    call(
+        userdata,
+        dst,
+        [a, std::ptr::null(), std::ptr::null()],
+        ith,
+        nth,
+    )
+}
+
+unsafe extern "C" fn custom2_trampoline(
+    dst: *mut ggml_tensor,
+    a: *const ggml_tensor,
+    b: *const ggml_tensor,
+    ith: c_int,
+    nth: c_int,
+    userdata: *mut c_void,
+) {
+    call(userdata, dst, [a, b, std::ptr::null()], ith, nth)
+}
+
+unsafe extern "C" fn custom3_trampoline(
+    dst: *mut ggml_tensor,
+    a: *const ggml_tensor,
+    b: *const ggml_tensor,
+    c: *const ggml_tensor,
+    ith: c_int,
+    nth: c_int,
+    userdata: *mut c_void,
+) {
+    call(userdata, dst, [a, b, c], ith, nth)
+}
+
+/// Mirrors `struct ggml_map_custom1_op_params` (and the `2` and `3` variants, which only differ
+/// in the type of `fun`) from `ggml.c`, which GGML copies into the `op_params` of the node.
+#[repr(C)]
+struct OpParams {
+    fun: *const c_void,
+    n_tasks: c_int,
+    userdata: *mut c_void,
+}
+
+/// Takes the payload of a panic in the custom operations among `nodes` since the last call, if
+/// any. The operations may have been created in any context.
+pub(crate) fn take_panic<'a>(
+    nodes: impl IntoIterator<Item = Tensor<'a>>,
+) -> Option<Box<dyn Any + Send>> {
+    let mut payload = None;
+    for node in nodes {
+        let trampoline = match node.op() {
+            op if op == ggml_op_GGML_OP_MAP_CUSTOM1 => custom1_trampoline as *const c_void,
+            op if op == ggml_op_GGML_OP_MAP_CUSTOM2 => custom2_trampoline as *const c_void,
+            op if op == ggml_op_GGML_OP_MAP_CUSTOM3 => custom3_trampoline as *const c_void,
+            _ => continue,
+        };
+        let params = unsafe {
+            std::ptr::read_unaligned((*node.as_ptr()).op_params.as_ptr().cast::<OpParams>())
+        };
+        // Operations built from the raw bindings have some other userdata.
+        if params.fun != trampoline {
+            continue;
+        }
+        let data = unsafe { &*(params.userdata as *const CustomOpData) };
+        // Every panic is taken so it isn't raised again by the next computation.
+        if let Some(panic) = data.take_panic() {
+            payload.get_or_insert(panic);
+        }
+    }
+    payload
+}
+
+fn n_tasks(n_tasks: Option<usize>) -> c_int {
+    n_tasks.map_or(GGML_N_TASKS_MAX, |n| n.max(1) as c_int)
+}
+
+impl<'ctx> Tensor<'ctx> {
+    /// `ggml_map_custom1`: the result has the same type and shape as `self` and is computed by
+    /// `op` on up to `n_tasks` threads (all of them with `None`).
+    pub fn map_custom1(self, op: CustomOp1, n_tasks: Option<usize>) -> Result<Self> {
+        let check = Ok(Output::like("ggml_map_custom1", self));
+        self.checked(check, |ctx| unsafe {
+            let data = CustomOpData::new(CustomFn::Op1(op));
+            ggml_map_custom1(
+                ctx,
+                self.as_ptr(),
+                Some(custom1_trampoline),
+                self::n_tasks(n_tasks),
+                self.context().keep_custom_op(data),
+            )
+        })
+    }
+
+    /// `ggml_map_custom2`, see [`Tensor::map_custom1`].
+    pub fn map_custom2(
+        self,
+        b: Tensor<'ctx>,
+        op: CustomOp2,
+        n_tasks: Option<usize>,
+    ) -> Result<Self> {
+        let check = Ok(Output::like("ggml_map_custom2", self));
+        self.checked(check, |ctx| unsafe {
+            let data = CustomOpData::new(CustomFn::Op2(op));
+            ggml_map_custom2(
+                ctx,
+                self.as_ptr(),
+                b.as_ptr(),
+                Some(custom2_trampoline),
+                self::n_tasks(n_tasks),
+                self.context().keep_custom_op(data),
+            )
+        })
+    }
+
+    /// `ggml_map_custom3`, see [`Tensor::map_custom1`].
+    pub fn map_custom3(
+        self,
+        b: Tensor<'ctx>,
+        c: Tensor<'ctx>,
+        op: CustomOp3,
+        n_tasks: Option<usize>,
+    ) -> Result<Self> {
+        let check = Ok(Output::like("ggml_map_custom3", self));
+        self.checked(check, |ctx| unsafe {
+            let data = CustomOpData::new(CustomFn::Op3(op));
+            ggml_map_custom3(
+                ctx,
+                self.as_ptr(),
+                b.as_ptr(),
+                c.as_ptr(),
+                Some(custom3_trampoline),
+                self::n_tasks(n_tasks),
+                self.context().keep_custom_op(data),
+            )
+        })
+    }
+}
@@ -0,0 +1,227 @@
+//! Renders graphs as DOT, JSON or Mermaid strings, i.e. to show them in a debugging UI where
+//! `ggml_graph_dump_dot` (which only writes files) and `ggml_graph_print` (stdout) don't help.
+//!
+//! Leafs and nodes are walked in the graph's order, so every tensor's sources come before it.
+//! Operations only changing how memory is viewed can be left out, drawing the edges from the
+//! tensor they view instead.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{graph::Graph, *};
+
+/// What to include when rendering a graph. The default shows every tensor.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DumpOptions {
+    /// Leave out `VIEW`, `PERMUTE` and `TRANSPOSE` nodes.
+    pub collapse_views: bool,
+    /// Leave out `RESHAPE` nodes.
+    pub collapse_reshapes: bool,
+}
+
+impl DumpOptions {
+    fn collapses(&self, op: ggml_op) -> bool {
+        (self.collapse_views
+            && (op == ggml_op_GGML_OP_VIEW
+                || op == ggml_op_GGML_OP_PERMUTE
+                || op == ggml_op_GGML_OP_TRANSPOSE))
+            || (self.collapse_reshapes && op == ggml_op_GGML_OP_RESHAPE)
+    }
+}
+
+/// A tensor as it's rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DumpNode {
+    /// Index in [`GraphDump::nodes`].
+    pub id: usize,
+    pub name: String,
+    /// `ggml_op_desc`, which is the unary operation for `UNARY` nodes.
+    pub op: &'static str,
+    pub type_name: &'static str,
+    pub shape: Vec<i64>,
+    pub leaf: bool,
+    pub param: bool,
+    /// The operand slot (the index in `ggml_tensor::src`) and id of each operand, in order.
+    pub sources: Vec<(usize, usize)>,
+}
+
+/// The tensors of a graph as plain data, built by [`Graph::dump`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphDump {
+    pub nodes: Vec<DumpNode>,
+}
+
+impl Graph<'_> {
+    /// Collects the graph's leafs and nodes (in that order) with their operands.
+    pub fn dump(&self, options: DumpOptions) -> GraphDump {
+        let mut ids = HashMap::new();
+        let mut nodes = Vec::new();
+        let leafs = self.leafs().map(|t| (t, true));
+        for (tensor, leaf) in leafs.chain(self.nodes().map(|t| (t, false))) {
+            let sources = tensor
+                .sources()
+                .enumerate()
+                .filter_map(|(slot, src)| Some((slot, *ids.get(&src.as_ptr())?)))
+                .collect::<Vec<_>>();
+            // A collapsed tensor stands for its first operand.
+            if !leaf && options.collapses(tensor.op()) {
+                if let Some((_, src)) = sources.first() {
+                    ids.insert(tensor.as_ptr(), *src);
+                    continue;
+                }
+            }
+            let id = nodes.len();
+            ids.insert(tensor.as_ptr(), id);
+            nodes.push(DumpNode {
+                id,
+                name: tensor.name().into_owned(),
+                op: tensor.op_desc(),
+                type_name: tensor.type_name(),
+                shape: tensor.shape().to_vec(),
+                leaf,
+                param: tensor.is_param(),
+                sources,
+            });
+        }
+        GraphDump { nodes }
+    }
+
+    /// The graph in Graphviz's DOT language, see [`GraphDump::to_dot`].
+    pub fn to_dot(&self, options: DumpOptions) -> String {
+        self.dump(options).to_dot()
+    }
+
+    /// The graph as JSON, see [`GraphDump::to_json`].
+    pub fn to_json(&self, options: DumpOptions) -> String {
+        self.dump(options).to_json()
+    }
+
+    /// The graph as a Mermaid flowchart, see [`GraphDump::to_mermaid`].
+    pub fn to_mermaid(&self, options: DumpOptions) -> String {
+        self.dump(options).to_mermaid()
+    }
+}
+
+impl DumpNode {
+    fn shape_desc(&self) -> String {
+        let dims = self
+            .shape
+            .iter()
+            .map(|ne| ne.to_string())
+            .collect::<Vec<_>>();
+        format!("{} [{}]", self.type_name, dims.join(", "))
+    }
+
+    fn lines(&self) -> [String; 3] {
+        [self.name.clone(), self.op.to_owned(), self.shape_desc()]
+    }
+}
+
+/// `val` as a JSON string, quotes included.
+pub(crate) fn json_string(val: &str) -> String {
+    let mut out = String::with_capacity(val.len() + 2);
+    out.push('"');
+    for c in val.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+impl GraphDump {
+    /// A `digraph` with a box per tensor showing its name, operation, type and shape, and an
+    /// edge from each operand. Leafs are pink and parameters yellow, like
+    /// `ggml_graph_dump_dot` draws them.
+    pub fn to_dot(&self) -> String {
+        let escape = |val: &str| {
+            val.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+        };
+        let mut out = String::from("digraph G {\n  newrank = true;\n  rankdir = TB;\n");
+        for node in &self.nodes {
+            let color = match (node.leaf, node.param) {
+                (_, true) => "yellow",
+                (true, _) => "pink",
+                _ => "white",
+            };
+            let label = node.lines().map(|line| escape(&line)).join("\\n");
+            let _ = writeln!(
+                out,
+                "  n{} [shape = box, style = filled, fillcolor = {color}, label = \"{label}\"];",
+                node.id
+            );
+        }
+        for node in &self.nodes {
+            for (slot, src) in &node.sources {
+                let _ = writeln!(out, "  n{src} -> n{} [label = \"src{slot}\"];", node.id);
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// A JSON object with a `nodes` array of objects holding each tensor's `id`, `name`, `op`,
+    /// `type`, `shape`, `leaf` and `param` fields and its `sources` as objects with the operand
+    /// `slot` and `id`.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"nodes\": [");
+        for (i, node) in self.nodes.iter().enumerate() {
+            let sources = node
+                .sources
+                .iter()
+                .map(|(slot, id)| format!("{{\"slot\": {slot}, \"id\": {id}}}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = write!(
+                out,
+                "{}\n  {{\"id\": {}, \"name\": {}, \"op\": {}, \"type\": {}, \"shape\": {:?}, \
+                 \"leaf\": {}, \"param\": {}, \"sources\": [{}]}}",
+                if i == 0 { "" } else { "," },
+                node.id,
+                json_string(&node.name),
+                json_string(node.op),
+                json_string(node.type_name),
+                node.shape,
+                node.leaf,
+                node.param,
+                sources,
+            );
+        }
+        out.push_str("\n]}\n");
+        out
+    }
+
+    /// A top to bottom `flowchart` with leafs as rounded boxes, for Markdown renderers that
+    /// support Mermaid.
+    pub fn to_mermaid(&self) -> String {
+        // Mermaid labels can't contain quotes, but take entity codes and line breaks.
+        let escape = |val: &str| {
+            val.replace('#', "#35;")
+                .replace('&', "#amp;")
+                .replace('"', "#quot;")
+                .replace('<', "#lt;")
+                .replace('>', "#gt;")
+                .replace('\n', "<br/>")
+        };
+        let mut out = String::from("flowchart TD\n");
+        for node in &self.nodes {
+            let label = node.lines().map(|line| escape(&line)).join("<br/>");
+            let (open, close) = if node.leaf { ("(", ")") } else { ("[", "]") };
+            let _ = writeln!(out, "  n{}{open}\"{label}\"{close}", node.id);
+        }
+        for node in &self.nodes {
+            for (_, src) in &node.sources {
+                let _ = writeln!(out, "  n{src} --> n{}", node.id);
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,128 @@
+use std::{fmt, path::PathBuf};
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Errors from the safe wrappers. Anything GGML would otherwise abort on is checked up front
+/// and reported here instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// `ggml_init` failed, usually because all `GGML_MAX_CONTEXTS` contexts are in use.
+    ContextInit,
+    /// The context's memory buffer doesn't have room for the requested allocation.
+    OutOfMemory { needed: usize, available: usize },
+    /// The requested tensor shape isn't valid for its type.
+    InvalidShape {
+        type_: crate::ggml_type,
+        shape: Vec<i64>,
+    },
+    /// The tensor's type doesn't match the Rust type used to access it.
+    TypeMismatch {
+        expected: crate::ggml_type,
+        actual: crate::ggml_type,
+    },
+    /// The operation requires a contiguous tensor.
+    NotContiguous,
+    /// The tensor has no data allocated.
+    NoData,
+    /// A slice doesn't have the number of elements the tensor does.
+    LengthMismatch { expected: usize, actual: usize },
+    /// The tensor data is borrowed by a [`TensorSlice`](crate::TensorSlice), so it can't be
+    /// written to, or mutably borrowed as an array, so it can't be used at all.
+    DataBorrowed,
+    /// The operands of an operation have incompatible shapes or types.
+    Shape(crate::shape::ShapeError),
+    /// The graph doesn't have room for any more nodes or leafs.
+    GraphFull { size: usize },
+    /// Computing the graph was stopped by the abort callback.
+    Aborted,
+    /// `ggml_graph_compute` failed with the given status.
+    Compute(crate::ggml_status),
+    /// The type can't be used for this, i.e. it can't be quantized to.
+    UnsupportedType(crate::ggml_type),
+    /// Quantizing to this type requires an importance matrix.
+    MissingImatrix(crate::ggml_type),
+    /// `ggml_validate_row_data` rejected the data (i.e. NaN or infinite values).
+    InvalidData(crate::ggml_type),
+    /// The graph has no gradients, either because it was created without room for them or
+    /// because nothing in it depends on a parameter.
+    NoGradients,
+    /// GGML can't compute the gradient of this operation (`ggml_op_desc`).
+    NotDifferentiable(&'static str),
+    /// The optimizer failed with the given `ggml_opt_result`.
+    Optimizer(crate::ggml_opt_result),
+    /// `gguf_init_from_file` couldn't load the file.
+    GgufLoad { path: PathBuf },
+    /// Writing a GGUF file failed.
+    GgufWrite {
+        path: PathBuf,
+        kind: std::io::ErrorKind,
+    },
+    /// A checkpoint doesn't match the optimizer it's loaded into.
+    Checkpoint(String),
+    /// Exporting or importing a graph failed, or the file isn't one GGML can import.
+    GraphFile { path: PathBuf, msg: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ContextInit => write!(
+                f,
+                "ggml_init failed (more than {} contexts?)",
+                crate::GGML_MAX_CONTEXTS
+            ),
+            Self::OutOfMemory { needed, available } => write!(
+                f,
+                "context out of memory: needed {needed} bytes, {available} available"
+            ),
+            Self::InvalidShape { type_, shape } => {
+                write!(f, "invalid shape {shape:?} for tensor type {type_}")
+            }
+            Self::TypeMismatch { expected, actual } => {
+                write!(f, "expected tensor type {expected}, got {actual}")
+            }
+            Self::NotContiguous => write!(f, "tensor is not contiguous"),
+            Self::NoData => write!(f, "tensor has no data"),
+            Self::LengthMismatch { expected, actual } => {
+                write!(f, "expected {expected} elements, got {actual}")
+            }
+            Self::DataBorrowed => write!(f, "tensor data is borrowed"),
+            Self::Shape(err) => write!(f, "{err}"),
+            Self::GraphFull { size } => write!(f, "graph is full ({size} nodes)"),
+            Self::Aborted => write!(f, "graph computation aborted"),
+            Self::Compute(status) => write!(f, "graph computation failed with status {status}"),
+            Self::UnsupportedType(type_) => write!(f, "unsupported tensor type {type_}"),
+            Self::MissingImatrix(type_) => {
+                write!(
+                    f,
+                    "quantizing to type {type_} requires an importance matrix"
+                )
+            }
+            Self::InvalidData(type_) => write!(f, "invalid data for tensor type {type_}"),
+            Self::NoGradients => write!(f, "graph has no gradients"),
+            Self::NotDifferentiable(op) => write!(f, "operation {op} has no backward pass"),
+            Self::Optimizer(result) => write!(f, "optimizer failed with result {result}"),
+            Self::GgufLoad { path } => write!(f, "failed to load GGUF file {}", path.display()),
+            Self::GgufWrite { path, kind } => {
+                write!(f, "failed to write GGUF file {}: {kind}", path.display())
+            }
+            Self::Checkpoint(msg) => write!(f, "invalid checkpoint: {msg}"),
+            Self::GraphFile { path, msg } => write!(f, "graph file {}: {msg}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Shape(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<crate::shape::ShapeError> for Error {
+    fn from(err: crate::shape::ShapeError) -> Self {
+        Self::Shape(err)
+    }
+}
@@ -0,0 +1,80 @@
+//! Interop between GGML's half precision types and the [`half`] crate.
+//!
+//! `ggml_fp16_t` is a `u16` with the same bits as a [`half::f16`] and `ggml_bf16_t` wraps the
+//! bits of a [`half::bf16`], so converting between them is free: [`f16_from_ggml`] and friends
+//! for single values and [`bytemuck`] (which both types implement `Pod` for) for slices.
+//!
+//! The `*_slice` functions convert whole slices with GGML's row converters, which use F16C
+//! and NEON when available. Run `cargo bench --features half` to compare them with `half`'s
+//! own `HalfFloatSliceExt`.
+
+use half::{bf16, f16};
+
+use crate::{context, *};
+
+unsafe impl bytemuck::Zeroable for ggml_bf16_t {}
+unsafe impl bytemuck::Pod for ggml_bf16_t {}
+
+impl From<bf16> for ggml_bf16_t {
+    fn from(val: bf16) -> Self {
+        Self {
+            bits: val.to_bits(),
+        }
+    }
+}
+
+impl From<ggml_bf16_t> for bf16 {
+    fn from(val: ggml_bf16_t) -> Self {
+        bf16::from_bits(val.bits)
+    }
+}
+
+pub fn f16_from_ggml(val: ggml_fp16_t) -> f16 {
+    f16::from_bits(val)
+}
+
+pub fn f16_to_ggml(val: f16) -> ggml_fp16_t {
+    val.to_bits()
+}
+
+/// Views `ggml_fp16_t` data as [`half::f16`].
+pub fn f16_slice(data: &[ggml_fp16_t]) -> &[f16] {
+    bytemuck::cast_slice(data)
+}
+
+/// Views `ggml_bf16_t` data as [`half::bf16`].
+pub fn bf16_slice(data: &[ggml_bf16_t]) -> &[bf16] {
+    bytemuck::cast_slice(data)
+}
+
+fn check_len(src: usize, dst: usize) {
+    assert_eq!(
+        src, dst,
+        "Source and destination slices have different lengths"
+    );
+}
+
+/// Converts `src` to F16 with `ggml_fp32_to_fp16_row`. Panics if the lengths differ.
+pub fn f32_to_f16_slice(src: &[f32], dst: &mut [f16]) {
+    check_len(src.len(), dst.len());
+    unsafe { ggml_fp32_to_fp16_row(src.as_ptr(), dst.as_mut_ptr().cast(), src.len() as i64) }
+}
+
+/// Converts `src` to F32 with `ggml_fp16_to_fp32_row`. Panics if the lengths differ.
+pub fn f16_to_f32_slice(src: &[f16], dst: &mut [f32]) {
+    check_len(src.len(), dst.len());
+    context::init_tables();
+    unsafe { ggml_fp16_to_fp32_row(src.as_ptr().cast(), dst.as_mut_ptr(), src.len() as i64) }
+}
+
+/// Converts `src` to BF16 with `ggml_fp32_to_bf16_row`. Panics if the lengths differ.
+pub fn f32_to_bf16_slice(src: &[f32], dst: &mut [bf16]) {
+    check_len(src.len(), dst.len());
+    unsafe { ggml_fp32_to_bf16_row(src.as_ptr(), dst.as_mut_ptr().cast(), src.len() as i64) }
+}
+
+/// Converts `src` to F32 with `ggml_bf16_to_fp32_row`. Panics if the lengths differ.
+pub fn bf16_to_f32_slice(src: &[bf16], dst: &mut [f32]) {
+    check_len(src.len(), dst.len());
+    unsafe { ggml_bf16_to_fp32_row(src.as_ptr().cast(), dst.as_mut_ptr(), src.len() as i64) }
+}
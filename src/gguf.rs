@@ -0,0 +1,212 @@
+//! Minimal GGUF reading and writing for the wrappers that store tensors in files.
+
+use std::{
+    ffi::{CStr, CString},
+    fs::File,
+    io::{BufWriter, Write},
+    os::raw::c_int,
+    path::Path,
+};
+
+use crate::{
+    context::Context,
+    error::{Error, Result},
+    shape::Checker,
+    tensor::{ensure_readable, Tensor},
+    *,
+};
+
+fn key(key: &str) -> CString {
+    CString::new(key).expect("GGUF key contains a NUL byte")
+}
+
+/// A GGUF file loaded along with its tensor data (`gguf_init_from_file`).
+pub(crate) struct GgufFile {
+    gguf: *mut gguf_context,
+    ctx: Context,
+}
+
+macro_rules! getters {
+    ($($name:ident -> $ty:ty => $get:ident, $gguf_type:ident;)*) => {
+        $(
+            /// The value of `key`, if it exists and has the right type.
+            pub(crate) fn $name(&self, key: &str) -> Option<$ty> {
+                let id = self.find(key, $gguf_type)?;
+                Some(unsafe { $get(self.gguf, id) })
+            }
+        )*
+    };
+}
+
+impl GgufFile {
+    pub(crate) fn open(path: &Path) -> Result<Self> {
+        let err = || Error::GgufLoad {
+            path: path.to_path_buf(),
+        };
+        let fname = path
+            .to_str()
+            .and_then(|path| CString::new(path).ok())
+            .ok_or_else(err)?;
+        let mut ctx = std::ptr::null_mut();
+        let params = gguf_init_params {
+            no_alloc: false,
+            ctx: &mut ctx,
+        };
+        let gguf = unsafe { gguf_init_from_file(fname.as_ptr(), params) };
+        if gguf.is_null() {
+            return Err(err());
+        }
+        if ctx.is_null() {
+            unsafe { gguf_free(gguf) };
+            return Err(err());
+        }
+        Ok(Self {
+            gguf,
+            ctx: unsafe { Context::from_raw(ctx) },
+        })
+    }
+
+    /// The tensors in the file along with their names, in file order.
+    pub(crate) fn tensors(&self) -> impl Iterator<Item = (String, Tensor<'_>)> + '_ {
+        let n_tensors = unsafe { gguf_get_n_tensors(self.gguf) };
+        (0..n_tensors).filter_map(|i| {
+            let name = unsafe { CStr::from_ptr(gguf_get_tensor_name(self.gguf, i)) };
+            let name = name.to_string_lossy().into_owned();
+            let tensor = self.ctx.get_tensor(&name)?;
+            Some((name, tensor))
+        })
+    }
+
+    pub(crate) fn tensor(&self, name: &str) -> Option<Tensor<'_>> {
+        self.ctx.get_tensor(name)
+    }
+
+    fn find(&self, key: &str, type_: gguf_type) -> Option<c_int> {
+        let id = unsafe { gguf_find_key(self.gguf, self::key(key).as_ptr()) };
+        (id >= 0 && unsafe { gguf_get_kv_type(self.gguf, id) } == type_).then_some(id)
+    }
+
+    getters! {
+        get_u32 -> u32 => gguf_get_val_u32, gguf_type_GGUF_TYPE_UINT32;
+        get_u64 -> u64 => gguf_get_val_u64, gguf_type_GGUF_TYPE_UINT64;
+        get_i32 -> i32 => gguf_get_val_i32, gguf_type_GGUF_TYPE_INT32;
+        get_f32 -> f32 => gguf_get_val_f32, gguf_type_GGUF_TYPE_FLOAT32;
+        get_bool -> bool => gguf_get_val_bool, gguf_type_GGUF_TYPE_BOOL;
+    }
+
+    pub(crate) fn get_str(&self, key: &str) -> Option<String> {
+        let id = self.find(key, gguf_type_GGUF_TYPE_STRING)?;
+        let val = unsafe { CStr::from_ptr(gguf_get_val_str(self.gguf, id)) };
+        Some(val.to_string_lossy().into_owned())
+    }
+}
+
+impl Drop for GgufFile {
+    fn drop(&mut self) {
+        unsafe { gguf_free(self.gguf) }
+    }
+}
+
+/// Builds a GGUF file in memory and writes it out. Only the metadata is serialized by GGML
+/// (`gguf_get_meta_data`), the tensor data is written from Rust so I/O errors are reported
+/// instead of aborting like `gguf_write_to_file` does.
+pub(crate) struct GgufWriter<'a> {
+    gguf: *mut gguf_context,
+    // Holds views of the added tensors, named as they're stored in the file.
+    views: Context,
+    tensors: Vec<Tensor<'a>>,
+}
+
+macro_rules! setters {
+    ($($name:ident($ty:ty) => $set:ident;)*) => {
+        $(
+            pub(crate) fn $name(&mut self, key: &str, val: $ty) {
+                unsafe { $set(self.gguf, self::key(key).as_ptr(), val) }
+            }
+        )*
+    };
+}
+
+impl<'a> GgufWriter<'a> {
+    /// A writer with room for `n_tensors` tensors.
+    pub(crate) fn new(n_tensors: usize) -> Result<Self> {
+        let views = Context::new_no_alloc(n_tensors.max(1) * unsafe { ggml_tensor_overhead() })?;
+        Ok(Self {
+            gguf: unsafe { gguf_init_empty() },
+            views,
+            tensors: Vec::new(),
+        })
+    }
+
+    setters! {
+        set_u32(u32) => gguf_set_val_u32;
+        set_u64(u64) => gguf_set_val_u64;
+        set_i32(i32) => gguf_set_val_i32;
+        set_f32(f32) => gguf_set_val_f32;
+        set_bool(bool) => gguf_set_val_bool;
+    }
+
+    pub(crate) fn set_str(&mut self, key: &str, val: &str) {
+        let val = CString::new(val).expect("GGUF value contains a NUL byte");
+        unsafe { gguf_set_val_str(self.gguf, self::key(key).as_ptr(), val.as_ptr()) }
+    }
+
+    /// Adds the data of `tensor`, which must be contiguous, under `name`. Names have to be
+    /// unique, non-empty and shorter than `GGML_MAX_NAME`.
+    pub(crate) fn add_tensor(&mut self, name: &str, tensor: Tensor<'a>) -> Result<()> {
+        let c = Checker("gguf_add_tensor");
+        c.arg(
+            !name.is_empty() && name.len() < GGML_MAX_NAME as usize && !name.contains('\0'),
+            || format!("invalid tensor name {name:?}"),
+        )?;
+        c.arg(self.views.get_tensor(name).is_none(), || {
+            format!("duplicate tensor name {name:?}")
+        })?;
+        if !tensor.is_contiguous() {
+            return Err(Error::NotContiguous);
+        }
+        if tensor.data_ptr().is_null() {
+            return Err(Error::NoData);
+        }
+        self.views
+            .ensure_room(tensor.type_(), tensor.shape(), true)?;
+        let view = unsafe { ggml_view_tensor(self.views.as_ptr(), tensor.as_ptr()) };
+        let view = unsafe { Tensor::from_raw(&self.views, view) }
+            .expect("ggml_view_tensor returned null")
+            .set_name(name);
+        unsafe { gguf_add_tensor(self.gguf, view.as_ptr()) };
+        self.tensors.push(tensor);
+        Ok(())
+    }
+
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let err = |err: std::io::Error| Error::GgufWrite {
+            path: path.to_path_buf(),
+            kind: err.kind(),
+        };
+        ensure_readable(self.tensors.iter().map(|tensor| tensor.data_range()))?;
+        let mut meta = vec![0u8; unsafe { gguf_get_meta_size(self.gguf) }];
+        unsafe { gguf_get_meta_data(self.gguf, meta.as_mut_ptr().cast()) };
+        let alignment = unsafe { gguf_get_alignment(self.gguf) };
+
+        let mut file = BufWriter::new(File::create(path).map_err(err)?);
+        // The metadata is already padded to the alignment, and each tensor's offset is the
+        // previous one's padded size.
+        file.write_all(&meta).map_err(err)?;
+        for tensor in &self.tensors {
+            let data = unsafe {
+                std::slice::from_raw_parts(tensor.data_ptr().cast::<u8>(), tensor.nbytes())
+            };
+            file.write_all(data).map_err(err)?;
+            let padding = data.len().next_multiple_of(alignment) - data.len();
+            file.write_all(&vec![0; padding]).map_err(err)?;
+        }
+        file.flush().map_err(err)
+    }
+}
+
+impl Drop for GgufWriter<'_> {
+    fn drop(&mut self) {
+        unsafe { gguf_free(self.gguf) }
+    }
+}
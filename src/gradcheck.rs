@@ -0,0 +1,417 @@
+//! Checking GGML's gradients against finite differences.
+//!
+//! [`GradCheck::check`] builds the backward graph of a scalar loss
+//! (`ggml_build_backward_expand`), computes the gradients of every parameter and compares
+//! each with the central difference `(loss(x + eps) - loss(x - eps)) / 2 eps`, perturbing one
+//! element at a time. [`GradCheck::check_explicit`] does the same for gradients built by hand
+//! from the `_back` operations, i.e. to test `ggml_flash_attn_back` whose forward operation
+//! can't be differentiated. The resulting [`GradReport`] has the errors of each input and,
+//! with [`GradReport::by_op`], of each operation the inputs feed into.
+//!
+//! GGML aborts when building the backward pass of an operation it can't differentiate, so
+//! graphs are checked against the operations it can first (see [`has_backward`]).
+
+use std::fmt::{self, Write};
+
+use crate::{
+    error::{Error, Result},
+    graph::Graph,
+    tensor::Tensor,
+    *,
+};
+
+/// Whether `ggml_build_backward_expand` can differentiate `tensor`'s operation. Operations
+/// missing here abort (or, for `FLASH_ATTN_EXT`, fail an assertion) when one of their
+/// operands needs a gradient.
+pub fn has_backward(tensor: &Tensor) -> bool {
+    let op = tensor.op();
+    if op == ggml_op_GGML_OP_UNARY {
+        let unary = unsafe { ggml_get_unary_op(tensor.as_ptr()) };
+        return [
+            ggml_unary_op_GGML_UNARY_OP_ABS,
+            ggml_unary_op_GGML_UNARY_OP_SGN,
+            ggml_unary_op_GGML_UNARY_OP_NEG,
+            ggml_unary_op_GGML_UNARY_OP_STEP,
+            ggml_unary_op_GGML_UNARY_OP_RELU,
+            ggml_unary_op_GGML_UNARY_OP_SILU,
+        ]
+        .contains(&unary);
+    }
+    [
+        ggml_op_GGML_OP_NONE,
+        ggml_op_GGML_OP_DUP,
+        ggml_op_GGML_OP_ADD,
+        ggml_op_GGML_OP_ADD1,
+        ggml_op_GGML_OP_ACC,
+        ggml_op_GGML_OP_SUB,
+        ggml_op_GGML_OP_MUL,
+        ggml_op_GGML_OP_DIV,
+        ggml_op_GGML_OP_SQR,
+        ggml_op_GGML_OP_SQRT,
+        ggml_op_GGML_OP_LOG,
+        ggml_op_GGML_OP_SUM,
+        ggml_op_GGML_OP_SUM_ROWS,
+        ggml_op_GGML_OP_REPEAT,
+        ggml_op_GGML_OP_REPEAT_BACK,
+        ggml_op_GGML_OP_RMS_NORM,
+        ggml_op_GGML_OP_MUL_MAT,
+        ggml_op_GGML_OP_SCALE,
+        ggml_op_GGML_OP_SET,
+        ggml_op_GGML_OP_CPY,
+        ggml_op_GGML_OP_CONT,
+        ggml_op_GGML_OP_RESHAPE,
+        ggml_op_GGML_OP_VIEW,
+        ggml_op_GGML_OP_PERMUTE,
+        ggml_op_GGML_OP_TRANSPOSE,
+        ggml_op_GGML_OP_GET_ROWS,
+        ggml_op_GGML_OP_DIAG_MASK_INF,
+        ggml_op_GGML_OP_DIAG_MASK_ZERO,
+        ggml_op_GGML_OP_SOFT_MAX,
+        ggml_op_GGML_OP_ROPE,
+        ggml_op_GGML_OP_ROPE_BACK,
+        ggml_op_GGML_OP_CROSS_ENTROPY_LOSS,
+    ]
+    .contains(&op)
+}
+
+/// Settings for checking gradients, see the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GradCheck {
+    /// How much each element is perturbed by.
+    pub eps: f32,
+    /// A gradient passes if it differs from the finite difference by at most
+    /// `atol + rtol * |finite difference|`.
+    pub atol: f32,
+    pub rtol: f32,
+    /// Elements checked per input, evenly spaced if the input has more.
+    pub max_elements: usize,
+    /// Nodes (and leafs) the graphs have room for.
+    pub graph_size: usize,
+    pub n_threads: usize,
+}
+
+impl Default for GradCheck {
+    fn default() -> Self {
+        Self {
+            eps: 1e-3,
+            atol: 1e-3,
+            rtol: 1e-2,
+            max_elements: 256,
+            graph_size: GGML_DEFAULT_GRAPH_SIZE as usize,
+            n_threads: 1,
+        }
+    }
+}
+
+impl GradCheck {
+    /// Checks the gradients GGML computes for every parameter `loss` depends on. `loss` must
+    /// be an F32 scalar and the parameters F32 and contiguous. The backward graph is
+    /// allocated in the loss' context, see [`Graph::backward`].
+    pub fn check<'ctx>(&self, loss: Tensor<'ctx>) -> Result<GradReport> {
+        check_loss(loss)?;
+        let grad = loss.grad().ok_or(Error::NoGradients)?;
+        let mut forward = Graph::with_size(loss.context(), self.graph_size, true)?;
+        forward.build_forward(&loss)?;
+        if let Some(node) = forward
+            .nodes()
+            .find(|node| node.grad().is_some() && !has_backward(node))
+        {
+            return Err(Error::NotDifferentiable(node.op_desc()));
+        }
+        let inputs = forward.nodes().filter(Tensor::is_param).collect::<Vec<_>>();
+        inputs.iter().try_for_each(|input| check_input(*input))?;
+
+        let mut backward = forward.backward(false)?;
+        // `ggml_graph_reset` zeroes the gradients, so they need data too.
+        backward.ensure_data()?;
+        unsafe { ggml_graph_reset(forward.as_ptr()) };
+        grad.set_f32([0], 1.0)?;
+        backward.compute(self.n_threads)?;
+        // `backward` replaced the parameters' gradients with the operations computing them.
+        let inputs = inputs
+            .into_iter()
+            .map(|input| {
+                let grad = input.grad().expect("parameter without a gradient");
+                Ok((input, values(grad)?))
+            })
+            .collect::<Result<_>>()?;
+        self.compare(&mut forward, loss, inputs)
+    }
+
+    /// Checks gradients built by hand: each pair is an input of `loss` (F32 and contiguous)
+    /// and a tensor with the gradient of `loss` with respect to it, in the same order. The
+    /// inputs needn't be parameters and nothing is differentiated by GGML.
+    pub fn check_explicit<'ctx>(
+        &self,
+        loss: Tensor<'ctx>,
+        grads: &[(Tensor<'ctx>, Tensor<'ctx>)],
+    ) -> Result<GradReport> {
+        check_loss(loss)?;
+        let ctx = loss.context();
+        let mut analytic = Graph::with_size(ctx, self.graph_size, false)?;
+        for (input, grad) in grads {
+            check_input(*input)?;
+            if grad.nelements() != input.nelements() {
+                return Err(Error::LengthMismatch {
+                    expected: input.nelements() as usize,
+                    actual: grad.nelements() as usize,
+                });
+            }
+            analytic.build_forward(grad)?;
+        }
+        analytic.compute(self.n_threads)?;
+        let inputs = grads
+            .iter()
+            .map(|(input, grad)| Ok((*input, values(*grad)?)))
+            .collect::<Result<_>>()?;
+
+        let mut forward = Graph::with_size(ctx, self.graph_size, false)?;
+        forward.build_forward(&loss)?;
+        self.compare(&mut forward, loss, inputs)
+    }
+
+    fn compare<'ctx>(
+        &self,
+        forward: &mut Graph<'ctx>,
+        loss: Tensor<'ctx>,
+        inputs: Vec<(Tensor<'ctx>, Vec<f32>)>,
+    ) -> Result<GradReport> {
+        let compute = |forward: &mut Graph<'ctx>| -> Result<f64> {
+            forward.compute(self.n_threads)?;
+            Ok(loss.get_f32([0])? as f64)
+        };
+
+        let mut entries = Vec::with_capacity(inputs.len());
+        for (i, (input, analytic)) in inputs.into_iter().enumerate() {
+            let mut ops = Vec::new();
+            for node in forward.nodes() {
+                let op = node.op_desc();
+                if node.sources().any(|src| src == input) && !ops.contains(&op) {
+                    ops.push(op);
+                }
+            }
+            let name = input.name();
+            let mut entry = GradEntry {
+                name: if name.is_empty() {
+                    format!("input {i}")
+                } else {
+                    name.into_owned()
+                },
+                ops,
+                checked: 0,
+                max_abs_error: 0.0,
+                max_rel_error: 0.0,
+                worst: None,
+                passed: true,
+            };
+
+            let n = analytic.len();
+            if n == 0 {
+                entries.push(entry);
+                continue;
+            }
+            let step = n.div_ceil(self.max_elements.max(1));
+            for index in (0..n).step_by(step) {
+                let element = unravel(input, index);
+                let x = input.get_f32(element)?;
+                input.set_f32(element, x + self.eps)?;
+                let plus = compute(forward);
+                input.set_f32(element, x - self.eps)?;
+                let minus = compute(forward);
+                input.set_f32(element, x)?;
+                let numeric = ((plus? - minus?) / (2.0 * self.eps as f64)) as f32;
+                entry.add(index, analytic[index], numeric, self);
+            }
+            entries.push(entry);
+        }
+        // Leave the nodes computed from the unperturbed inputs.
+        compute(forward)?;
+        Ok(GradReport { entries })
+    }
+}
+
+fn check_loss(loss: Tensor) -> Result<()> {
+    if loss.type_() != ggml_type_GGML_TYPE_F32 {
+        return Err(Error::TypeMismatch {
+            expected: ggml_type_GGML_TYPE_F32,
+            actual: loss.type_(),
+        });
+    }
+    if loss.nelements() != 1 {
+        return Err(Error::InvalidShape {
+            type_: loss.type_(),
+            shape: loss.shape().to_vec(),
+        });
+    }
+    Ok(())
+}
+
+fn check_input(input: Tensor) -> Result<()> {
+    if input.type_() != ggml_type_GGML_TYPE_F32 {
+        return Err(Error::TypeMismatch {
+            expected: ggml_type_GGML_TYPE_F32,
+            actual: input.type_(),
+        });
+    }
+    if !input.is_contiguous() {
+        return Err(Error::NotContiguous);
+    }
+    if input.data_ptr().is_null() {
+        return Err(Error::NoData);
+    }
+    Ok(())
+}
+
+/// The elements of a computed tensor as F32, in logical order.
+fn values(tensor: Tensor) -> Result<Vec<f32>> {
+    (0..tensor.nelements() as usize)
+        .map(|i| tensor.get_f32(unravel(tensor, i)))
+        .collect()
+}
+
+/// The index of the `i`th element of `tensor` in logical order.
+fn unravel(tensor: Tensor, mut i: usize) -> [usize; 4] {
+    let mut index = [0; 4];
+    for (idx, ne) in index.iter_mut().zip(tensor.ne()) {
+        *idx = i % ne as usize;
+        i /= ne as usize;
+    }
+    index
+}
+
+/// The worst mismatch found for an input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mismatch {
+    /// Index of the element, in logical order.
+    pub index: usize,
+    pub analytic: f32,
+    pub numeric: f32,
+}
+
+/// The results for one input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradEntry {
+    /// The input's name, or `input <i>` if it has none.
+    pub name: String,
+    /// The operations (`ggml_op_desc`) that take the input as an operand.
+    pub ops: Vec<&'static str>,
+    /// Number of elements checked.
+    pub checked: usize,
+    pub max_abs_error: f32,
+    /// `|analytic - numeric| / max(|analytic|, |numeric|)`, 0 where both are.
+    pub max_rel_error: f32,
+    /// The element with the largest absolute error.
+    pub worst: Option<Mismatch>,
+    /// Whether every element was within the tolerances.
+    pub passed: bool,
+}
+
+impl GradEntry {
+    fn add(&mut self, index: usize, analytic: f32, numeric: f32, check: &GradCheck) {
+        let abs_error = (analytic - numeric).abs();
+        let scale = analytic.abs().max(numeric.abs());
+        let rel_error = if scale > 0.0 { abs_error / scale } else { 0.0 };
+        self.checked += 1;
+        // NaN errors fail and count as the worst.
+        if abs_error.is_nan() || abs_error > self.max_abs_error {
+            self.max_abs_error = abs_error;
+            self.worst = Some(Mismatch {
+                index,
+                analytic,
+                numeric,
+            });
+        }
+        self.max_rel_error = self.max_rel_error.max(rel_error);
+        self.passed &= abs_error <= check.atol + check.rtol * numeric.abs();
+    }
+}
+
+/// The largest errors of the inputs an operation takes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpErrors {
+    pub op: &'static str,
+    pub max_abs_error: f32,
+    pub max_rel_error: f32,
+    pub passed: bool,
+}
+
+/// The results of a [`GradCheck`], one entry per input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradReport {
+    pub entries: Vec<GradEntry>,
+}
+
+impl GradReport {
+    /// Whether every input passed.
+    pub fn passed(&self) -> bool {
+        self.entries.iter().all(|entry| entry.passed)
+    }
+
+    /// The errors grouped by the operations the inputs feed into, in order of appearance. An
+    /// input used by several operations counts for each of them.
+    pub fn by_op(&self) -> Vec<OpErrors> {
+        let mut ops = Vec::<OpErrors>::new();
+        for entry in &self.entries {
+            for op in &entry.ops {
+                let errors = match ops.iter_mut().find(|errors| errors.op == *op) {
+                    Some(errors) => errors,
+                    None => {
+                        ops.push(OpErrors {
+                            op,
+                            max_abs_error: 0.0,
+                            max_rel_error: 0.0,
+                            passed: true,
+                        });
+                        ops.last_mut().unwrap()
+                    }
+                };
+                errors.max_abs_error = errors.max_abs_error.max(entry.max_abs_error);
+                errors.max_rel_error = errors.max_rel_error.max(entry.max_rel_error);
+                errors.passed &= entry.passed;
+            }
+        }
+        ops
+    }
+
+    /// The report as a table with one row per input, followed by one per operation.
+    pub fn to_table(&self) -> String {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.name.len())
+            .chain(self.by_op().iter().map(|errors| errors.op.len()))
+            .chain(Some(5))
+            .max()
+            .unwrap_or(5);
+        let mut out = format!(
+            "{:width$}  {:>8}  {:>12}  {:>12}  {:>6}\n",
+            "input", "checked", "max abs", "max rel", "passed"
+        );
+        for entry in &self.entries {
+            let _ = writeln!(
+                out,
+                "{:width$}  {:>8}  {:>12.6e}  {:>12.6e}  {:>6}",
+                entry.name, entry.checked, entry.max_abs_error, entry.max_rel_error, entry.passed,
+            );
+        }
+        let _ = writeln!(
+            out,
+            "\n{:width$}  {:>8}  {:>12}  {:>12}  {:>6}",
+            "op", "", "max abs", "max rel", "passed"
+        );
+        for errors in self.by_op() {
+            let _ = writeln!(
+                out,
+                "{:width$}  {:>8}  {:>12.6e}  {:>12.6e}  {:>6}",
+                errors.op, "", errors.max_abs_error, errors.max_rel_error, errors.passed,
+            );
+        }
+        out
+    }
+}
+
+impl fmt::Display for GradReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
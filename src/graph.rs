@@ -0,0 +1,271 @@
+use std::{collections::HashSet, ffi::CString, ptr::NonNull};
+
+use crate::{
+    callback::{abort_trampoline, AbortFn, Callback},
+    context::Context,
+    custom::take_panic,
+    error::{Error, Result},
+    tensor::{ensure_readable, ensure_writable, Tensor},
+    *,
+};
+
+/// Upper bound of the tensors `ggml_compute_backward` creates for each operand with a gradient,
+/// and of how many of them need data, used by [`Graph::backward`]. The gradient operations are
+/// at most as large as the largest of the node and its operands.
+const BACKWARD_TENSORS: (usize, usize) = (8, 4);
+
+/// A computation graph (`ggml_cgraph`) allocated in a [`Context`], along with the work buffer
+/// used to compute it. The buffer is kept between calls to [`Graph::compute`] and only grows
+/// when a plan needs more space than the last one did.
+pub struct Graph<'ctx> {
+    ptr: NonNull<ggml_cgraph>,
+    ctx: &'ctx Context,
+    // `u64` so the buffer is suitably aligned for the floats GGML stores in it.
+    work: Vec<u64>,
+    abort: Option<Box<Callback<AbortFn<'ctx>>>>,
+}
+
+impl<'ctx> Graph<'ctx> {
+    /// Creates a graph with room for `GGML_DEFAULT_GRAPH_SIZE` nodes and no gradients.
+    pub fn new(ctx: &'ctx Context) -> Result<Self> {
+        Self::with_size(ctx, GGML_DEFAULT_GRAPH_SIZE as usize, false)
+    }
+
+    /// Creates a graph with room for `size` nodes (and as many leafs), optionally with space
+    /// for gradients (`ggml_new_graph_custom`).
+    pub fn with_size(ctx: &'ctx Context, size: usize, grads: bool) -> Result<Self> {
+        let needed = unsafe { ggml_graph_overhead_custom(size, grads) };
+        let available = ctx.mem_size() - ctx.used_mem();
+        if needed > available {
+            return Err(Error::OutOfMemory { needed, available });
+        }
+        let ptr = unsafe { ggml_new_graph_custom(ctx.as_ptr(), size, grads) };
+        Ok(Self {
+            ptr: NonNull::new(ptr).expect("ggml_new_graph_custom returned NULL"),
+            ctx,
+            work: Vec::new(),
+            abort: None,
+        })
+    }
+
+    /// Wraps a graph allocated in `ctx` by someone else, i.e. `ggml_graph_import`.
+    pub(crate) unsafe fn from_raw(ctx: &'ctx Context, ptr: *mut ggml_cgraph) -> Self {
+        Self {
+            ptr: NonNull::new(ptr).expect("NULL graph"),
+            ctx,
+            work: Vec::new(),
+            abort: None,
+        }
+    }
+
+    pub fn as_ptr(&self) -> *mut ggml_cgraph {
+        self.ptr.as_ptr()
+    }
+
+    pub fn context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    fn raw(&self) -> &ggml_cgraph {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Maximum number of nodes (and leafs) the graph can hold.
+    pub fn size(&self) -> usize {
+        self.raw().size as usize
+    }
+
+    pub fn n_nodes(&self) -> usize {
+        self.raw().n_nodes as usize
+    }
+
+    pub fn n_leafs(&self) -> usize {
+        self.raw().n_leafs as usize
+    }
+
+    /// The tensors computed by the graph, in evaluation order.
+    pub fn nodes(&self) -> impl Iterator<Item = Tensor<'ctx>> + '_ {
+        self.tensors(self.raw().nodes, self.n_nodes())
+    }
+
+    /// The graph's inputs and constants.
+    pub fn leafs(&self) -> impl Iterator<Item = Tensor<'ctx>> + '_ {
+        self.tensors(self.raw().leafs, self.n_leafs())
+    }
+
+    /// The leaf or node named `name`, if any (`ggml_graph_get_tensor`). Building the graph
+    /// names the tensors that don't have a name `leaf_{i}` and `node_{i}`.
+    pub fn get_tensor(&self, name: &str) -> Option<Tensor<'ctx>> {
+        let name = CString::new(name).ok()?;
+        let ptr = unsafe { ggml_graph_get_tensor(self.as_ptr(), name.as_ptr()) };
+        unsafe { Tensor::from_raw(self.ctx, ptr) }
+    }
+
+    fn tensors(
+        &self,
+        ptrs: *mut *mut ggml_tensor,
+        len: usize,
+    ) -> impl Iterator<Item = Tensor<'ctx>> + '_ {
+        let ptrs = if len == 0 {
+            &[][..]
+        } else {
+            unsafe { std::slice::from_raw_parts(ptrs, len) }
+        };
+        ptrs.iter()
+            .map(|t| unsafe { Tensor::from_raw(self.ctx, *t) }.expect("NULL tensor in graph"))
+    }
+
+    /// Adds `tensor` and everything it depends on to the graph (`ggml_build_forward_expand`).
+    pub fn build_forward(&mut self, tensor: &Tensor<'ctx>) -> Result<()> {
+        let (nodes, leafs) = self.count_new(*tensor);
+        if self.n_nodes() + nodes > self.size() || self.n_leafs() + leafs > self.size() {
+            return Err(Error::GraphFull { size: self.size() });
+        }
+        unsafe { ggml_build_forward_expand(self.as_ptr(), tensor.as_ptr()) };
+        Ok(())
+    }
+
+    /// Whether the graph was created with room for gradients.
+    pub fn has_grads(&self) -> bool {
+        !self.raw().grads.is_null()
+    }
+
+    /// Creates the backward graph: a copy of this graph (`ggml_graph_dup`) extended to also
+    /// compute the gradients of its nodes (`ggml_build_backward_expand`). With `keep` the
+    /// gradient tensors of the nodes are duplicated first, as `ggml_opt` does.
+    ///
+    /// This graph must have been created with gradients. GGML allocates the gradient
+    /// operations in the context and aborts if it or the new graph (which has the same size as
+    /// this one) runs out of room, so both are checked against a conservative estimate first
+    /// (a few tensors per operand). That can reject a graph that would just fit.
+    pub fn backward(&self, keep: bool) -> Result<Graph<'ctx>> {
+        if !self.has_grads() || self.n_nodes() == 0 {
+            return Err(Error::NoGradients);
+        }
+        let (nodes, size) = self.backward_size(keep);
+        if self.n_nodes() + nodes > self.size() {
+            return Err(Error::GraphFull { size: self.size() });
+        }
+        let needed = unsafe { ggml_graph_overhead_custom(self.size(), true) }.saturating_add(size);
+        let available = self.ctx.mem_size() - self.ctx.used_mem();
+        if needed > available {
+            return Err(Error::OutOfMemory { needed, available });
+        }
+        let ptr = unsafe { ggml_graph_dup(self.ctx.as_ptr(), self.as_ptr()) };
+        unsafe { ggml_build_backward_expand(self.ctx.as_ptr(), self.as_ptr(), ptr, keep) };
+        Ok(Self {
+            ptr: NonNull::new(ptr).expect("ggml_graph_dup returned NULL"),
+            ctx: self.ctx,
+            work: Vec::new(),
+            abort: None,
+        })
+    }
+
+    /// Estimates the nodes `ggml_build_backward_expand` adds and the bytes it allocates: the
+    /// duplicated gradients with `keep`, and `BACKWARD_TENSORS` per operand with a gradient
+    /// of each node with one.
+    fn backward_size(&self, keep: bool) -> (usize, usize) {
+        let overhead = unsafe { ggml_tensor_overhead() };
+        let padded = |t: Tensor| t.nbytes().next_multiple_of(GGML_MEM_ALIGN as usize);
+        let (mut nodes, mut size) = (0usize, 0usize);
+        for node in self.nodes().filter(|node| node.grad().is_some()) {
+            if keep {
+                size = size.saturating_add(overhead + padded(node));
+            }
+            let largest = node.sources().chain([node]).map(padded).max().unwrap_or(0);
+            let operands = node.sources().filter(|src| src.grad().is_some()).count();
+            let (tensors, data) = BACKWARD_TENSORS;
+            nodes += operands * tensors;
+            size = size.saturating_add(
+                (operands * tensors * overhead).saturating_add(operands * data * largest),
+            );
+        }
+        (nodes, size)
+    }
+
+    /// Counts the nodes and leafs that adding `tensor` would add, the same way
+    /// `ggml_visit_parents` decides between the two.
+    fn count_new(&self, tensor: Tensor<'ctx>) -> (usize, usize) {
+        let mut seen = self
+            .nodes()
+            .chain(self.leafs())
+            .map(|t| t.as_ptr())
+            .collect::<HashSet<_>>();
+        let mut stack = vec![tensor];
+        let (mut nodes, mut leafs) = (0, 0);
+        while let Some(t) = stack.pop() {
+            if !seen.insert(t.as_ptr()) {
+                continue;
+            }
+            if t.op() == ggml_op_GGML_OP_NONE && unsafe { (*t.as_ptr()).grad.is_null() } {
+                leafs += 1;
+            } else {
+                nodes += 1;
+            }
+            stack.extend(t.sources());
+        }
+        (nodes, leafs)
+    }
+
+    /// Sets a callback that's polled while computing the graph: returning `true` stops the
+    /// computation and [`Graph::compute`] returns [`Error::Aborted`].
+    pub fn set_abort_callback(&mut self, callback: impl Fn() -> bool + Send + Sync + 'ctx) {
+        self.abort = Some(Callback::new(Box::new(callback)));
+    }
+
+    pub fn clear_abort_callback(&mut self) {
+        self.abort = None;
+    }
+
+    /// Checks that every node and leaf has data for the CPU to compute with, which GGML
+    /// doesn't.
+    pub(crate) fn ensure_data(&self) -> Result<()> {
+        if self
+            .nodes()
+            .chain(self.leafs())
+            .any(|t| t.data_ptr().is_null())
+        {
+            return Err(Error::NoData);
+        }
+        Ok(())
+    }
+
+    /// Size in bytes of the cached work buffer.
+    pub fn work_size(&self) -> usize {
+        self.work.len() * std::mem::size_of::<u64>()
+    }
+
+    /// Computes the graph on the CPU with `n_threads` threads (`ggml_graph_plan` followed by
+    /// `ggml_graph_compute`). Fails with [`Error::DataBorrowed`] if a node's data is borrowed
+    /// and with [`Error::NoData`] if a tensor has no data (i.e. in a
+    /// [`Context::new_no_alloc`] context).
+    pub fn compute(&mut self, n_threads: usize) -> Result<()> {
+        self.ensure_data()?;
+        ensure_writable(self.nodes().map(|node| node.data_range()))?;
+        ensure_readable(self.leafs().map(|leaf| leaf.data_range()))?;
+        let mut plan = unsafe { ggml_graph_plan(self.as_ptr(), n_threads.max(1) as _) };
+        let words = plan.work_size.div_ceil(std::mem::size_of::<u64>());
+        if words > self.work.len() {
+            self.work.resize(words, 0);
+        }
+        if plan.work_size > 0 {
+            plan.work_data = self.work.as_mut_ptr().cast();
+        }
+        if let Some(abort) = &mut self.abort {
+            plan.abort_callback = Some(abort_trampoline);
+            plan.abort_callback_data = abort.userdata();
+        }
+        let status = unsafe { ggml_graph_compute(self.as_ptr(), &mut plan) };
+        if let Some(abort) = &self.abort {
+            abort.resume_panic();
+        }
+        if let Some(payload) = take_panic(self.nodes()) {
+            std::panic::resume_unwind(payload);
+        }
+        match status {
+            status if status == ggml_status_GGML_STATUS_SUCCESS => Ok(()),
+            status if status == ggml_status_GGML_STATUS_ABORTED => Err(Error::Aborted),
+            status => Err(Error::Compute(status)),
+        }
+    }
+}
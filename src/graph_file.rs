@@ -0,0 +1,401 @@
+//! Saving graphs to files and loading them back (`ggml_graph_export`/`ggml_graph_import`), i.e.
+//! to build a graph once and ship it to workers that only compute it.
+//!
+//! The file holds the leafs with their data and the nodes with their operations and operands,
+//! so an imported graph computes the same thing without the code that built it. GGML doesn't
+//! check what it reads, so files are validated here first: a truncated or corrupted file
+//! returns an error instead of reading out of bounds. The operations themselves aren't checked,
+//! so [`ImportedGraph::import`] is unsafe: only import files from a trusted source, such as
+//! ones written by [`Graph::export`].
+
+use std::{collections::HashSet, ffi::CString, path::Path, ptr::NonNull};
+
+use crate::{
+    context::Context,
+    error::{Error, Result},
+    graph::Graph,
+    shape::Checker,
+    tensor::{ensure_readable, Tensor},
+    *,
+};
+
+const MAX_SRC: usize = GGML_MAX_SRC as usize;
+
+fn is_view(op: ggml_op) -> bool {
+    op == ggml_op_GGML_OP_VIEW
+        || op == ggml_op_GGML_OP_RESHAPE
+        || op == ggml_op_GGML_OP_PERMUTE
+        || op == ggml_op_GGML_OP_TRANSPOSE
+}
+
+/// Operations that call function pointers stored in their parameters, which are meaningless
+/// once the graph is loaded elsewhere.
+fn is_custom(op: ggml_op) -> bool {
+    (ggml_op_GGML_OP_MAP_UNARY..=ggml_op_GGML_OP_MAP_CUSTOM3).contains(&op)
+}
+
+fn nelements(ne: &[i64; 4]) -> i128 {
+    ne.iter().map(|ne| *ne as i128).product()
+}
+
+fn c_path(path: &Path, err: impl Fn(String) -> Error) -> Result<CString> {
+    path.to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| err("path isn't valid UTF-8 or contains a NUL byte".to_owned()))
+}
+
+impl Graph<'_> {
+    /// Writes the graph to `path` (`ggml_graph_export`), which also prints a table of its
+    /// tensors to stdout. The file is read back and validated like [`ImportedGraph::import`]
+    /// does, so a graph that was exported successfully can be imported.
+    ///
+    /// Every leaf needs data since it's stored in the file. Graphs can't have parameters
+    /// (nodes without an operation), custom operations, or nodes written in place that other
+    /// nodes read afterwards: GGML's import gives every node its own memory, so the update
+    /// wouldn't be seen.
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let err = |msg: String| Error::GraphFile {
+            path: path.to_path_buf(),
+            msg,
+        };
+        self.check_exportable()?;
+        let fname = c_path(path, err)?;
+        // GGML only reports failing to open the file on stderr, so create it first to get the
+        // actual error.
+        std::fs::File::create(path).map_err(|e| err(e.to_string()))?;
+        unsafe { ggml_graph_export(self.as_ptr(), fname.as_ptr()) };
+        let data = std::fs::read(path).map_err(|e| err(e.to_string()))?;
+        if let Err(msg) = validate(&data) {
+            let _ = std::fs::remove_file(path);
+            return Err(err(msg));
+        }
+        Ok(())
+    }
+
+    fn check_exportable(&self) -> Result<()> {
+        if self.leafs().any(|leaf| leaf.data_ptr().is_null()) {
+            return Err(Error::NoData);
+        }
+        ensure_readable(self.leafs().map(|leaf| leaf.data_range()))?;
+        let c = Checker("ggml_graph_export");
+        let nodes = self.nodes().collect::<Vec<_>>();
+        let root = |t: Tensor| t.view_src().unwrap_or(t).as_ptr();
+        // Nodes computed in place, which share memory with their `view_src`.
+        let in_place = nodes
+            .iter()
+            .filter(|n| !is_view(n.op()) && n.view_src().is_some())
+            .map(|n| n.as_ptr())
+            .collect::<HashSet<_>>();
+        for (i, node) in nodes.iter().enumerate() {
+            c.arg(node.op() != ggml_op_GGML_OP_NONE, || {
+                format!("node {} is a parameter", node.name())
+            })?;
+            c.arg(!is_custom(node.op()), || {
+                format!("node {} is a custom operation", node.name())
+            })?;
+            if !in_place.contains(&node.as_ptr()) {
+                continue;
+            }
+            // Reading the node itself or a copy made in place from it is fine, reading the
+            // memory it was written to isn't.
+            let target = root(*node);
+            let stale = nodes[i + 1..].iter().find(|later| {
+                later.sources().any(|src| {
+                    src != *node && !in_place.contains(&src.as_ptr()) && root(src) == target
+                })
+            });
+            if let Some(later) = stale {
+                c.arg(false, || {
+                    format!(
+                        "node {} reads memory node {} writes in place",
+                        later.name(),
+                        node.name()
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A graph loaded from a file written by [`Graph::export`], along with the two contexts
+/// `ggml_graph_import` allocates: one for the file's contents, which the leafs point into, and
+/// one for the graph and its nodes.
+///
+/// Inputs can be changed by writing to the leafs before computing the graph again.
+pub struct ImportedGraph {
+    graph: NonNull<ggml_cgraph>,
+    eval: Context,
+    // Only kept alive for the leafs.
+    _data: Context,
+}
+
+// Like a `Context`, it's not tied to a thread.
+unsafe impl Send for ImportedGraph {}
+
+impl ImportedGraph {
+    /// Validates the file at `path` and loads it with `ggml_graph_import`.
+    ///
+    /// # Safety
+    /// Only the file's structure is validated. Its operations must be ones GGML can compute
+    /// with the operands the file gives them, otherwise computing the graph can read or write
+    /// out of bounds. Files written by [`Graph::export`] are fine.
+    pub unsafe fn import(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let err = |msg: String| Error::GraphFile {
+            path: path.to_path_buf(),
+            msg,
+        };
+        let fname = c_path(path, err)?;
+        let data = std::fs::read(path).map_err(|e| err(e.to_string()))?;
+        validate(&data).map_err(err)?;
+        drop(data);
+
+        let (mut ctx_data, mut ctx_eval) = (std::ptr::null_mut(), std::ptr::null_mut());
+        let graph = unsafe { ggml_graph_import(fname.as_ptr(), &mut ctx_data, &mut ctx_eval) };
+        // GGML allocates the contexts itself, so whatever it returns is freed on drop.
+        let [data, eval] = [ctx_data, ctx_eval]
+            .map(|ctx| (!ctx.is_null()).then(|| unsafe { Context::from_raw(ctx) }));
+        match (NonNull::new(graph), data, eval) {
+            (Some(graph), Some(data), Some(eval)) => Ok(Self {
+                graph,
+                eval,
+                _data: data,
+            }),
+            _ => Err(err("ggml_graph_import failed".to_owned())),
+        }
+    }
+
+    /// The context holding the graph's tensors.
+    pub fn context(&self) -> &Context {
+        &self.eval
+    }
+
+    /// A handle to compute or inspect the graph. Each handle has its own work buffer.
+    pub fn graph(&self) -> Graph<'_> {
+        unsafe { Graph::from_raw(&self.eval, self.graph.as_ptr()) }
+    }
+
+    /// The leaf or node named `name`, if any (`ggml_graph_get_tensor`).
+    pub fn get_tensor(&self, name: &str) -> Option<Tensor<'_>> {
+        self.graph().get_tensor(name)
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize, what: &str) -> Result<&'a [u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.data.len())
+            .ok_or_else(|| format!("file ends in the middle of {what}"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u32(&mut self, what: &str) -> Result<u32, String> {
+        let bytes = self.bytes(4, what)?;
+        Ok(u32::from_ne_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn u64(&mut self, what: &str) -> Result<u64, String> {
+        let bytes = self.bytes(8, what)?;
+        Ok(u64::from_ne_bytes(bytes.try_into().unwrap()))
+    }
+}
+
+/// The fields of a leaf or node that are written the same way for both.
+struct Record<'a> {
+    type_: ggml_type,
+    op: ggml_op,
+    ne: [i64; 4],
+    nb: [usize; 4],
+    name: &'a [u8],
+    op_params: &'a [u8],
+}
+
+impl Record<'_> {
+    fn name(&self) -> String {
+        let len = self.name.iter().position(|c| *c == 0).unwrap_or(0);
+        String::from_utf8_lossy(&self.name[..len]).into_owned()
+    }
+
+    /// `ggml_nbytes`.
+    fn nbytes(&self) -> Option<usize> {
+        let blck = unsafe { ggml_blck_size(self.type_) } as usize;
+        let first = if blck == 1 {
+            unsafe { ggml_type_size(self.type_) }
+        } else {
+            (self.ne[0] as usize).checked_mul(self.nb[0])? / blck
+        };
+        (usize::from(blck == 1)..4).try_fold(first, |n, i| {
+            n.checked_add((self.ne[i] as usize - 1).checked_mul(self.nb[i])?)
+        })
+    }
+
+    /// The size of the data of a contiguous tensor of this shape, which is what
+    /// `ggml_new_tensor` allocates.
+    fn contiguous_size(&self) -> Option<usize> {
+        let row = unsafe { ggml_type_size(self.type_) }
+            .checked_mul(self.ne[0] as usize / unsafe { ggml_blck_size(self.type_) } as usize)?;
+        self.ne[1..]
+            .iter()
+            .try_fold(row, |n, ne| n.checked_mul(*ne as usize))
+    }
+
+    /// `ggml_is_contiguous`.
+    fn is_contiguous(&self) -> bool {
+        let blck = unsafe { ggml_blck_size(self.type_) } as usize;
+        let stride = |i: usize| self.nb[i].checked_mul(self.ne[i] as usize);
+        self.nb[0] == unsafe { ggml_type_size(self.type_) }
+            && Some(self.nb[1]) == stride(0).map(|n| n / blck)
+            && Some(self.nb[2]) == stride(1)
+            && Some(self.nb[3]) == stride(2)
+    }
+}
+
+fn read_record<'a>(r: &mut Reader<'a>, what: &str) -> Result<Record<'a>, String> {
+    let type_ = r.u32(what)?;
+    let op = r.u32(what)?;
+    let (mut ne, mut nb) = ([0; 4], [0; 4]);
+    for i in 0..4 {
+        ne[i] = r.u64(what)? as i64;
+        nb[i] = r.u64(what)? as usize;
+    }
+    let name = r.bytes(GGML_MAX_NAME as usize, what)?;
+    let op_params = r.bytes(GGML_MAX_OP_PARAMS as usize, what)?;
+    let record = Record {
+        type_,
+        op,
+        ne,
+        nb,
+        name,
+        op_params,
+    };
+
+    if !name.contains(&0) {
+        return Err(format!("the name of {what} isn't terminated"));
+    }
+    let what = format!("{what} ({})", record.name());
+    if type_ >= ggml_type_GGML_TYPE_COUNT || unsafe { ggml_blck_size(type_) } <= 0 {
+        return Err(format!("{what} has invalid type {type_}"));
+    }
+    let blck = unsafe { ggml_blck_size(type_) } as i64;
+    if ne.iter().any(|ne| *ne < 1) || ne[0] % blck != 0 {
+        return Err(format!("{what} has invalid shape {ne:?}"));
+    }
+    if op >= ggml_op_GGML_OP_COUNT {
+        return Err(format!("{what} has invalid operation {op}"));
+    }
+    // Sizes are limited like allocations are, so padding and adding them up can't overflow.
+    let too_large = |n: Option<usize>| n.map_or(true, |n| n > isize::MAX as usize);
+    if too_large(record.nbytes()) || too_large(record.contiguous_size()) {
+        return Err(format!("{what} is too large"));
+    }
+    Ok(record)
+}
+
+/// Checks that a file written by `ggml_graph_export` can be read by `ggml_graph_import` without
+/// aborting or reading or writing out of bounds.
+fn validate(data: &[u8]) -> Result<(), String> {
+    let mut r = Reader { data, pos: 0 };
+    if r.u32("the header")? != GGML_FILE_MAGIC {
+        return Err("not a GGML graph file".to_owned());
+    }
+    let version = r.u32("the header")?;
+    if version != GGML_FILE_VERSION {
+        return Err(format!("unsupported version {version}"));
+    }
+    let n_leafs = r.u32("the header")? as usize;
+    let n_nodes = r.u32("the header")? as usize;
+    let size_eval = r.u64("the header")?;
+
+    // For each tensor, the bytes that can be accessed from its data pointer.
+    let mut records = Vec::new();
+    let mut available = Vec::new();
+    for i in 0..n_leafs {
+        let what = format!("leaf {i}");
+        let leaf = read_record(&mut r, &what)?;
+        if leaf.op != ggml_op_GGML_OP_NONE {
+            return Err(format!("{what} has an operation"));
+        }
+        let nbytes = leaf.nbytes().unwrap();
+        r.bytes(nbytes, &what)?;
+        records.push(leaf);
+        available.push(nbytes);
+    }
+
+    // What the nodes that aren't views allocate and what the file says they need.
+    let (mut needed, mut total) = (0u64, 0u64);
+    let pad = |n: usize| n.next_multiple_of(GGML_MEM_ALIGN as usize) as u64;
+    for i in 0..n_nodes {
+        let what = format!("node {i}");
+        let node = read_record(&mut r, &what)?;
+        let mut src = [None; MAX_SRC];
+        for s in &mut src {
+            let idx = r.u32(&what)? as i32;
+            // Operands come before the nodes using them.
+            if idx != -1 && !(0..(n_leafs + i) as i32).contains(&idx) {
+                return Err(format!("{what} has invalid operand {idx}"));
+            }
+            *s = usize::try_from(idx).ok();
+        }
+
+        let what = format!("{what} ({})", node.name());
+        if node.op == ggml_op_GGML_OP_NONE || is_custom(node.op) {
+            return Err(format!("{what} has unsupported operation {}", node.op));
+        }
+        let nbytes = node.nbytes().unwrap();
+        let size = node.contiguous_size().unwrap();
+        total = total.saturating_add(pad(nbytes));
+        if is_view(node.op) {
+            // Views are created from their first operand with the shape in the file.
+            let Some(src) = src[0] else {
+                return Err(format!("{what} is a view without an operand"));
+            };
+            let offset = if node.op == ggml_op_GGML_OP_VIEW {
+                u64::from_ne_bytes(node.op_params[..8].try_into().unwrap()) as usize
+            } else {
+                0
+            };
+            let (src_record, src_available) = (&records[src], available[src]);
+            let fits = |n: usize| n.checked_add(offset).is_some_and(|n| n <= src_available);
+            let consistent = match node.op {
+                op if op == ggml_op_GGML_OP_RESHAPE => {
+                    src_record.is_contiguous() && nelements(&node.ne) == nelements(&src_record.ne)
+                }
+                op if op == ggml_op_GGML_OP_TRANSPOSE => {
+                    let ne = src_record.ne;
+                    node.ne == [ne[1], ne[0], ne[2], ne[3]]
+                }
+                _ => true,
+            };
+            if node.type_ != src_record.type_ || !consistent || !fits(size) || !fits(nbytes) {
+                return Err(format!("{what} doesn't fit its operand"));
+            }
+            available.push(src_available - offset);
+        } else {
+            if nbytes > size {
+                return Err(format!("{what} has strides larger than its shape"));
+            }
+            needed = needed.saturating_add(pad(size));
+            available.push(size);
+        }
+        records.push(node);
+    }
+
+    if !(needed..=total).contains(&size_eval) {
+        return Err(format!("invalid evaluation size {size_eval}"));
+    }
+    if r.pos != data.len() {
+        return Err("trailing data after the last node".to_owned());
+    }
+    Ok(())
+}
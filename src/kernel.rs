@@ -0,0 +1,256 @@
+//! GGML's CPU dot product kernels (`vec_dot` from the type traits) without building a graph.
+//!
+//! A [`QuantKernel`] computes dot products between rows of a [`QuantizedBuffer`] and queries,
+//! which first have to be converted to the kernel's `vec_dot_type` (i.e. Q8_K for the K
+//! quants) with [`QuantKernel::quantize_queries`]. Kernels that handle two rows at a time
+//! (`nrows == 2`, i.e. Q4_0 and Q8_0 with ARM's `i8mm`) are used that way by
+//! [`QuantKernel::dots`].
+
+use std::os::raw::{c_int, c_void};
+
+use crate::{
+    context,
+    error::{Error, Result},
+    quant::{can_quantize, par_rows, type_traits, QuantizedBuffer},
+    *,
+};
+
+type VecDotFn =
+    unsafe extern "C" fn(c_int, *mut f32, usize, *const c_void, usize, *const c_void, usize, c_int);
+type FromFloatFn = unsafe extern "C" fn(*const f32, *mut c_void, i64);
+
+/// Queries converted to a kernel's `vec_dot_type`, one row per query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedQueries {
+    type_: ggml_type,
+    n: usize,
+    count: usize,
+    // Stored as words so the blocks are suitably aligned for GGML.
+    data: Vec<u64>,
+    nbytes: usize,
+}
+
+impl QuantizedQueries {
+    pub fn type_(&self) -> ggml_type {
+        self.type_
+    }
+
+    /// Number of queries.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr().cast(), self.nbytes) }
+    }
+
+    /// The converted data of query `i`. Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> &[u8] {
+        let row_size = self.nbytes / self.count;
+        &self.data()[i * row_size..(i + 1) * row_size]
+    }
+}
+
+/// Dot products of `n` element rows of type `type_` with queries, see the [module
+/// docs](self).
+#[derive(Debug, Clone, Copy)]
+pub struct QuantKernel {
+    type_: ggml_type,
+    vec_dot_type: ggml_type,
+    n: usize,
+    nrows: usize,
+    vec_dot: VecDotFn,
+    from_float: Option<FromFloatFn>,
+}
+
+impl QuantKernel {
+    /// The kernel for rows of `n` elements of type `type_`. `n` has to be a non-zero multiple
+    /// of the block size of both `type_` and its `vec_dot_type`.
+    pub fn new(type_: ggml_type, n: usize) -> Result<Self> {
+        let unsupported = || Error::UnsupportedType(type_);
+        let traits = type_traits(type_)
+            .filter(|_| can_quantize(type_))
+            .ok_or_else(unsupported)?;
+        let vec_dot = traits.vec_dot.ok_or_else(unsupported)?;
+        let dot_traits = type_traits(traits.vec_dot_type).ok_or_else(unsupported)?;
+        // F32 has no `from_float`, queries are copied as is.
+        if dot_traits.from_float.is_none() && traits.vec_dot_type != ggml_type_GGML_TYPE_F32 {
+            return Err(unsupported());
+        }
+        let whole_blocks = |blck_size: c_int| n % blck_size as usize == 0;
+        if n == 0
+            || n > c_int::MAX as usize
+            || !whole_blocks(traits.blck_size)
+            || !whole_blocks(dot_traits.blck_size)
+        {
+            return Err(Error::InvalidShape {
+                type_,
+                shape: vec![n as i64],
+            });
+        }
+        context::init_tables();
+        Ok(Self {
+            type_,
+            vec_dot_type: traits.vec_dot_type,
+            n,
+            nrows: traits.nrows.max(1) as usize,
+            vec_dot,
+            from_float: dot_traits.from_float,
+        })
+    }
+
+    pub fn type_(&self) -> ggml_type {
+        self.type_
+    }
+
+    /// The type queries are converted to.
+    pub fn vec_dot_type(&self) -> ggml_type {
+        self.vec_dot_type
+    }
+
+    /// Number of elements in a row.
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Number of rows the kernel handles at a time, 1 or 2.
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Converts one or more queries (concatenated, so `queries.len()` has to be a multiple of
+    /// [`QuantKernel::n`]) to the kernel's `vec_dot_type`.
+    pub fn quantize_queries(&self, queries: &[f32]) -> Result<QuantizedQueries> {
+        if queries.len() % self.n != 0 {
+            return Err(Error::LengthMismatch {
+                expected: queries.len().next_multiple_of(self.n),
+                actual: queries.len(),
+            });
+        }
+        let count = queries.len() / self.n;
+        let nbytes = unsafe { ggml_row_size(self.vec_dot_type, self.n as i64) } * count;
+        let mut data = vec![0u64; nbytes.div_ceil(std::mem::size_of::<u64>())];
+        match self.from_float {
+            Some(from_float) => unsafe {
+                from_float(
+                    queries.as_ptr(),
+                    data.as_mut_ptr().cast(),
+                    queries.len() as i64,
+                )
+            },
+            None => unsafe {
+                std::ptr::copy_nonoverlapping(
+                    queries.as_ptr(),
+                    data.as_mut_ptr().cast(),
+                    queries.len(),
+                )
+            },
+        }
+        Ok(QuantizedQueries {
+            type_: self.vec_dot_type,
+            n: self.n,
+            count,
+            data,
+            nbytes,
+        })
+    }
+
+    fn check(&self, rows: &QuantizedBuffer, queries: &QuantizedQueries) -> Result<()> {
+        for (expected, actual) in [
+            (self.type_, rows.type_()),
+            (self.vec_dot_type, queries.type_),
+        ] {
+            if expected != actual {
+                return Err(Error::TypeMismatch { expected, actual });
+            }
+        }
+        for actual in [rows.n_per_row(), queries.n] {
+            if actual != self.n {
+                return Err(Error::LengthMismatch {
+                    expected: self.n,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The dot product of row `row` of `rows` with query `query`. Panics if either index is
+    /// out of bounds.
+    pub fn dot(
+        &self,
+        rows: &QuantizedBuffer,
+        row: usize,
+        queries: &QuantizedQueries,
+        query: usize,
+    ) -> Result<f32> {
+        self.check(rows, queries)?;
+        let (x, y) = (rows.row(row), queries.row(query));
+        let mut s = 0.0;
+        unsafe {
+            (self.vec_dot)(
+                self.n as c_int,
+                &mut s,
+                0,
+                x.as_ptr().cast(),
+                0,
+                y.as_ptr().cast(),
+                0,
+                1,
+            )
+        };
+        Ok(s)
+    }
+
+    /// The dot products of every row of `rows` with every query, as a
+    /// `queries.len() x rows.nrows()` matrix: the result for query `q` and row `r` is at
+    /// `q * rows.nrows() + r`. Queries are split between all available threads.
+    pub fn dots(&self, rows: &QuantizedBuffer, queries: &QuantizedQueries) -> Result<Vec<f32>> {
+        self.check(rows, queries)?;
+        let nx = rows.nrows();
+        let (row_size, query_size) = (rows.row_size(), queries.nbytes / queries.count.max(1));
+        let x = |r: usize| unsafe { rows.data().as_ptr().add(r * row_size).cast() };
+        let y = |q: usize| unsafe { queries.data().as_ptr().add(q * query_size).cast() };
+        let dot = |s: *mut f32, r: usize, q: usize, nrc: usize| unsafe {
+            // With `nrc == 2`, `s[i + nx * j]` is set to row `r + i` times query `q + j`.
+            (self.vec_dot)(
+                self.n as c_int,
+                s,
+                nx,
+                x(r),
+                row_size,
+                y(q),
+                query_size,
+                nrc as c_int,
+            )
+        };
+
+        let mut out = vec![0.0; queries.count * nx];
+        par_rows(&mut out, nx, |qs, out| {
+            let mut q = qs.start;
+            while q < qs.end {
+                let pair = self.nrows == 2 && q + 1 < qs.end;
+                let s = &mut out[(q - qs.start) * nx..];
+                let mut r = 0;
+                while r < nx {
+                    if pair && r + 1 < nx {
+                        dot(s[r..].as_mut_ptr(), r, q, 2);
+                        r += 2;
+                    } else {
+                        dot(s[r..].as_mut_ptr(), r, q, 1);
+                        if pair {
+                            dot(s[nx + r..].as_mut_ptr(), r, q + 1, 1);
+                        }
+                        r += 1;
+                    }
+                }
+                q += if pair { 2 } else { 1 };
+            }
+        });
+        Ok(out)
+    }
+}
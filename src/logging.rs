@@ -0,0 +1,133 @@
+//! Forwards `llama.cpp` log output to the `log` (with the `log` feature) or `tracing` (with the
+//! `tracing` feature) crates instead of stderr. That includes the output of GGML's Metal and
+//! CUDA backends when they're built in, which `llama_log_set` also covers, but not the rest of
+//! GGML: `ggml.c` and the CPU backend always print to stderr.
+//!
+//! Messages are logged with the `ggml` target when they come from a GGML backend (which
+//! prefixes them with the name of the `ggml_*` function) and `llama` otherwise. `llama.cpp` sometimes logs a
+//! line in several pieces (i.e. the progress dots while loading a model), those are joined
+//! and logged as one record with the level of the first piece.
+
+use std::sync::Mutex;
+
+use crate::{callback::set_llama_log_callback, *};
+
+/// Collects log fragments until a whole line is available.
+struct LineBuffer {
+    line: String,
+    level: ggml_log_level,
+}
+
+impl LineBuffer {
+    const fn new() -> Self {
+        Self {
+            line: String::new(),
+            level: ggml_log_level_GGML_LOG_LEVEL_INFO,
+        }
+    }
+
+    /// Adds `text` and calls `emit` with the level, target and text of each complete line.
+    fn push(&mut self, level: ggml_log_level, text: &str, emit: impl Fn(Level, &str, &str)) {
+        for piece in text.split_inclusive('\n') {
+            if self.line.is_empty() {
+                self.level = level;
+            }
+            self.line.push_str(piece);
+            if !self.line.ends_with('\n') {
+                continue;
+            }
+            let line = self.line.trim_end();
+            if !line.is_empty() {
+                emit(self.level.into(), target(line), line);
+            }
+            self.line.clear();
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+}
+
+impl From<ggml_log_level> for Level {
+    fn from(level: ggml_log_level) -> Self {
+        if level == ggml_log_level_GGML_LOG_LEVEL_ERROR {
+            Self::Error
+        } else if level == ggml_log_level_GGML_LOG_LEVEL_WARN {
+            Self::Warn
+        } else if level == ggml_log_level_GGML_LOG_LEVEL_DEBUG {
+            Self::Debug
+        } else {
+            Self::Info
+        }
+    }
+}
+
+fn target(line: &str) -> &'static str {
+    if line.starts_with("ggml") {
+        "ggml"
+    } else {
+        "llama"
+    }
+}
+
+#[cfg(feature = "log")]
+static LOG_BUFFER: Mutex<LineBuffer> = Mutex::new(LineBuffer::new());
+
+/// Logs a message from `llama.cpp` or a GGML backend with the `log` crate. This is the callback
+/// installed by [`init_log`], but can also be called from a custom log callback.
+#[cfg(feature = "log")]
+pub fn log_message(level: ggml_log_level, text: &str) {
+    let mut buffer = LOG_BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    buffer.push(level, text, |level, target, line| {
+        let level = match level {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+        };
+        log::log!(target: target, level, "{line}");
+    });
+}
+
+/// Sends `llama.cpp` log output to the `log` crate, see the [module docs](self).
+#[cfg(feature = "log")]
+pub fn init_log() {
+    set_llama_log_callback(log_message);
+}
+
+#[cfg(feature = "tracing")]
+static TRACING_BUFFER: Mutex<LineBuffer> = Mutex::new(LineBuffer::new());
+
+/// Logs a message from `llama.cpp` or a GGML backend as a `tracing` event. This is the callback
+/// installed by [`init_tracing`], but can also be called from a custom log callback.
+#[cfg(feature = "tracing")]
+pub fn trace_message(level: ggml_log_level, text: &str) {
+    // Event targets and levels have to be constants.
+    macro_rules! event {
+        ($target:literal, $level:expr, $line:expr) => {
+            match $level {
+                Level::Error => tracing::error!(target: $target, "{}", $line),
+                Level::Warn => tracing::warn!(target: $target, "{}", $line),
+                Level::Info => tracing::info!(target: $target, "{}", $line),
+                Level::Debug => tracing::debug!(target: $target, "{}", $line),
+            }
+        };
+    }
+
+    let mut buffer = TRACING_BUFFER.lock().unwrap_or_else(|e| e.into_inner());
+    buffer.push(level, text, |level, target, line| match target {
+        "ggml" => event!("ggml", level, line),
+        _ => event!("llama", level, line),
+    });
+}
+
+/// Sends `llama.cpp` log output to `tracing`, see the [module docs](self).
+#[cfg(feature = "tracing")]
+pub fn init_tracing() {
+    set_llama_log_callback(trace_message);
+}
@@ -0,0 +1,1434 @@
+//! Graph building operations on [`Tensor`] handles. Each method maps directly to the `ggml_*`
+//! function of the same name (`_inplace` variants included) and creates its result in the
+//! context of `self`. Nothing is computed until the resulting graph is.
+//!
+//! The operands are checked (see `shape.rs`) before calling into GGML, which would abort on
+//! anything it can't handle. The `try_` variants return the problem as an
+//! [`Error`](crate::Error), the others and the operator overloads panic with it.
+//!
+//! A few functions aren't wrapped and are only available as raw bindings: the deprecated
+//! `ggml_rope`, `ggml_rope_custom` and `ggml_map_*_f32` and the shorthands for other operations
+//! (`ggml_conv_1d_ph`, `ggml_conv_2d_sk_p0`, `ggml_set_1d`, ...), as well as
+//! `ggml_map_custom{1,2,3}_inplace` (see [`custom`](crate::custom)). Operations whose CPU
+//! implementations index with the values of an operand without bounds checking them
+//! (`ggml_get_rows_back` and `ggml_ssm_*`) are `unsafe`.
+
+use std::{
+    ops::{Add, Div, Mul, Neg, Sub},
+    os::raw::c_int,
+    ptr,
+};
+
+use crate::{
+    context::Context,
+    error::Result,
+    shape::{self, Check},
+    tensor::Tensor,
+    *,
+};
+
+/// Parameters for [`Tensor::rope_ext`]. [`RopeParams::new`] uses the same defaults as
+/// `llama.cpp` for a model without any context extension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RopeParams {
+    pub n_dims: i32,
+    pub mode: i32,
+    pub n_ctx_orig: i32,
+    pub freq_base: f32,
+    pub freq_scale: f32,
+    pub ext_factor: f32,
+    pub attn_factor: f32,
+    pub beta_fast: f32,
+    pub beta_slow: f32,
+}
+
+impl RopeParams {
+    pub fn new(n_dims: i32) -> Self {
+        Self {
+            n_dims,
+            mode: 0,
+            n_ctx_orig: 0,
+            freq_base: 10000.0,
+            freq_scale: 1.0,
+            ext_factor: 0.0,
+            attn_factor: 1.0,
+            beta_fast: 32.0,
+            beta_slow: 1.0,
+        }
+    }
+}
+
+type RopeFn = unsafe extern "C" fn(
+    *mut ggml_context,
+    *mut ggml_tensor,
+    *mut ggml_tensor,
+    *mut ggml_tensor,
+    c_int,
+    c_int,
+    c_int,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+    f32,
+) -> *mut ggml_tensor;
+
+type SetFn = unsafe extern "C" fn(
+    *mut ggml_context,
+    *mut ggml_tensor,
+    *mut ggml_tensor,
+    usize,
+    usize,
+    usize,
+    usize,
+) -> *mut ggml_tensor;
+
+#[track_caller]
+fn or_panic<T>(result: Result<T>) -> T {
+    match result {
+        Ok(val) => val,
+        Err(err) => panic!("{err}"),
+    }
+}
+
+macro_rules! try_unary_ops {
+    ($($try_name:ident => $fun:ident, $check:ident);* $(;)?) => {
+        $(
+            #[doc = concat!("`", stringify!($fun), "`")]
+            pub fn $try_name(self) -> Result<Self> {
+                self.checked(shape::$check(stringify!($fun), self), |ctx| unsafe {
+                    $fun(ctx, self.as_ptr())
+                })
+            }
+        )*
+    };
+}
+
+macro_rules! unary_ops {
+    ($($name:ident, $try_name:ident => $fun:ident, $check:ident);* $(;)?) => {
+        $(
+            #[doc = concat!("`", stringify!($fun), "`, see [`Tensor::", stringify!($try_name), "`].")]
+            #[track_caller]
+            pub fn $name(self) -> Self {
+                or_panic(self.$try_name())
+            }
+        )*
+        try_unary_ops! { $($try_name => $fun, $check);* }
+    };
+}
+
+macro_rules! try_binary_ops {
+    ($($try_name:ident => $fun:ident, $check:ident);* $(;)?) => {
+        $(
+            #[doc = concat!("`", stringify!($fun), "`")]
+            pub fn $try_name(self, other: Tensor<'ctx>) -> Result<Self> {
+                self.checked(shape::$check(stringify!($fun), self, other), |ctx| unsafe {
+                    $fun(ctx, self.as_ptr(), other.as_ptr())
+                })
+            }
+        )*
+    };
+}
+
+macro_rules! binary_ops {
+    ($($name:ident, $try_name:ident => $fun:ident, $check:ident);* $(;)?) => {
+        $(
+            #[doc = concat!("`", stringify!($fun), "`, see [`Tensor::", stringify!($try_name), "`].")]
+            #[track_caller]
+            pub fn $name(self, other: Tensor<'ctx>) -> Self {
+                or_panic(self.$try_name(other))
+            }
+        )*
+        try_binary_ops! { $($try_name => $fun, $check);* }
+    };
+}
+
+macro_rules! scalar_ops {
+    ($($name:ident, $try_name:ident => $fun:ident, $check:ident);* $(;)?) => {
+        $(
+            #[doc = concat!("`", stringify!($fun), "`, see [`Tensor::", stringify!($try_name), "`].")]
+            #[track_caller]
+            pub fn $name(self, val: f32) -> Self {
+                or_panic(self.$try_name(val))
+            }
+
+            #[doc = concat!("`", stringify!($fun), "`")]
+            pub fn $try_name(self, val: f32) -> Result<Self> {
+                self.checked(shape::$check(stringify!($fun), self), |ctx| unsafe {
+                    $fun(ctx, self.as_ptr(), val)
+                })
+            }
+        )*
+    };
+}
+
+impl<'ctx> Tensor<'ctx> {
+    /// Runs `check`, makes sure the context has room for the result and then builds it.
+    pub(crate) fn checked(
+        self,
+        check: Check,
+        build: impl FnOnce(*mut ggml_context) -> *mut ggml_tensor,
+    ) -> Result<Self> {
+        let out = check?;
+        let ctx = self.context();
+        let tensors = out
+            .intermediates
+            .iter()
+            .chain([&out])
+            .map(|t| (t.type_, &t.ne[..], t.view))
+            .collect::<Vec<_>>();
+        ctx.ensure_room_all(&tensors)?;
+        Ok(self.wrap(build(ctx.as_ptr())))
+    }
+
+    unary_ops! {
+        dup, try_dup => ggml_dup, dup;
+        dup_inplace, try_dup_inplace => ggml_dup_inplace, dup;
+        neg_inplace, try_neg_inplace => ggml_neg_inplace, unary;
+        sqr, try_sqr => ggml_sqr, elementwise_f32;
+        sqr_inplace, try_sqr_inplace => ggml_sqr_inplace, elementwise_f32;
+        sqrt, try_sqrt => ggml_sqrt, elementwise_f32;
+        sqrt_inplace, try_sqrt_inplace => ggml_sqrt_inplace, elementwise_f32;
+        log, try_log => ggml_log, elementwise_f32;
+        log_inplace, try_log_inplace => ggml_log_inplace, elementwise_f32;
+        sum, try_sum => ggml_sum, sum;
+        sum_rows, try_sum_rows => ggml_sum_rows, reduce_rows;
+        mean, try_mean => ggml_mean, reduce_rows;
+        argmax, try_argmax => ggml_argmax, argmax;
+        tanh, try_tanh => ggml_tanh, unary;
+        tanh_inplace, try_tanh_inplace => ggml_tanh_inplace, unary;
+        relu, try_relu => ggml_relu, unary;
+        relu_inplace, try_relu_inplace => ggml_relu_inplace, unary;
+        gelu, try_gelu => ggml_gelu, unary;
+        gelu_inplace, try_gelu_inplace => ggml_gelu_inplace, unary;
+        silu, try_silu => ggml_silu, unary;
+        silu_inplace, try_silu_inplace => ggml_silu_inplace, unary;
+        softmax, try_softmax => ggml_soft_max, softmax;
+        softmax_inplace, try_softmax_inplace => ggml_soft_max_inplace, softmax;
+        transpose, try_transpose => ggml_transpose, transpose;
+        cont, try_cont => ggml_cont, dup;
+        abs, try_abs => ggml_abs, unary;
+        abs_inplace, try_abs_inplace => ggml_abs_inplace, unary;
+        sgn, try_sgn => ggml_sgn, unary;
+        sgn_inplace, try_sgn_inplace => ggml_sgn_inplace, unary;
+        step, try_step => ggml_step, unary;
+        step_inplace, try_step_inplace => ggml_step_inplace, unary;
+        elu, try_elu => ggml_elu, unary;
+        elu_inplace, try_elu_inplace => ggml_elu_inplace, unary;
+        sigmoid, try_sigmoid => ggml_sigmoid, unary;
+        sigmoid_inplace, try_sigmoid_inplace => ggml_sigmoid_inplace, unary;
+        gelu_quick, try_gelu_quick => ggml_gelu_quick, unary;
+        gelu_quick_inplace, try_gelu_quick_inplace => ggml_gelu_quick_inplace, unary;
+        hardswish, try_hardswish => ggml_hardswish, unary;
+        hardsigmoid, try_hardsigmoid => ggml_hardsigmoid, unary;
+        diag, try_diag => ggml_diag, diag;
+    }
+
+    try_unary_ops! {
+        try_neg => ggml_neg, unary;
+    }
+
+    binary_ops! {
+        add_inplace, try_add_inplace => ggml_add_inplace, broadcast;
+        sub_inplace, try_sub_inplace => ggml_sub_inplace, broadcast;
+        mul_inplace, try_mul_inplace => ggml_mul_inplace, broadcast;
+        div_inplace, try_div_inplace => ggml_div_inplace, broadcast;
+        add1, try_add1 => ggml_add1, add1;
+        add1_inplace, try_add1_inplace => ggml_add1_inplace, add1;
+        repeat, try_repeat => ggml_repeat, repeat;
+        get_rows, try_get_rows => ggml_get_rows, get_rows;
+        silu_back, try_silu_back => ggml_silu_back, unary_back;
+        softmax_back, try_softmax_back => ggml_soft_max_back, softmax_back;
+        softmax_back_inplace, try_softmax_back_inplace => ggml_soft_max_back_inplace, softmax_back;
+        repeat_back, try_repeat_back => ggml_repeat_back, repeat_back;
+        cross_entropy_loss, try_cross_entropy_loss => ggml_cross_entropy_loss, cross_entropy_loss;
+        out_prod, try_out_prod => ggml_out_prod, out_prod;
+        cpy, try_cpy => ggml_cpy, cpy;
+    }
+
+    try_binary_ops! {
+        try_add => ggml_add, broadcast;
+        try_sub => ggml_sub, broadcast;
+        try_mul => ggml_mul, broadcast;
+        try_div => ggml_div, broadcast;
+    }
+
+    scalar_ops! {
+        scale, try_scale => ggml_scale, scale;
+        scale_inplace, try_scale_inplace => ggml_scale_inplace, scale;
+        norm, try_norm => ggml_norm, elementwise_f32;
+        norm_inplace, try_norm_inplace => ggml_norm_inplace, elementwise_f32;
+        rms_norm, try_rms_norm => ggml_rms_norm, elementwise_f32;
+        rms_norm_inplace, try_rms_norm_inplace => ggml_rms_norm_inplace, elementwise_f32;
+    }
+
+    /// `ggml_mul_mat`: note that GGML's convention is that this computes `other * self^T`, so
+    /// the result has shape `[self.ne[1], other.ne[1], ...]`.
+    #[track_caller]
+    pub fn matmul(self, other: Tensor<'ctx>) -> Self {
+        or_panic(self.try_matmul(other))
+    }
+
+    /// `ggml_mul_mat`
+    pub fn try_matmul(self, other: Tensor<'ctx>) -> Result<Self> {
+        self.checked(shape::mul_mat("ggml_mul_mat", self, other), |ctx| unsafe {
+            ggml_mul_mat(ctx, self.as_ptr(), other.as_ptr())
+        })
+    }
+
+    /// `ggml_concat`
+    #[track_caller]
+    pub fn concat(self, other: Tensor<'ctx>, dim: usize) -> Self {
+        or_panic(self.try_concat(other, dim))
+    }
+
+    /// `ggml_concat`
+    pub fn try_concat(self, other: Tensor<'ctx>, dim: usize) -> Result<Self> {
+        self.checked(
+            shape::concat("ggml_concat", self, other, dim),
+            |ctx| unsafe { ggml_concat(ctx, self.as_ptr(), other.as_ptr(), dim as c_int) },
+        )
+    }
+
+    /// `ggml_soft_max_ext`
+    #[track_caller]
+    pub fn softmax_ext(self, mask: Option<Tensor<'ctx>>, scale: f32, max_bias: f32) -> Self {
+        or_panic(self.try_softmax_ext(mask, scale, max_bias))
+    }
+
+    /// `ggml_soft_max_ext`
+    pub fn try_softmax_ext(
+        self,
+        mask: Option<Tensor<'ctx>>,
+        scale: f32,
+        max_bias: f32,
+    ) -> Result<Self> {
+        let check = shape::softmax_ext("ggml_soft_max_ext", self, mask, max_bias);
+        self.checked(check, |ctx| unsafe {
+            ggml_soft_max_ext(
+                ctx,
+                self.as_ptr(),
+                mask.map_or(ptr::null_mut(), |t| t.as_ptr()),
+                scale,
+                max_bias,
+            )
+        })
+    }
+
+    /// `ggml_rope_ext`: `pos` is an I32 tensor with a position for each of `self.ne[2]`.
+    #[track_caller]
+    pub fn rope_ext(
+        self,
+        pos: Tensor<'ctx>,
+        freq_factors: Option<Tensor<'ctx>>,
+        params: &RopeParams,
+    ) -> Self {
+        or_panic(self.rope_ext_impl(pos, freq_factors, params, false))
+    }
+
+    /// `ggml_rope_ext`
+    pub fn try_rope_ext(
+        self,
+        pos: Tensor<'ctx>,
+        freq_factors: Option<Tensor<'ctx>>,
+        params: &RopeParams,
+    ) -> Result<Self> {
+        self.rope_ext_impl(pos, freq_factors, params, false)
+    }
+
+    /// `ggml_rope_ext_inplace`
+    #[track_caller]
+    pub fn rope_ext_inplace(
+        self,
+        pos: Tensor<'ctx>,
+        freq_factors: Option<Tensor<'ctx>>,
+        params: &RopeParams,
+    ) -> Self {
+        or_panic(self.rope_ext_impl(pos, freq_factors, params, true))
+    }
+
+    /// `ggml_rope_ext_inplace`
+    pub fn try_rope_ext_inplace(
+        self,
+        pos: Tensor<'ctx>,
+        freq_factors: Option<Tensor<'ctx>>,
+        params: &RopeParams,
+    ) -> Result<Self> {
+        self.rope_ext_impl(pos, freq_factors, params, true)
+    }
+
+    fn rope_ext_impl(
+        self,
+        pos: Tensor<'ctx>,
+        freq_factors: Option<Tensor<'ctx>>,
+        params: &RopeParams,
+        inplace: bool,
+    ) -> Result<Self> {
+        let (name, fun) = if inplace {
+            ("ggml_rope_ext_inplace", ggml_rope_ext_inplace as RopeFn)
+        } else {
+            ("ggml_rope_ext", ggml_rope_ext as RopeFn)
+        };
+        let check = shape::rope_ext(name, self, pos, freq_factors, params.n_dims);
+        self.checked(check, |ctx| unsafe {
+            fun(
+                ctx,
+                self.as_ptr(),
+                pos.as_ptr(),
+                freq_factors.map_or(ptr::null_mut(), |t| t.as_ptr()),
+                params.n_dims,
+                params.mode,
+                params.n_ctx_orig,
+                params.freq_base,
+                params.freq_scale,
+                params.ext_factor,
+                params.attn_factor,
+                params.beta_fast,
+                params.beta_slow,
+            )
+        })
+    }
+
+    /// `ggml_reshape_1d` through `ggml_reshape_4d`, depending on the number of dimensions.
+    #[track_caller]
+    pub fn reshape(self, shape: &[i64]) -> Self {
+        or_panic(self.try_reshape(shape))
+    }
+
+    /// `ggml_reshape_1d` through `ggml_reshape_4d`
+    pub fn try_reshape(self, shape: &[i64]) -> Result<Self> {
+        self.checked(shape::reshape("ggml_reshape", self, shape), |ctx| unsafe {
+            let a = self.as_ptr();
+            match *shape {
+                [ne0] => ggml_reshape_1d(ctx, a, ne0),
+                [ne0, ne1] => ggml_reshape_2d(ctx, a, ne0, ne1),
+                [ne0, ne1, ne2] => ggml_reshape_3d(ctx, a, ne0, ne1, ne2),
+                [ne0, ne1, ne2, ne3] => ggml_reshape_4d(ctx, a, ne0, ne1, ne2, ne3),
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    /// `ggml_reshape`: reshapes to the shape of `other`.
+    #[track_caller]
+    pub fn reshape_as(self, other: Tensor<'ctx>) -> Self {
+        or_panic(self.try_reshape_as(other))
+    }
+
+    /// `ggml_reshape`
+    pub fn try_reshape_as(self, other: Tensor<'ctx>) -> Result<Self> {
+        self.checked(
+            shape::reshape("ggml_reshape", self, &other.ne()),
+            |ctx| unsafe { ggml_reshape(ctx, self.as_ptr(), other.as_ptr()) },
+        )
+    }
+
+    /// `ggml_permute`: dimension `i` of `self` becomes dimension `axes[i]` of the result.
+    #[track_caller]
+    pub fn permute(self, axes: [usize; 4]) -> Self {
+        or_panic(self.try_permute(axes))
+    }
+
+    /// `ggml_permute`
+    pub fn try_permute(self, axes: [usize; 4]) -> Result<Self> {
+        let [axis0, axis1, axis2, axis3] = axes.map(|axis| axis as c_int);
+        self.checked(shape::permute("ggml_permute", self, axes), |ctx| unsafe {
+            ggml_permute(ctx, self.as_ptr(), axis0, axis1, axis2, axis3)
+        })
+    }
+
+    /// `ggml_view_1d` through `ggml_view_4d`. `nb` holds the strides for dimensions after the
+    /// first, so it must be one shorter than `shape`.
+    #[track_caller]
+    pub fn view(self, shape: &[i64], nb: &[usize], offset: usize) -> Self {
+        or_panic(self.try_view(shape, nb, offset))
+    }
+
+    /// `ggml_view_1d` through `ggml_view_4d`
+    pub fn try_view(self, shape: &[i64], nb: &[usize], offset: usize) -> Result<Self> {
+        let check = shape::view("ggml_view", self, shape, nb, offset);
+        self.checked(check, |ctx| unsafe {
+            let a = self.as_ptr();
+            match (shape, nb) {
+                (&[ne0], &[]) => ggml_view_1d(ctx, a, ne0, offset),
+                (&[ne0, ne1], &[nb1]) => ggml_view_2d(ctx, a, ne0, ne1, nb1, offset),
+                (&[ne0, ne1, ne2], &[nb1, nb2]) => {
+                    ggml_view_3d(ctx, a, ne0, ne1, ne2, nb1, nb2, offset)
+                }
+                (&[ne0, ne1, ne2, ne3], &[nb1, nb2, nb3]) => {
+                    ggml_view_4d(ctx, a, ne0, ne1, ne2, ne3, nb1, nb2, nb3, offset)
+                }
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    /// `ggml_rms_norm_back`: the gradient of [`Tensor::rms_norm`] with input `self` and output
+    /// gradient `b`.
+    #[track_caller]
+    pub fn rms_norm_back(self, b: Tensor<'ctx>, eps: f32) -> Self {
+        or_panic(self.try_rms_norm_back(b, eps))
+    }
+
+    /// `ggml_rms_norm_back`
+    pub fn try_rms_norm_back(self, b: Tensor<'ctx>, eps: f32) -> Result<Self> {
+        let check = shape::unary_back("ggml_rms_norm_back", self, b);
+        self.checked(check, |ctx| unsafe {
+            ggml_rms_norm_back(ctx, self.as_ptr(), b.as_ptr(), eps)
+        })
+    }
+
+    /// `ggml_cross_entropy_loss_back`: the gradient of [`Tensor::cross_entropy_loss`], where
+    /// `c` is the gradient of the loss.
+    #[track_caller]
+    pub fn cross_entropy_loss_back(self, b: Tensor<'ctx>, c: Tensor<'ctx>) -> Self {
+        or_panic(self.try_cross_entropy_loss_back(b, c))
+    }
+
+    /// `ggml_cross_entropy_loss_back`
+    pub fn try_cross_entropy_loss_back(self, b: Tensor<'ctx>, c: Tensor<'ctx>) -> Result<Self> {
+        let check = shape::cross_entropy_loss_back("ggml_cross_entropy_loss_back", self, b, c);
+        self.checked(check, |ctx| unsafe {
+            ggml_cross_entropy_loss_back(ctx, self.as_ptr(), b.as_ptr(), c.as_ptr())
+        })
+    }
+
+    /// `ggml_leaky_relu`
+    #[track_caller]
+    pub fn leaky_relu(self, negative_slope: f32) -> Self {
+        or_panic(self.leaky_relu_impl(negative_slope, false))
+    }
+
+    /// `ggml_leaky_relu`
+    pub fn try_leaky_relu(self, negative_slope: f32) -> Result<Self> {
+        self.leaky_relu_impl(negative_slope, false)
+    }
+
+    /// `ggml_leaky_relu` with `inplace` set.
+    #[track_caller]
+    pub fn leaky_relu_inplace(self, negative_slope: f32) -> Self {
+        or_panic(self.leaky_relu_impl(negative_slope, true))
+    }
+
+    /// `ggml_leaky_relu` with `inplace` set.
+    pub fn try_leaky_relu_inplace(self, negative_slope: f32) -> Result<Self> {
+        self.leaky_relu_impl(negative_slope, true)
+    }
+
+    fn leaky_relu_impl(self, negative_slope: f32, inplace: bool) -> Result<Self> {
+        let check = shape::leaky_relu("ggml_leaky_relu", self, inplace);
+        self.checked(check, |ctx| unsafe {
+            ggml_leaky_relu(ctx, self.as_ptr(), negative_slope, inplace)
+        })
+    }
+
+    /// `ggml_clamp`: note that the result is always a view of `self`, so this is really an
+    /// in-place operation.
+    #[track_caller]
+    pub fn clamp(self, min: f32, max: f32) -> Self {
+        or_panic(self.try_clamp(min, max))
+    }
+
+    /// `ggml_clamp`
+    pub fn try_clamp(self, min: f32, max: f32) -> Result<Self> {
+        self.checked(shape::clamp("ggml_clamp", self, min, max), |ctx| unsafe {
+            ggml_clamp(ctx, self.as_ptr(), min, max)
+        })
+    }
+
+    /// `ggml_group_norm`: normalizes groups of `ne[2]` (channels).
+    #[track_caller]
+    pub fn group_norm(self, n_groups: i32) -> Self {
+        or_panic(self.try_group_norm(n_groups))
+    }
+
+    /// `ggml_group_norm`
+    pub fn try_group_norm(self, n_groups: i32) -> Result<Self> {
+        let check = shape::group_norm("ggml_group_norm", self, n_groups);
+        self.checked(check, |ctx| unsafe {
+            ggml_group_norm(ctx, self.as_ptr(), n_groups)
+        })
+    }
+
+    /// `ggml_group_norm_inplace`
+    #[track_caller]
+    pub fn group_norm_inplace(self, n_groups: i32) -> Self {
+        or_panic(self.try_group_norm_inplace(n_groups))
+    }
+
+    /// `ggml_group_norm_inplace`
+    pub fn try_group_norm_inplace(self, n_groups: i32) -> Result<Self> {
+        let check = shape::group_norm("ggml_group_norm_inplace", self, n_groups);
+        self.checked(check, |ctx| unsafe {
+            ggml_group_norm_inplace(ctx, self.as_ptr(), n_groups)
+        })
+    }
+
+    /// `ggml_diag_mask_inf`: sets the elements above the diagonal (shifted right by `n_past`)
+    /// to `-inf`.
+    #[track_caller]
+    pub fn diag_mask_inf(self, n_past: i32) -> Self {
+        or_panic(self.try_diag_mask_inf(n_past))
+    }
+
+    /// `ggml_diag_mask_inf`
+    pub fn try_diag_mask_inf(self, n_past: i32) -> Result<Self> {
+        self.diag_mask_impl("ggml_diag_mask_inf", ggml_diag_mask_inf, n_past)
+    }
+
+    /// `ggml_diag_mask_inf_inplace`
+    #[track_caller]
+    pub fn diag_mask_inf_inplace(self, n_past: i32) -> Self {
+        or_panic(self.try_diag_mask_inf_inplace(n_past))
+    }
+
+    /// `ggml_diag_mask_inf_inplace`
+    pub fn try_diag_mask_inf_inplace(self, n_past: i32) -> Result<Self> {
+        self.diag_mask_impl(
+            "ggml_diag_mask_inf_inplace",
+            ggml_diag_mask_inf_inplace,
+            n_past,
+        )
+    }
+
+    /// `ggml_diag_mask_zero`: like [`Tensor::diag_mask_inf`] but with zeros.
+    #[track_caller]
+    pub fn diag_mask_zero(self, n_past: i32) -> Self {
+        or_panic(self.try_diag_mask_zero(n_past))
+    }
+
+    /// `ggml_diag_mask_zero`
+    pub fn try_diag_mask_zero(self, n_past: i32) -> Result<Self> {
+        self.diag_mask_impl("ggml_diag_mask_zero", ggml_diag_mask_zero, n_past)
+    }
+
+    /// `ggml_diag_mask_zero_inplace`
+    #[track_caller]
+    pub fn diag_mask_zero_inplace(self, n_past: i32) -> Self {
+        or_panic(self.try_diag_mask_zero_inplace(n_past))
+    }
+
+    /// `ggml_diag_mask_zero_inplace`
+    pub fn try_diag_mask_zero_inplace(self, n_past: i32) -> Result<Self> {
+        self.diag_mask_impl(
+            "ggml_diag_mask_zero_inplace",
+            ggml_diag_mask_zero_inplace,
+            n_past,
+        )
+    }
+
+    fn diag_mask_impl(
+        self,
+        name: &'static str,
+        fun: unsafe extern "C" fn(*mut ggml_context, *mut ggml_tensor, c_int) -> *mut ggml_tensor,
+        n_past: i32,
+    ) -> Result<Self> {
+        self.checked(shape::diag_mask(name, self, n_past), |ctx| unsafe {
+            fun(ctx, self.as_ptr(), n_past)
+        })
+    }
+
+    /// `ggml_add_cast`: adds the F32 tensor `b` to an F16 or quantized `self`, with a result
+    /// of type `type_`.
+    #[track_caller]
+    pub fn add_cast(self, b: Tensor<'ctx>, type_: ggml_type) -> Self {
+        or_panic(self.try_add_cast(b, type_))
+    }
+
+    /// `ggml_add_cast`
+    pub fn try_add_cast(self, b: Tensor<'ctx>, type_: ggml_type) -> Result<Self> {
+        let check = shape::add_cast("ggml_add_cast", self, b, type_);
+        self.checked(check, |ctx| unsafe {
+            ggml_add_cast(ctx, self.as_ptr(), b.as_ptr(), type_)
+        })
+    }
+
+    /// `ggml_mul_mat_id`: `self` is a stack of matrices along `ne[2]` (i.e. the experts of a
+    /// mixture of experts) and the I32 tensor `ids` picks the ones to multiply each row of `b`
+    /// with. The ids have to be in `0..self.ne[2]`, which GGML asserts on at compute time.
+    #[track_caller]
+    pub fn matmul_id(self, b: Tensor<'ctx>, ids: Tensor<'ctx>) -> Self {
+        or_panic(self.try_matmul_id(b, ids))
+    }
+
+    /// `ggml_mul_mat_id`
+    pub fn try_matmul_id(self, b: Tensor<'ctx>, ids: Tensor<'ctx>) -> Result<Self> {
+        let check = shape::mul_mat_id("ggml_mul_mat_id", self, b, ids);
+        self.checked(check, |ctx| unsafe {
+            ggml_mul_mat_id(ctx, self.as_ptr(), b.as_ptr(), ids.as_ptr())
+        })
+    }
+
+    /// `ggml_acc`: adds `b` to the view of `self` with strides `nb` (for the dimensions after
+    /// the first) starting at byte `offset`.
+    #[track_caller]
+    pub fn acc(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Self {
+        or_panic(self.try_acc(b, nb, offset))
+    }
+
+    /// `ggml_acc`
+    pub fn try_acc(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Result<Self> {
+        self.set_impl("ggml_acc", ggml_acc, b, nb, offset)
+    }
+
+    /// `ggml_acc_inplace`
+    #[track_caller]
+    pub fn acc_inplace(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Self {
+        or_panic(self.try_acc_inplace(b, nb, offset))
+    }
+
+    /// `ggml_acc_inplace`
+    pub fn try_acc_inplace(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Result<Self> {
+        self.set_impl("ggml_acc_inplace", ggml_acc_inplace, b, nb, offset)
+    }
+
+    /// `ggml_set`: like [`Tensor::acc`], but overwrites the view of `self`. Use `self.nb()` as
+    /// the strides to set a part of `self` (`ggml_set_1d` and `ggml_set_2d`).
+    #[track_caller]
+    pub fn set(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Self {
+        or_panic(self.try_set(b, nb, offset))
+    }
+
+    /// `ggml_set`
+    pub fn try_set(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Result<Self> {
+        self.set_impl("ggml_set", ggml_set, b, nb, offset)
+    }
+
+    /// `ggml_set_inplace`
+    #[track_caller]
+    pub fn set_inplace(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Self {
+        or_panic(self.try_set_inplace(b, nb, offset))
+    }
+
+    /// `ggml_set_inplace`
+    pub fn try_set_inplace(self, b: Tensor<'ctx>, nb: [usize; 3], offset: usize) -> Result<Self> {
+        self.set_impl("ggml_set_inplace", ggml_set_inplace, b, nb, offset)
+    }
+
+    fn set_impl(
+        self,
+        name: &'static str,
+        fun: SetFn,
+        b: Tensor<'ctx>,
+        [nb1, nb2, nb3]: [usize; 3],
+        offset: usize,
+    ) -> Result<Self> {
+        let check = shape::set(name, self, b, [nb1, nb2, nb3], offset);
+        self.checked(check, |ctx| unsafe {
+            fun(ctx, self.as_ptr(), b.as_ptr(), nb1, nb2, nb3, offset)
+        })
+    }
+
+    /// `ggml_cast`: converts to `type_`. Like [`Tensor::cpy`], only conversions between F32 and
+    /// the other types are supported.
+    #[track_caller]
+    pub fn cast(self, type_: ggml_type) -> Self {
+        or_panic(self.try_cast(type_))
+    }
+
+    /// `ggml_cast`
+    pub fn try_cast(self, type_: ggml_type) -> Result<Self> {
+        self.checked(shape::cast("ggml_cast", self, type_), |ctx| unsafe {
+            ggml_cast(ctx, self.as_ptr(), type_)
+        })
+    }
+
+    /// `ggml_cont_1d` through `ggml_cont_4d`: a contiguous copy with a new shape.
+    #[track_caller]
+    pub fn cont_shape(self, shape: &[i64]) -> Self {
+        or_panic(self.try_cont_shape(shape))
+    }
+
+    /// `ggml_cont_1d` through `ggml_cont_4d`
+    pub fn try_cont_shape(self, shape: &[i64]) -> Result<Self> {
+        self.checked(shape::cont_shape("ggml_cont", self, shape), |ctx| unsafe {
+            let a = self.as_ptr();
+            match *shape {
+                [ne0] => ggml_cont_1d(ctx, a, ne0),
+                [ne0, ne1] => ggml_cont_2d(ctx, a, ne0, ne1),
+                [ne0, ne1, ne2] => ggml_cont_3d(ctx, a, ne0, ne1, ne2),
+                [ne0, ne1, ne2, ne3] => ggml_cont_4d(ctx, a, ne0, ne1, ne2, ne3),
+                _ => unreachable!(),
+            }
+        })
+    }
+
+    /// `ggml_rope_back`: the gradient of [`Tensor::rope_ext`] without frequency factors.
+    #[track_caller]
+    pub fn rope_back(self, pos: Tensor<'ctx>, params: &RopeParams) -> Self {
+        or_panic(self.try_rope_back(pos, params))
+    }
+
+    /// `ggml_rope_back`
+    pub fn try_rope_back(self, pos: Tensor<'ctx>, params: &RopeParams) -> Result<Self> {
+        let check = shape::rope_back("ggml_rope_back", self, pos, params.n_dims);
+        self.checked(check, |ctx| unsafe {
+            ggml_rope_back(
+                ctx,
+                self.as_ptr(),
+                pos.as_ptr(),
+                ptr::null_mut(),
+                params.n_dims,
+                params.mode,
+                params.n_ctx_orig,
+                params.freq_base,
+                params.freq_scale,
+                params.ext_factor,
+                params.attn_factor,
+                params.beta_fast,
+                params.beta_slow,
+            )
+        })
+    }
+
+    /// `ggml_im2col`: unfolds the patches of `b` that the kernel `self` (only its shape is
+    /// used) is applied to into rows, so a convolution becomes a matrix multiplication.
+    /// `stride`, `padding` and `dilation` are given as `[x, y]`, `y` being ignored for 1D.
+    #[track_caller]
+    pub fn im2col(
+        self,
+        b: Tensor<'ctx>,
+        stride: [i32; 2],
+        padding: [i32; 2],
+        dilation: [i32; 2],
+        is_2d: bool,
+        dst_type: ggml_type,
+    ) -> Self {
+        or_panic(self.try_im2col(b, stride, padding, dilation, is_2d, dst_type))
+    }
+
+    /// `ggml_im2col`
+    pub fn try_im2col(
+        self,
+        b: Tensor<'ctx>,
+        [s0, s1]: [i32; 2],
+        [p0, p1]: [i32; 2],
+        [d0, d1]: [i32; 2],
+        is_2d: bool,
+        dst_type: ggml_type,
+    ) -> Result<Self> {
+        let check = shape::im2col(
+            "ggml_im2col",
+            self,
+            b,
+            [s0, s1],
+            [p0, p1],
+            [d0, d1],
+            is_2d,
+            dst_type,
+        );
+        self.checked(check, |ctx| unsafe {
+            ggml_im2col(
+                ctx,
+                self.as_ptr(),
+                b.as_ptr(),
+                s0,
+                s1,
+                p0,
+                p1,
+                d0,
+                d1,
+                is_2d,
+                dst_type,
+            )
+        })
+    }
+
+    /// `ggml_conv_1d`: convolves `b` (`[W, IC, N]`) with the kernel `self` (`[K, IC, OC]`).
+    /// The result is `[OW, OC, N]`.
+    #[track_caller]
+    pub fn conv_1d(self, b: Tensor<'ctx>, s0: i32, p0: i32, d0: i32) -> Self {
+        or_panic(self.try_conv_1d(b, s0, p0, d0))
+    }
+
+    /// `ggml_conv_1d`
+    pub fn try_conv_1d(self, b: Tensor<'ctx>, s0: i32, p0: i32, d0: i32) -> Result<Self> {
+        let check = shape::conv_1d("ggml_conv_1d", self, b, s0, p0, d0);
+        self.checked(check, |ctx| unsafe {
+            ggml_conv_1d(ctx, self.as_ptr(), b.as_ptr(), s0, p0, d0)
+        })
+    }
+
+    /// `ggml_conv_2d`: convolves `b` (`[W, H, IC, N]`) with the kernel `self`
+    /// (`[KW, KH, IC, OC]`). The result is `[OW, OH, OC, N]`.
+    #[track_caller]
+    pub fn conv_2d(
+        self,
+        b: Tensor<'ctx>,
+        stride: [i32; 2],
+        padding: [i32; 2],
+        dilation: [i32; 2],
+    ) -> Self {
+        or_panic(self.try_conv_2d(b, stride, padding, dilation))
+    }
+
+    /// `ggml_conv_2d`
+    pub fn try_conv_2d(
+        self,
+        b: Tensor<'ctx>,
+        [s0, s1]: [i32; 2],
+        [p0, p1]: [i32; 2],
+        [d0, d1]: [i32; 2],
+    ) -> Result<Self> {
+        let check = shape::conv_2d("ggml_conv_2d", self, b, [s0, s1], [p0, p1], [d0, d1]);
+        self.checked(check, |ctx| unsafe {
+            ggml_conv_2d(ctx, self.as_ptr(), b.as_ptr(), s0, s1, p0, p1, d0, d1)
+        })
+    }
+
+    /// `ggml_conv_transpose_1d`: GGML only implements it without padding and dilation.
+    #[track_caller]
+    pub fn conv_transpose_1d(self, b: Tensor<'ctx>, s0: i32) -> Self {
+        or_panic(self.try_conv_transpose_1d(b, s0))
+    }
+
+    /// `ggml_conv_transpose_1d`
+    pub fn try_conv_transpose_1d(self, b: Tensor<'ctx>, s0: i32) -> Result<Self> {
+        let check = shape::conv_transpose_1d("ggml_conv_transpose_1d", self, b, s0);
+        self.checked(check, |ctx| unsafe {
+            ggml_conv_transpose_1d(ctx, self.as_ptr(), b.as_ptr(), s0, 0, 1)
+        })
+    }
+
+    /// `ggml_conv_transpose_2d_p0`
+    #[track_caller]
+    pub fn conv_transpose_2d_p0(self, b: Tensor<'ctx>, stride: i32) -> Self {
+        or_panic(self.try_conv_transpose_2d_p0(b, stride))
+    }
+
+    /// `ggml_conv_transpose_2d_p0`
+    pub fn try_conv_transpose_2d_p0(self, b: Tensor<'ctx>, stride: i32) -> Result<Self> {
+        let check = shape::conv_transpose_2d_p0("ggml_conv_transpose_2d_p0", self, b, stride);
+        self.checked(check, |ctx| unsafe {
+            ggml_conv_transpose_2d_p0(ctx, self.as_ptr(), b.as_ptr(), stride)
+        })
+    }
+
+    /// `ggml_pool_1d` along `ne[0]`. GGML only implements windows that don't overlap, so `s0`
+    /// has to equal `k0`, and no padding.
+    #[track_caller]
+    pub fn pool_1d(self, op: ggml_op_pool, k0: i32, s0: i32) -> Self {
+        or_panic(self.try_pool_1d(op, k0, s0))
+    }
+
+    /// `ggml_pool_1d`
+    pub fn try_pool_1d(self, op: ggml_op_pool, k0: i32, s0: i32) -> Result<Self> {
+        let check = shape::pool_1d("ggml_pool_1d", self, op, k0, s0);
+        self.checked(check, |ctx| unsafe {
+            ggml_pool_1d(ctx, self.as_ptr(), op, k0, s0, 0)
+        })
+    }
+
+    /// `ggml_pool_2d` over `ne[0]` and `ne[1]`, with `kernel`, `stride` and `padding` given as
+    /// `[x, y]`.
+    #[track_caller]
+    pub fn pool_2d(
+        self,
+        op: ggml_op_pool,
+        kernel: [i32; 2],
+        stride: [i32; 2],
+        padding: [i32; 2],
+    ) -> Self {
+        or_panic(self.try_pool_2d(op, kernel, stride, padding))
+    }
+
+    /// `ggml_pool_2d`
+    pub fn try_pool_2d(
+        self,
+        op: ggml_op_pool,
+        [k0, k1]: [i32; 2],
+        [s0, s1]: [i32; 2],
+        [p0, p1]: [i32; 2],
+    ) -> Result<Self> {
+        let check = shape::pool_2d("ggml_pool_2d", self, op, [k0, k1], [s0, s1], [p0, p1]);
+        self.checked(check, |ctx| unsafe {
+            ggml_pool_2d(ctx, self.as_ptr(), op, k0, k1, s0, s1, p0 as f32, p1 as f32)
+        })
+    }
+
+    /// `ggml_upscale`: nearest neighbour upscaling of `ne[0]` and `ne[1]` by
+    /// `scale_factor`.
+    #[track_caller]
+    pub fn upscale(self, scale_factor: i32) -> Self {
+        or_panic(self.try_upscale(scale_factor))
+    }
+
+    /// `ggml_upscale`
+    pub fn try_upscale(self, scale_factor: i32) -> Result<Self> {
+        let [ne0, ne1, ne2, ne3] = self.ne();
+        let sf = scale_factor as i64;
+        let ne = [ne0.saturating_mul(sf), ne1.saturating_mul(sf), ne2, ne3];
+        self.checked(shape::upscale("ggml_upscale", self, ne), |ctx| unsafe {
+            ggml_upscale(ctx, self.as_ptr(), scale_factor)
+        })
+    }
+
+    /// `ggml_upscale_ext`: nearest neighbour upscaling to the shape `ne`.
+    #[track_caller]
+    pub fn upscale_ext(self, ne: [i64; 4]) -> Self {
+        or_panic(self.try_upscale_ext(ne))
+    }
+
+    /// `ggml_upscale_ext`
+    pub fn try_upscale_ext(self, ne: [i64; 4]) -> Result<Self> {
+        let check = shape::upscale("ggml_upscale_ext", self, ne);
+        let [ne0, ne1, ne2, ne3] = ne.map(|ne| ne as c_int);
+        self.checked(check, |ctx| unsafe {
+            ggml_upscale_ext(ctx, self.as_ptr(), ne0, ne1, ne2, ne3)
+        })
+    }
+
+    /// `ggml_pad`: pads the end of each dimension with `p[i]` zeros.
+    #[track_caller]
+    pub fn pad(self, p: [i32; 4]) -> Self {
+        or_panic(self.try_pad(p))
+    }
+
+    /// `ggml_pad`
+    pub fn try_pad(self, p: [i32; 4]) -> Result<Self> {
+        let [p0, p1, p2, p3] = p;
+        self.checked(shape::pad("ggml_pad", self, p), |ctx| unsafe {
+            ggml_pad(ctx, self.as_ptr(), p0, p1, p2, p3)
+        })
+    }
+
+    /// `ggml_timestep_embedding`: sinusoidal embeddings of size `dim` (rounded up to even) for
+    /// the timesteps in the vector `self`.
+    #[track_caller]
+    pub fn timestep_embedding(self, dim: i32, max_period: i32) -> Self {
+        or_panic(self.try_timestep_embedding(dim, max_period))
+    }
+
+    /// `ggml_timestep_embedding`
+    pub fn try_timestep_embedding(self, dim: i32, max_period: i32) -> Result<Self> {
+        let check = shape::timestep_embedding("ggml_timestep_embedding", self, dim);
+        self.checked(check, |ctx| unsafe {
+            ggml_timestep_embedding(ctx, self.as_ptr(), dim, max_period)
+        })
+    }
+
+    /// `ggml_argsort`: the I32 indices that sort each row.
+    #[track_caller]
+    pub fn argsort(self, order: ggml_sort_order) -> Self {
+        or_panic(self.try_argsort(order))
+    }
+
+    /// `ggml_argsort`
+    pub fn try_argsort(self, order: ggml_sort_order) -> Result<Self> {
+        self.checked(shape::argsort("ggml_argsort", self, order), |ctx| unsafe {
+            ggml_argsort(ctx, self.as_ptr(), order)
+        })
+    }
+
+    /// `ggml_top_k`: the I32 indices of the `k` largest elements of each row, largest first.
+    #[track_caller]
+    pub fn top_k(self, k: i32) -> Self {
+        or_panic(self.try_top_k(k))
+    }
+
+    /// `ggml_top_k`
+    pub fn try_top_k(self, k: i32) -> Result<Self> {
+        self.checked(shape::top_k("ggml_top_k", self, k), |ctx| unsafe {
+            ggml_top_k(ctx, self.as_ptr(), k)
+        })
+    }
+
+    /// `ggml_flash_attn_ext` with queries `self` (`[D, N, H, B]`, F32), keys and values
+    /// `[D, M, H_kv, B_kv]` (F16) and an optional F16 mask of at least
+    /// `[M, N padded to GGML_KQ_MASK_PAD]`. Note that the result is `[D, H, N, B]`.
+    #[track_caller]
+    pub fn flash_attn_ext(
+        self,
+        k: Tensor<'ctx>,
+        v: Tensor<'ctx>,
+        mask: Option<Tensor<'ctx>>,
+        scale: f32,
+        max_bias: f32,
+    ) -> Self {
+        or_panic(self.try_flash_attn_ext(k, v, mask, scale, max_bias))
+    }
+
+    /// `ggml_flash_attn_ext`
+    pub fn try_flash_attn_ext(
+        self,
+        k: Tensor<'ctx>,
+        v: Tensor<'ctx>,
+        mask: Option<Tensor<'ctx>>,
+        scale: f32,
+        max_bias: f32,
+    ) -> Result<Self> {
+        let check = shape::flash_attn_ext("ggml_flash_attn_ext", self, k, v, mask, max_bias);
+        self.checked(check, |ctx| unsafe {
+            ggml_flash_attn_ext(
+                ctx,
+                self.as_ptr(),
+                k.as_ptr(),
+                v.as_ptr(),
+                mask.map_or(ptr::null_mut(), |t| t.as_ptr()),
+                scale,
+                max_bias,
+            )
+        })
+    }
+
+    /// `ggml_flash_attn_back`: the gradients of attention with queries `self` (`[D, N, H, B]`),
+    /// keys `k` (`[D, M, H_kv, B]`), transposed values `v` (`[M, D, H_kv, B]`) and output
+    /// gradient `d`. They're returned one after the other in a single F32 vector, each padded
+    /// to `GGML_MEM_ALIGN` bytes.
+    #[track_caller]
+    pub fn flash_attn_back(
+        self,
+        k: Tensor<'ctx>,
+        v: Tensor<'ctx>,
+        d: Tensor<'ctx>,
+        masked: bool,
+    ) -> Self {
+        or_panic(self.try_flash_attn_back(k, v, d, masked))
+    }
+
+    /// `ggml_flash_attn_back`
+    pub fn try_flash_attn_back(
+        self,
+        k: Tensor<'ctx>,
+        v: Tensor<'ctx>,
+        d: Tensor<'ctx>,
+        masked: bool,
+    ) -> Result<Self> {
+        let check = shape::flash_attn_back("ggml_flash_attn_back", self, k, v, d);
+        self.checked(check, |ctx| unsafe {
+            ggml_flash_attn_back(
+                ctx,
+                self.as_ptr(),
+                k.as_ptr(),
+                v.as_ptr(),
+                d.as_ptr(),
+                masked,
+            )
+        })
+    }
+
+    /// `ggml_unary`: applies one of the `GGML_UNARY_OP_*` operations, like [`Tensor::relu`]
+    /// and friends do.
+    #[track_caller]
+    pub fn unary(self, op: ggml_unary_op) -> Self {
+        or_panic(self.try_unary(op))
+    }
+
+    /// `ggml_unary`
+    pub fn try_unary(self, op: ggml_unary_op) -> Result<Self> {
+        self.checked(shape::unary_op("ggml_unary", self, op), |ctx| unsafe {
+            ggml_unary(ctx, self.as_ptr(), op)
+        })
+    }
+
+    /// `ggml_unary_inplace`
+    #[track_caller]
+    pub fn unary_inplace(self, op: ggml_unary_op) -> Self {
+        or_panic(self.try_unary_inplace(op))
+    }
+
+    /// `ggml_unary_inplace`
+    pub fn try_unary_inplace(self, op: ggml_unary_op) -> Result<Self> {
+        let check = shape::unary_op("ggml_unary_inplace", self, op);
+        self.checked(check, |ctx| unsafe {
+            ggml_unary_inplace(ctx, self.as_ptr(), op)
+        })
+    }
+
+    /// `ggml_get_rows_back`: the gradient of [`Tensor::get_rows`], adding the rows of `self`
+    /// to the rows `b` of a zero matrix shaped like `c`.
+    ///
+    /// # Safety
+    /// The row indices in `b` must be less than `c.ne[1]` when the graph is computed, which
+    /// GGML doesn't check.
+    #[track_caller]
+    pub unsafe fn get_rows_back(self, b: Tensor<'ctx>, c: Tensor<'ctx>) -> Self {
+        or_panic(self.try_get_rows_back(b, c))
+    }
+
+    /// `ggml_get_rows_back`
+    ///
+    /// # Safety
+    /// See [`Tensor::get_rows_back`].
+    pub unsafe fn try_get_rows_back(self, b: Tensor<'ctx>, c: Tensor<'ctx>) -> Result<Self> {
+        let check = shape::get_rows_back("ggml_get_rows_back", self, b, c);
+        self.checked(check, |ctx| {
+            ggml_get_rows_back(ctx, self.as_ptr(), b.as_ptr(), c.as_ptr())
+        })
+    }
+
+    /// `ggml_ssm_conv`: the convolution of a Mamba layer, with states `self`
+    /// (`[d_conv - 1, d_inner, n_kv]`), tokens `x` (`[d_inner, n_tokens]`), kernel `c`
+    /// (`[d_conv, d_inner]`) and the I32 sequence ids of each token `sq` (`[n_kv, n_tokens]`).
+    /// The result is an F32 vector of the output followed by the new states.
+    ///
+    /// # Safety
+    /// The sequence ids in `sq` must be less than `n_kv` when the graph is computed, which GGML
+    /// doesn't check for all of them.
+    #[track_caller]
+    pub unsafe fn ssm_conv(self, x: Tensor<'ctx>, c: Tensor<'ctx>, sq: Tensor<'ctx>) -> Self {
+        or_panic(self.try_ssm_conv(x, c, sq))
+    }
+
+    /// `ggml_ssm_conv`
+    ///
+    /// # Safety
+    /// See [`Tensor::ssm_conv`].
+    pub unsafe fn try_ssm_conv(
+        self,
+        x: Tensor<'ctx>,
+        c: Tensor<'ctx>,
+        sq: Tensor<'ctx>,
+    ) -> Result<Self> {
+        let check = shape::ssm_conv("ggml_ssm_conv", self, x, c, sq);
+        self.checked(check, |ctx| {
+            ggml_ssm_conv(ctx, self.as_ptr(), x.as_ptr(), c.as_ptr(), sq.as_ptr())
+        })
+    }
+
+    /// `ggml_ssm_scan`: the selective scan of a Mamba layer, with states `self`
+    /// (`[d_state, d_inner, n_kv]`), `x` and `dt` (`[d_inner, n_tokens]`), `a`
+    /// (`[d_state, d_inner]`), `b` and `c` (`[d_state, n_tokens]`) and the I32 sequence ids of
+    /// each token `sq` (`[n_kv, n_tokens]`). The result is an F32 vector of the output followed
+    /// by the new states.
+    ///
+    /// # Safety
+    /// See [`Tensor::ssm_conv`].
+    #[allow(clippy::too_many_arguments)]
+    #[track_caller]
+    pub unsafe fn ssm_scan(
+        self,
+        x: Tensor<'ctx>,
+        dt: Tensor<'ctx>,
+        a: Tensor<'ctx>,
+        b: Tensor<'ctx>,
+        c: Tensor<'ctx>,
+        sq: Tensor<'ctx>,
+    ) -> Self {
+        or_panic(self.try_ssm_scan(x, dt, a, b, c, sq))
+    }
+
+    /// `ggml_ssm_scan`
+    ///
+    /// # Safety
+    /// See [`Tensor::ssm_conv`].
+    #[allow(clippy::too_many_arguments)]
+    pub unsafe fn try_ssm_scan(
+        self,
+        x: Tensor<'ctx>,
+        dt: Tensor<'ctx>,
+        a: Tensor<'ctx>,
+        b: Tensor<'ctx>,
+        c: Tensor<'ctx>,
+        sq: Tensor<'ctx>,
+    ) -> Result<Self> {
+        let check = shape::ssm_scan("ggml_ssm_scan", self, x, dt, a, b, c, sq);
+        self.checked(check, |ctx| {
+            ggml_ssm_scan(
+                ctx,
+                self.as_ptr(),
+                x.as_ptr(),
+                dt.as_ptr(),
+                a.as_ptr(),
+                b.as_ptr(),
+                c.as_ptr(),
+                sq.as_ptr(),
+            )
+        })
+    }
+
+    /// `ggml_win_part`: splits `ne[1]` and `ne[2]` into `w` x `w` windows (zero padded), which
+    /// end up along `ne[3]`.
+    #[track_caller]
+    pub fn win_part(self, w: i32) -> Self {
+        or_panic(self.try_win_part(w))
+    }
+
+    /// `ggml_win_part`
+    pub fn try_win_part(self, w: i32) -> Result<Self> {
+        self.checked(shape::win_part("ggml_win_part", self, w), |ctx| unsafe {
+            ggml_win_part(ctx, self.as_ptr(), w)
+        })
+    }
+
+    /// `ggml_win_unpart`: the reverse of [`Tensor::win_part`] for an original size of `w0` x
+    /// `h0`.
+    #[track_caller]
+    pub fn win_unpart(self, w0: i32, h0: i32, w: i32) -> Self {
+        or_panic(self.try_win_unpart(w0, h0, w))
+    }
+
+    /// `ggml_win_unpart`
+    pub fn try_win_unpart(self, w0: i32, h0: i32, w: i32) -> Result<Self> {
+        let check = shape::win_unpart("ggml_win_unpart", self, w0, h0, w);
+        self.checked(check, |ctx| unsafe {
+            ggml_win_unpart(ctx, self.as_ptr(), w0, h0, w)
+        })
+    }
+
+    /// `ggml_get_rel_pos`: the F16 relative position embeddings of `qh` queries and as many
+    /// keys from the table `self` with `2 * qh - 1` rows.
+    #[track_caller]
+    pub fn get_rel_pos(self, qh: i32, kh: i32) -> Self {
+        or_panic(self.try_get_rel_pos(qh, kh))
+    }
+
+    /// `ggml_get_rel_pos`
+    pub fn try_get_rel_pos(self, qh: i32, kh: i32) -> Result<Self> {
+        let check = shape::get_rel_pos("ggml_get_rel_pos", self, qh, kh);
+        self.checked(check, |ctx| unsafe {
+            ggml_get_rel_pos(ctx, self.as_ptr(), qh, kh)
+        })
+    }
+
+    /// `ggml_add_rel_pos`: adds the relative positions along the width `pw` and height `ph`
+    /// to the attention `self`.
+    #[track_caller]
+    pub fn add_rel_pos(self, pw: Tensor<'ctx>, ph: Tensor<'ctx>) -> Self {
+        or_panic(self.try_add_rel_pos(pw, ph))
+    }
+
+    /// `ggml_add_rel_pos`
+    pub fn try_add_rel_pos(self, pw: Tensor<'ctx>, ph: Tensor<'ctx>) -> Result<Self> {
+        self.add_rel_pos_impl("ggml_add_rel_pos", ggml_add_rel_pos, pw, ph)
+    }
+
+    /// `ggml_add_rel_pos_inplace`
+    #[track_caller]
+    pub fn add_rel_pos_inplace(self, pw: Tensor<'ctx>, ph: Tensor<'ctx>) -> Self {
+        or_panic(self.try_add_rel_pos_inplace(pw, ph))
+    }
+
+    /// `ggml_add_rel_pos_inplace`
+    pub fn try_add_rel_pos_inplace(self, pw: Tensor<'ctx>, ph: Tensor<'ctx>) -> Result<Self> {
+        self.add_rel_pos_impl("ggml_add_rel_pos_inplace", ggml_add_rel_pos_inplace, pw, ph)
+    }
+
+    fn add_rel_pos_impl(
+        self,
+        name: &'static str,
+        fun: unsafe extern "C" fn(
+            *mut ggml_context,
+            *mut ggml_tensor,
+            *mut ggml_tensor,
+            *mut ggml_tensor,
+        ) -> *mut ggml_tensor,
+        pw: Tensor<'ctx>,
+        ph: Tensor<'ctx>,
+    ) -> Result<Self> {
+        self.checked(shape::add_rel_pos(name, self, pw, ph), |ctx| unsafe {
+            fun(ctx, self.as_ptr(), pw.as_ptr(), ph.as_ptr())
+        })
+    }
+
+    /// `ggml_conv_depthwise_2d`: convolves each channel of `b` (`[W, H, C, N]`) with its own
+    /// F16 kernel in `self` (`[KW, KH, 1, C]`). The result is `[OW, OH, C, N]`.
+    #[track_caller]
+    pub fn conv_depthwise_2d(
+        self,
+        b: Tensor<'ctx>,
+        stride: [i32; 2],
+        padding: [i32; 2],
+        dilation: [i32; 2],
+    ) -> Self {
+        or_panic(self.try_conv_depthwise_2d(b, stride, padding, dilation))
+    }
+
+    /// `ggml_conv_depthwise_2d`
+    pub fn try_conv_depthwise_2d(
+        self,
+        b: Tensor<'ctx>,
+        [s0, s1]: [i32; 2],
+        [p0, p1]: [i32; 2],
+        [d0, d1]: [i32; 2],
+    ) -> Result<Self> {
+        let check = shape::conv_depthwise_2d(
+            "ggml_conv_depthwise_2d",
+            self,
+            b,
+            [s0, s1],
+            [p0, p1],
+            [d0, d1],
+        );
+        self.checked(check, |ctx| unsafe {
+            ggml_conv_depthwise_2d(ctx, self.as_ptr(), b.as_ptr(), s0, s1, p0, p1, d0, d1)
+        })
+    }
+}
+
+impl Context {
+    /// `ggml_arange`: an F32 vector counting from `start` to `stop` (excluded) by `step`.
+    #[track_caller]
+    pub fn arange(&self, start: f32, stop: f32, step: f32) -> Tensor<'_> {
+        or_panic(self.try_arange(start, stop, step))
+    }
+
+    /// `ggml_arange`
+    pub fn try_arange(&self, start: f32, stop: f32, step: f32) -> Result<Tensor<'_>> {
+        let out = shape::arange("ggml_arange", start, stop, step)?;
+        self.ensure_room(out.type_, &out.ne, out.view)?;
+        let ptr = unsafe { ggml_arange(self.as_ptr(), start, stop, step) };
+        Ok(unsafe { Tensor::from_raw(self, ptr) }.expect("GGML operation returned NULL"))
+    }
+}
+
+impl<'ctx> Add for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_add`, see [`Tensor::try_add`].
+    #[track_caller]
+    fn add(self, rhs: Self) -> Self::Output {
+        or_panic(self.try_add(rhs))
+    }
+}
+
+impl<'ctx> Sub for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_sub`, see [`Tensor::try_sub`].
+    #[track_caller]
+    fn sub(self, rhs: Self) -> Self::Output {
+        or_panic(self.try_sub(rhs))
+    }
+}
+
+impl<'ctx> Mul for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_mul`, which is element-wise. See [`Tensor::matmul`] for matrix multiplication.
+    #[track_caller]
+    fn mul(self, rhs: Self) -> Self::Output {
+        or_panic(self.try_mul(rhs))
+    }
+}
+
+impl<'ctx> Div for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_div`, see [`Tensor::try_div`].
+    #[track_caller]
+    fn div(self, rhs: Self) -> Self::Output {
+        or_panic(self.try_div(rhs))
+    }
+}
+
+impl<'ctx> Mul<f32> for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_scale`
+    #[track_caller]
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.scale(rhs)
+    }
+}
+
+impl<'ctx> Neg for Tensor<'ctx> {
+    type Output = Tensor<'ctx>;
+
+    /// `ggml_neg`, see [`Tensor::try_neg`].
+    #[track_caller]
+    fn neg(self) -> Self::Output {
+        or_panic(self.try_neg())
+    }
+}
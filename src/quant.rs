@@ -0,0 +1,303 @@
+//! Quantizing and dequantizing `f32` data without going through a context.
+//!
+//! [`quantize`] wraps `ggml_quantize_chunk` (calling `ggml_quantize_init` and checking the
+//! importance matrix requirement first) and [`dequantize`] the type's `to_float` from
+//! `ggml_internal_get_type_traits`. Both split the rows between all available threads.
+//!
+//! Shapes are in GGML order, so `shape[0]` is the length of a row and has to be a multiple of
+//! the type's block size.
+
+use std::{ffi::CStr, ops::Range, thread};
+
+use crate::{
+    context,
+    error::{Error, Result},
+    shape::full_ne,
+    *,
+};
+
+/// The types `ggml_quantize_chunk` can produce.
+pub const QUANTIZE_TYPES: &[ggml_type] = &[
+    ggml_type_GGML_TYPE_F32,
+    ggml_type_GGML_TYPE_F16,
+    ggml_type_GGML_TYPE_BF16,
+    ggml_type_GGML_TYPE_Q4_0,
+    ggml_type_GGML_TYPE_Q4_1,
+    ggml_type_GGML_TYPE_Q5_0,
+    ggml_type_GGML_TYPE_Q5_1,
+    ggml_type_GGML_TYPE_Q8_0,
+    ggml_type_GGML_TYPE_Q2_K,
+    ggml_type_GGML_TYPE_Q3_K,
+    ggml_type_GGML_TYPE_Q4_K,
+    ggml_type_GGML_TYPE_Q5_K,
+    ggml_type_GGML_TYPE_Q6_K,
+    ggml_type_GGML_TYPE_IQ2_XXS,
+    ggml_type_GGML_TYPE_IQ2_XS,
+    ggml_type_GGML_TYPE_IQ2_S,
+    ggml_type_GGML_TYPE_IQ3_XXS,
+    ggml_type_GGML_TYPE_IQ3_S,
+    ggml_type_GGML_TYPE_IQ1_S,
+    ggml_type_GGML_TYPE_IQ1_M,
+    ggml_type_GGML_TYPE_IQ4_NL,
+    ggml_type_GGML_TYPE_IQ4_XS,
+];
+
+/// Whether `type_` is one of [`QUANTIZE_TYPES`].
+pub fn can_quantize(type_: ggml_type) -> bool {
+    QUANTIZE_TYPES.contains(&type_)
+}
+
+/// `ggml_internal_get_type_traits`, or `None` for types that don't exist.
+pub fn type_traits(type_: ggml_type) -> Option<ggml_type_traits_t> {
+    if type_ >= ggml_type_GGML_TYPE_COUNT || unsafe { ggml_blck_size(type_) } == 0 {
+        return None;
+    }
+    Some(unsafe { ggml_internal_get_type_traits(type_) })
+}
+
+/// `ggml_type_name` as a string.
+pub fn type_name(type_: ggml_type) -> &'static str {
+    if type_ >= ggml_type_GGML_TYPE_COUNT {
+        return "unknown";
+    }
+    let name = unsafe { ggml_type_name(type_) };
+    if name.is_null() {
+        return "unknown";
+    }
+    unsafe { CStr::from_ptr(name) }
+        .to_str()
+        .unwrap_or("unknown")
+}
+
+/// Quantized (or F16/BF16/F32) data along with its type and shape. The data is always valid
+/// for the type, as checked by `ggml_validate_row_data`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizedBuffer {
+    type_: ggml_type,
+    ne: [i64; GGML_MAX_DIMS as usize],
+    // Stored as words so the blocks are suitably aligned for GGML.
+    data: Vec<u64>,
+    nbytes: usize,
+}
+
+impl QuantizedBuffer {
+    /// `ne` must have passed [`check_blocks`].
+    fn zeroed(type_: ggml_type, ne: [i64; GGML_MAX_DIMS as usize]) -> Self {
+        let nrows = ne[1..].iter().product::<i64>() as usize;
+        let nbytes = unsafe { ggml_row_size(type_, ne[0]) } * nrows;
+        Self {
+            type_,
+            ne,
+            data: vec![0; nbytes.div_ceil(std::mem::size_of::<u64>())],
+            nbytes,
+        }
+    }
+
+    /// Copies already quantized data, i.e. a tensor loaded from a GGUF file. Fails with
+    /// [`Error::InvalidData`] if `ggml_validate_row_data` rejects it.
+    pub fn from_bytes(type_: ggml_type, shape: &[i64], data: &[u8]) -> Result<Self> {
+        let ne = check_shape(type_, shape)?;
+        let mut buffer = Self::zeroed(type_, ne);
+        if data.len() != buffer.nbytes {
+            return Err(Error::LengthMismatch {
+                expected: buffer.nbytes,
+                actual: data.len(),
+            });
+        }
+        buffer.data_mut().copy_from_slice(data);
+        buffer.validate()?;
+        Ok(buffer)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let data = self.data();
+        if unsafe { ggml_validate_row_data(self.type_, data.as_ptr().cast(), data.len()) } {
+            Ok(())
+        } else {
+            Err(Error::InvalidData(self.type_))
+        }
+    }
+
+    pub fn type_(&self) -> ggml_type {
+        self.type_
+    }
+
+    pub fn ne(&self) -> [i64; GGML_MAX_DIMS as usize] {
+        self.ne
+    }
+
+    /// Number of elements in a row (`ne[0]`).
+    pub fn n_per_row(&self) -> usize {
+        self.ne[0] as usize
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.ne[1..].iter().product::<i64>() as usize
+    }
+
+    pub fn nelements(&self) -> usize {
+        self.n_per_row() * self.nrows()
+    }
+
+    /// Size of a row in bytes (`ggml_row_size`).
+    pub fn row_size(&self) -> usize {
+        unsafe { ggml_row_size(self.type_, self.ne[0]) }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr().cast(), self.nbytes) }
+    }
+
+    fn data_mut(&mut self) -> &mut [u8] {
+        unsafe { std::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.nbytes) }
+    }
+
+    /// The quantized data of row `i`. Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> &[u8] {
+        let row_size = self.row_size();
+        &self.data()[i * row_size..(i + 1) * row_size]
+    }
+}
+
+/// Checks that `type_` can be quantized to and `shape` has whole blocks along `ne[0]`.
+fn check_shape(type_: ggml_type, shape: &[i64]) -> Result<[i64; GGML_MAX_DIMS as usize]> {
+    if !can_quantize(type_) {
+        return Err(Error::UnsupportedType(type_));
+    }
+    check_blocks(type_, shape)
+}
+
+/// Checks that `shape` has up to `GGML_MAX_DIMS` dimensions and whole blocks along `ne[0]`,
+/// and that its number of elements and size in bytes fit, so they can be computed without
+/// checking afterwards.
+pub(crate) fn check_blocks(
+    type_: ggml_type,
+    shape: &[i64],
+) -> Result<[i64; GGML_MAX_DIMS as usize]> {
+    let invalid = || Error::InvalidShape {
+        type_,
+        shape: shape.to_vec(),
+    };
+    let blck_size = unsafe { ggml_blck_size(type_) } as i64;
+    if shape.is_empty()
+        || shape.len() > GGML_MAX_DIMS as usize
+        || shape.iter().any(|ne| *ne < 0)
+        || blck_size == 0
+        || shape[0] % blck_size != 0
+    {
+        return Err(invalid());
+    }
+    let nelements = shape.iter().try_fold(1i64, |n, ne| n.checked_mul(*ne));
+    let nrows = shape[1..].iter().try_fold(1i64, |n, ne| n.checked_mul(*ne));
+    // `ggml_row_size` multiplies `ne[0]` by the type size before dividing by the block size.
+    let nbytes = (shape[0] as usize)
+        .checked_mul(unsafe { ggml_type_size(type_) })
+        .zip(nrows)
+        .and_then(|(row, nrows)| (row / blck_size as usize).checked_mul(nrows as usize));
+    if nelements.is_none() || nbytes.map_or(true, |nbytes| nbytes > isize::MAX as usize) {
+        return Err(invalid());
+    }
+    Ok(full_ne(shape))
+}
+
+/// Calls `f` with ranges of rows and the matching part of `dst` from as many threads as
+/// there are CPUs (or rows, if there are fewer).
+pub(crate) fn par_rows<T: Send>(
+    dst: &mut [T],
+    row_len: usize,
+    f: impl Fn(Range<usize>, &mut [T]) + Sync,
+) {
+    let nrows = dst.len().checked_div(row_len).unwrap_or(0);
+    let n_threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(nrows);
+    if n_threads <= 1 {
+        return f(0..nrows, dst);
+    }
+    let per_thread = nrows.div_ceil(n_threads);
+    thread::scope(|s| {
+        for (i, chunk) in dst.chunks_mut(per_thread * row_len).enumerate() {
+            let f = &f;
+            let start = i * per_thread;
+            s.spawn(move || f(start..start + chunk.len() / row_len, chunk));
+        }
+    });
+}
+
+/// Quantizes `src` with `ggml_quantize_chunk`. `shape` is in GGML order, `src` must have as
+/// many elements and `imatrix`, if any, must have one weight per element of a row.
+///
+/// Types for which `ggml_quantize_requires_imatrix` is true fail with
+/// [`Error::MissingImatrix`] if `imatrix` is `None`, the others use it when given.
+pub fn quantize(
+    src: &[f32],
+    shape: &[i64],
+    type_: ggml_type,
+    imatrix: Option<&[f32]>,
+) -> Result<QuantizedBuffer> {
+    let ne = check_shape(type_, shape)?;
+    let n_per_row = ne[0] as usize;
+    let mut buffer = QuantizedBuffer::zeroed(type_, ne);
+    if src.len() != buffer.nelements() {
+        return Err(Error::LengthMismatch {
+            expected: buffer.nelements(),
+            actual: src.len(),
+        });
+    }
+    match imatrix {
+        Some(imatrix) if imatrix.len() != n_per_row => {
+            return Err(Error::LengthMismatch {
+                expected: n_per_row,
+                actual: imatrix.len(),
+            })
+        }
+        None if unsafe { ggml_quantize_requires_imatrix(type_) } => {
+            return Err(Error::MissingImatrix(type_))
+        }
+        _ => {}
+    }
+
+    context::init_tables();
+    unsafe { ggml_quantize_init(type_) };
+    let row_size = buffer.row_size();
+    par_rows(buffer.data_mut(), row_size, |rows, dst| {
+        let src = &src[rows.start * n_per_row..rows.end * n_per_row];
+        let imatrix = imatrix.map_or(std::ptr::null(), <[f32]>::as_ptr);
+        // Each thread passes its own part of `src` and `dst`, so the chunk starts at 0.
+        unsafe {
+            ggml_quantize_chunk(
+                type_,
+                src.as_ptr(),
+                dst.as_mut_ptr().cast(),
+                0,
+                rows.len() as i64,
+                n_per_row as i64,
+                imatrix,
+            )
+        };
+    });
+    buffer.validate()?;
+    Ok(buffer)
+}
+
+/// Converts `buffer` back to `f32` with the type's `to_float`.
+pub fn dequantize(buffer: &QuantizedBuffer) -> Vec<f32> {
+    let n_per_row = buffer.n_per_row();
+    let mut dst = vec![0.0; buffer.nelements()];
+    if buffer.type_ == ggml_type_GGML_TYPE_F32 {
+        let data = unsafe { std::slice::from_raw_parts(buffer.data.as_ptr().cast(), dst.len()) };
+        dst.copy_from_slice(data);
+        return dst;
+    }
+
+    context::init_tables();
+    let to_float = type_traits(buffer.type_)
+        .and_then(|traits| traits.to_float)
+        .expect("Quantize type without to_float");
+    let row_size = buffer.row_size();
+    par_rows(&mut dst, n_per_row, |rows, dst| {
+        let src = &buffer.data()[rows.start * row_size..rows.end * row_size];
+        unsafe { to_float(src.as_ptr().cast(), dst.as_mut_ptr(), dst.len() as i64) };
+    });
+    dst
+}
@@ -0,0 +1,1497 @@
+//! Shape validation for the operations in `ops.rs`. These mirror the `GGML_ASSERT`s in the
+//! `ggml_*` graph building functions (and the type restrictions their CPU implementations
+//! assert on at compute time) so invalid graphs are reported as errors instead of aborting.
+
+use std::fmt;
+
+use crate::{tensor::Tensor, *};
+
+type Ne = [i64; GGML_MAX_DIMS as usize];
+
+/// An operation was passed operands it can't handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeError {
+    /// The `ggml_*` function that would have failed.
+    pub op: &'static str,
+    pub kind: ShapeErrorKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeErrorKind {
+    /// `src` can't be broadcast (repeated) to the shape of `dst`.
+    Broadcast { src: Ne, dst: Ne },
+    /// A dimension of operand `a` has to match one of operand `b`.
+    DimMismatch {
+        a: usize,
+        a_dim: usize,
+        a_ne: i64,
+        b: usize,
+        b_dim: usize,
+        b_ne: i64,
+    },
+    /// The number of elements doesn't match.
+    ElementCount { expected: i64, actual: i64 },
+    /// The operand has to be contiguous (or at least have contiguous rows).
+    NotContiguous {
+        operand: usize,
+        ne: Ne,
+        nb: [usize; 4],
+    },
+    /// The operand can't be transposed.
+    Transposed { operand: usize },
+    /// The operand has an unsupported type.
+    Type { operand: usize, type_: ggml_type },
+    /// The operand must have (at most) the given number of dimensions.
+    Rank {
+        operand: usize,
+        expected: usize,
+        ne: Ne,
+    },
+    /// A view would extend past the end of the tensor it's a view of.
+    ViewOutOfBounds { end: usize, nbytes: usize },
+    /// Some other argument is invalid.
+    Argument(String),
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.op)?;
+        match &self.kind {
+            ShapeErrorKind::Broadcast { src, dst } => {
+                write!(f, "can't broadcast shape {src:?} to {dst:?}")
+            }
+            ShapeErrorKind::DimMismatch {
+                a,
+                a_dim,
+                a_ne,
+                b,
+                b_dim,
+                b_ne,
+            } => write!(
+                f,
+                "operand {a} ne[{a_dim}] = {a_ne} doesn't match operand {b} ne[{b_dim}] = {b_ne}"
+            ),
+            ShapeErrorKind::ElementCount { expected, actual } => {
+                write!(f, "expected {expected} elements, got {actual}")
+            }
+            ShapeErrorKind::NotContiguous { operand, ne, nb } => write!(
+                f,
+                "operand {operand} (ne {ne:?}, nb {nb:?}) must be contiguous"
+            ),
+            ShapeErrorKind::Transposed { operand } => {
+                write!(f, "operand {operand} must not be transposed")
+            }
+            ShapeErrorKind::Type { operand, type_ } => {
+                write!(f, "operand {operand} has unsupported type {type_}")
+            }
+            ShapeErrorKind::Rank {
+                operand,
+                expected,
+                ne,
+            } => write!(
+                f,
+                "operand {operand} (ne {ne:?}) must have at most {expected} dimensions"
+            ),
+            ShapeErrorKind::ViewOutOfBounds { end, nbytes } => {
+                write!(f, "view ends at byte {end} of a {nbytes} byte tensor")
+            }
+            ShapeErrorKind::Argument(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// Type and shape of an operation's result, used to make sure the context has room for it.
+pub(crate) struct Output {
+    pub type_: ggml_type,
+    pub ne: Ne,
+    pub view: bool,
+    /// Tensors created before the result by operations built from several others (i.e. the
+    /// `im2col` and `mul_mat` of a convolution).
+    pub intermediates: Vec<Output>,
+}
+
+impl Output {
+    /// `_inplace` operations return a view of their first operand.
+    pub fn new(op: &str, type_: ggml_type, ne: Ne) -> Self {
+        Self {
+            type_,
+            ne,
+            view: op.ends_with("_inplace"),
+            intermediates: Vec::new(),
+        }
+    }
+
+    pub fn like(op: &str, t: Tensor) -> Self {
+        Self::new(op, t.type_(), t.ne())
+    }
+
+    pub fn view(type_: ggml_type, ne: Ne) -> Self {
+        Self {
+            type_,
+            ne,
+            view: true,
+            intermediates: Vec::new(),
+        }
+    }
+
+    pub fn after(mut self, intermediates: impl IntoIterator<Item = Output>) -> Self {
+        self.intermediates.extend(intermediates);
+        self
+    }
+}
+
+pub(crate) type Check = std::result::Result<Output, ShapeError>;
+
+/// Helper for building errors for one operation.
+#[derive(Clone, Copy)]
+pub(crate) struct Checker(pub &'static str);
+
+impl Checker {
+    pub fn err<T>(self, kind: ShapeErrorKind) -> std::result::Result<T, ShapeError> {
+        Err(ShapeError { op: self.0, kind })
+    }
+
+    pub fn types(self, operand: usize, t: Tensor, types: &[ggml_type]) -> Result<(), ShapeError> {
+        if types.contains(&t.type_()) {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::Type {
+            operand,
+            type_: t.type_(),
+        })
+    }
+
+    pub fn f32(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        self.types(operand, t, &[ggml_type_GGML_TYPE_F32])
+    }
+
+    pub fn contiguous(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        if t.is_contiguous() {
+            return Ok(());
+        }
+        self.not_contiguous(operand, t)
+    }
+
+    /// Rows have to be contiguous, but not necessarily the tensor (`ggml_is_contiguous_1`).
+    pub fn contiguous_rows(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        if unsafe { ggml_is_contiguous_1(t.as_ptr()) } {
+            return Ok(());
+        }
+        self.not_contiguous(operand, t)
+    }
+
+    /// Dimensions after the first are laid out contiguously (`ggml_is_padded_1d`).
+    pub fn padded_1d(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        let (ne, nb) = (t.ne(), t.nb());
+        if nb[1] == nb[0] * ne[0] as usize
+            && nb[2] == nb[1] * ne[1] as usize
+            && nb[3] == nb[2] * ne[2] as usize
+        {
+            return Ok(());
+        }
+        self.not_contiguous(operand, t)
+    }
+
+    fn not_contiguous<T>(self, operand: usize, t: Tensor) -> Result<T, ShapeError> {
+        self.err(ShapeErrorKind::NotContiguous {
+            operand,
+            ne: t.ne(),
+            nb: t.nb(),
+        })
+    }
+
+    /// `src` can be repeated to fill `dst` (`ggml_can_repeat`).
+    pub fn can_repeat(self, src: Tensor, dst: Tensor) -> Result<(), ShapeError> {
+        let (sne, dne) = (src.ne(), dst.ne());
+        let ok = if sne.contains(&0) {
+            dne.contains(&0)
+        } else {
+            sne.iter().zip(dne).all(|(s, d)| d % s == 0)
+        };
+        if ok {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::Broadcast { src: sne, dst: dne })
+    }
+
+    /// `a.ne[a_dim]` must equal `b.ne[b_dim]`.
+    pub fn dims_eq(
+        self,
+        (a, a_dim, at): (usize, usize, Tensor),
+        (b, b_dim, bt): (usize, usize, Tensor),
+    ) -> Result<(), ShapeError> {
+        let (a_ne, b_ne) = (at.ne()[a_dim], bt.ne()[b_dim]);
+        if a_ne == b_ne {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::DimMismatch {
+            a,
+            a_dim,
+            a_ne,
+            b,
+            b_dim,
+            b_ne,
+        })
+    }
+
+    pub fn max_rank(self, operand: usize, t: Tensor, expected: usize) -> Result<(), ShapeError> {
+        if t.ne()[expected..].iter().all(|ne| *ne == 1) {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::Rank {
+            operand,
+            expected,
+            ne: t.ne(),
+        })
+    }
+
+    pub fn nelements(self, expected: i64, actual: i64) -> Result<(), ShapeError> {
+        if expected == actual {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::ElementCount { expected, actual })
+    }
+
+    pub fn arg(self, ok: bool, msg: impl FnOnce() -> String) -> Result<(), ShapeError> {
+        if ok {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::Argument(msg()))
+    }
+
+    /// The number of elements of a requested shape, which mustn't be negative or overflow.
+    pub fn shape_nelements(self, shape: &[i64]) -> Result<i64, ShapeError> {
+        let n = shape.iter().try_fold(1i64, |n, ne| {
+            if *ne < 0 {
+                return None;
+            }
+            n.checked_mul(*ne)
+        });
+        match n {
+            Some(n) => Ok(n),
+            None => self.err(ShapeErrorKind::Argument(format!("invalid shape {shape:?}"))),
+        }
+    }
+}
+
+/// Pads a shape with ones up to `GGML_MAX_DIMS`.
+pub(crate) fn full_ne(shape: &[i64]) -> Ne {
+    let mut ne = [1; GGML_MAX_DIMS as usize];
+    ne[..shape.len()].copy_from_slice(shape);
+    ne
+}
+
+/// Element-wise operations on F32 tensors without any other requirements.
+pub(crate) fn elementwise_f32(op: &'static str, a: Tensor) -> Check {
+    Checker(op).f32(0, a)?;
+    Ok(Output::like(op, a))
+}
+
+/// Operations implemented with `ggml_unary`.
+pub(crate) fn unary(op: &'static str, a: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous_rows(0, a)?;
+    Ok(Output::like(op, a))
+}
+
+/// `ggml_add` and friends: `b` is broadcast to the shape of `a`.
+pub(crate) fn broadcast(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.can_repeat(b, a)?;
+    // Only addition supports anything other than F32, and only the F32 kernel broadcasts.
+    if !op.starts_with("ggml_add") || a.type_() == ggml_type_GGML_TYPE_F32 {
+        c.f32(0, a)?;
+        c.f32(1, b)?;
+        return Ok(Output::like(op, a));
+    }
+    let (a_type, b_type) = (a.type_(), b.type_());
+    let ok = if a_type == ggml_type_GGML_TYPE_F16 || a_type == ggml_type_GGML_TYPE_BF16 {
+        b_type == a_type || b_type == ggml_type_GGML_TYPE_F32
+    } else if unsafe { ggml_is_quantized(a_type) } {
+        // The sum is quantized again.
+        let traits = unsafe { ggml_internal_get_type_traits(a_type) };
+        b_type == ggml_type_GGML_TYPE_F32 && traits.from_float.is_some()
+    } else {
+        false
+    };
+    c.arg(ok, || format!("can't add type {b_type} to type {a_type}"))?;
+    c.same_shape((0, a), (1, b))?;
+    c.unit_stride(0, a)?;
+    c.unit_stride(1, b)?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn dup(op: &'static str, a: Tensor) -> Check {
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn sum(op: &'static str, a: Tensor) -> Check {
+    Checker(op).f32(0, a)?;
+    Ok(Output::new(op, a.type_(), [1; 4]))
+}
+
+pub(crate) fn reduce_rows(op: &'static str, a: Tensor) -> Check {
+    Checker(op).f32(0, a)?;
+    let [_, ne1, ne2, ne3] = a.ne();
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, [1, ne1, ne2, ne3]))
+}
+
+pub(crate) fn argmax(op: &'static str, a: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.max_rank(0, a, 2)?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_I32,
+        [a.ne()[1], 1, 1, 1],
+    ))
+}
+
+pub(crate) fn softmax(op: &'static str, a: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn scale(op: &'static str, a: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.padded_1d(0, a)?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn transpose(_op: &'static str, a: Tensor) -> Check {
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    Ok(Output::view(a.type_(), [ne1, ne0, ne2, ne3]))
+}
+
+pub(crate) fn add1(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.max_rank(1, b, 0)?;
+    c.padded_1d(0, a)?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn repeat(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    Checker(op).can_repeat(a, b)?;
+    Ok(Output::new(op, a.type_(), b.ne()))
+}
+
+pub(crate) fn get_rows(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.types(1, b, &[ggml_type_GGML_TYPE_I32])?;
+    c.dims_eq((0, 2, a), (1, 1, b))?;
+    c.max_rank(1, b, 3)?;
+    let type_ = if a.type_() == ggml_type_GGML_TYPE_I32 {
+        ggml_type_GGML_TYPE_I32
+    } else {
+        ggml_type_GGML_TYPE_F32
+    };
+    let bne = b.ne();
+    Ok(Output::new(op, type_, [a.ne()[0], bne[0], bne[1], bne[2]]))
+}
+
+pub(crate) fn mul_mat(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.arg(is_matmul_type(a.type_()), || {
+        format!("operand 0 has unsupported type {}", a.type_())
+    })?;
+    // `b` is converted to the type `a` is multiplied with, which is only implemented from F32.
+    let vec_dot_type = unsafe { ggml_internal_get_type_traits(a.type_()) }.vec_dot_type;
+    c.types(1, b, &[ggml_type_GGML_TYPE_F32, vec_dot_type])?;
+    c.dims_eq((0, 0, a), (1, 0, b))?;
+    c.can_repeat_dims(a, b, 2..4)?;
+    if unsafe { ggml_is_transposed(a.as_ptr()) } {
+        return c.err(ShapeErrorKind::Transposed { operand: 0 });
+    }
+    let (ane, bne) = (a.ne(), b.ne());
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [ane[1], bne[1], bne[2], bne[3]],
+    ))
+}
+
+impl Checker {
+    /// Like [`Checker::can_repeat`] but only for some dimensions.
+    fn can_repeat_dims(
+        self,
+        src: Tensor,
+        dst: Tensor,
+        dims: std::ops::Range<usize>,
+    ) -> Result<(), ShapeError> {
+        let (sne, dne) = (src.ne(), dst.ne());
+        if dims
+            .into_iter()
+            .all(|d| sne[d] != 0 && dne[d] % sne[d] == 0)
+        {
+            return Ok(());
+        }
+        self.err(ShapeErrorKind::Broadcast { src: sne, dst: dne })
+    }
+}
+
+pub(crate) fn concat(op: &'static str, a: Tensor, b: Tensor, dim: usize) -> Check {
+    let c = Checker(op);
+    c.arg(dim < GGML_MAX_DIMS as usize, || {
+        format!("dimension {dim} out of range")
+    })?;
+    c.types(1, b, &[a.type_()])?;
+    for d in (0..GGML_MAX_DIMS as usize).filter(|d| *d != dim) {
+        c.dims_eq((0, d, a), (1, d, b))?;
+    }
+    let mut ne = a.ne();
+    ne[dim] += b.ne()[dim];
+    Ok(Output::new(op, a.type_(), ne))
+}
+
+pub(crate) fn softmax_ext(
+    op: &'static str,
+    a: Tensor,
+    mask: Option<Tensor>,
+    max_bias: f32,
+) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    if let Some(mask) = mask {
+        c.types(1, mask, &[ggml_type_GGML_TYPE_F16, ggml_type_GGML_TYPE_F32])?;
+        c.contiguous(1, mask)?;
+        c.max_rank(1, mask, 2)?;
+        c.dims_eq((1, 0, mask), (0, 0, a))?;
+        c.arg(mask.ne()[1] >= a.ne()[1], || {
+            format!(
+                "mask ne[1] = {} is smaller than ne[1] = {}",
+                mask.ne()[1],
+                a.ne()[1]
+            )
+        })?;
+    } else {
+        c.arg(max_bias <= 0.0, || String::from("max_bias requires a mask"))?;
+    }
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn rope_ext(
+    op: &'static str,
+    a: Tensor,
+    pos: Tensor,
+    freq_factors: Option<Tensor>,
+    n_dims: i32,
+) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16])?;
+    c.types(1, pos, &[ggml_type_GGML_TYPE_I32])?;
+    c.max_rank(1, pos, 1)?;
+    c.dims_eq((0, 2, a), (1, 0, pos))?;
+    c.arg(n_dims >= 0 && n_dims as i64 <= a.ne()[0], || {
+        format!("n_dims {n_dims} out of range for ne[0] = {}", a.ne()[0])
+    })?;
+    if let Some(ff) = freq_factors {
+        c.f32(2, ff)?;
+        c.arg(ff.ne()[0] >= n_dims as i64 / 2, || {
+            format!(
+                "freq_factors has {} elements, needs {}",
+                ff.ne()[0],
+                n_dims / 2
+            )
+        })?;
+    }
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn reshape(op: &'static str, a: Tensor, shape: &[i64]) -> Check {
+    let c = Checker(op);
+    c.arg((1..=GGML_MAX_DIMS as usize).contains(&shape.len()), || {
+        format!("can't reshape to {} dimensions", shape.len())
+    })?;
+    c.contiguous(0, a)?;
+    c.nelements(a.nelements(), c.shape_nelements(shape)?)?;
+    Ok(Output::view(a.type_(), full_ne(shape)))
+}
+
+pub(crate) fn permute(op: &'static str, a: Tensor, axes: [usize; 4]) -> Check {
+    let c = Checker(op);
+    let mut seen = [false; 4];
+    for axis in axes {
+        c.arg(axis < 4 && !seen[axis], || format!("invalid axes {axes:?}"))?;
+        seen[axis] = true;
+    }
+    let mut ne = [0; 4];
+    for (src, dst) in axes.into_iter().enumerate() {
+        ne[dst] = a.ne()[src];
+    }
+    Ok(Output::view(a.type_(), ne))
+}
+
+/// The end of a view's data in bytes, `None` if that overflows. GGML itself only checks the
+/// size of the view as if it were contiguous, but with arbitrary strides the last element also
+/// has to be in bounds.
+fn view_end(type_: ggml_type, shape: &[i64], nb: &[usize], offset: usize) -> Option<usize> {
+    let blocks = usize::try_from(shape[0] / unsafe { ggml_blck_size(type_) } as i64).ok()?;
+    let row_size = blocks.checked_mul(unsafe { ggml_type_size(type_) })?;
+    let dense_end = shape[1..]
+        .iter()
+        .try_fold(row_size, |acc, ne| acc.checked_mul(*ne as usize))?;
+    let strided_end = shape[1..]
+        .iter()
+        .zip(nb)
+        .try_fold(row_size, |acc, (ne, nb)| {
+            (*ne as usize - 1).checked_mul(*nb)?.checked_add(acc)
+        })?;
+    offset.checked_add(dense_end.max(strided_end))
+}
+
+pub(crate) fn view(
+    op: &'static str,
+    a: Tensor,
+    shape: &[i64],
+    nb: &[usize],
+    offset: usize,
+) -> Check {
+    let c = Checker(op);
+    c.arg(
+        (1..=GGML_MAX_DIMS as usize).contains(&shape.len()) && nb.len() + 1 == shape.len(),
+        || format!("{} dimensions with {} strides", shape.len(), nb.len()),
+    )?;
+    c.arg(shape.iter().all(|ne| *ne > 0), || {
+        format!("invalid view shape {shape:?}")
+    })?;
+    let blck_size = unsafe { ggml_blck_size(a.type_()) } as i64;
+    c.arg(shape[0] % blck_size == 0, || {
+        format!("ne[0] = {} isn't a multiple of the block size", shape[0])
+    })?;
+    match view_end(a.type_(), shape, nb, offset) {
+        Some(end) if end <= a.nbytes() => Ok(Output::view(a.type_(), full_ne(shape))),
+        end => c.err(ShapeErrorKind::ViewOutOfBounds {
+            end: end.unwrap_or(usize::MAX),
+            nbytes: a.nbytes(),
+        }),
+    }
+}
+
+impl Checker {
+    /// Elements are next to each other (`nb[0]` is the type size), which the CPU
+    /// implementations of most operations assert on.
+    fn unit_stride(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        if t.nb()[0] == unsafe { ggml_type_size(t.type_()) } {
+            return Ok(());
+        }
+        self.not_contiguous(operand, t)
+    }
+
+    fn same_shape(
+        self,
+        (a, at): (usize, Tensor),
+        (b, bt): (usize, Tensor),
+    ) -> Result<(), ShapeError> {
+        (0..GGML_MAX_DIMS as usize).try_for_each(|d| self.dims_eq((a, d, at), (b, d, bt)))
+    }
+
+    fn not_transposed(self, operand: usize, t: Tensor) -> Result<(), ShapeError> {
+        if unsafe { ggml_is_transposed(t.as_ptr()) } {
+            return self.err(ShapeErrorKind::Transposed { operand });
+        }
+        Ok(())
+    }
+
+    fn positive(self, name: &str, val: i64) -> Result<(), ShapeError> {
+        self.arg(val > 0, || format!("{name} = {val} must be positive"))
+    }
+}
+
+const FLOAT_TYPES: [ggml_type; 3] = [
+    ggml_type_GGML_TYPE_F32,
+    ggml_type_GGML_TYPE_F16,
+    ggml_type_GGML_TYPE_BF16,
+];
+
+/// Types GGML can multiply matrices of, that is everything but the integer types.
+fn is_matmul_type(type_: ggml_type) -> bool {
+    FLOAT_TYPES.contains(&type_) || unsafe { ggml_is_quantized(type_) }
+}
+
+pub(crate) fn leaky_relu(op: &'static str, a: Tensor, inplace: bool) -> Check {
+    let out = unary(op, a)?;
+    Ok(Output {
+        view: inplace,
+        ..out
+    })
+}
+
+pub(crate) fn clamp(op: &'static str, a: Tensor, min: f32, max: f32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.unit_stride(0, a)?;
+    c.arg(min <= max, || format!("min {min} is larger than max {max}"))?;
+    // The result is always a view of `a`.
+    Ok(Output::view(a.type_(), a.ne()))
+}
+
+pub(crate) fn group_norm(op: &'static str, a: Tensor, n_groups: i32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.unit_stride(0, a)?;
+    c.positive("n_groups", n_groups.into())?;
+    Ok(Output::like(op, a))
+}
+
+/// `ggml_silu_back` and `ggml_rms_norm_back`: the input and the gradient of the output.
+pub(crate) fn unary_back(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.f32(1, b)?;
+    c.same_shape((0, a), (1, b))?;
+    c.contiguous_rows(0, a)?;
+    c.contiguous_rows(1, b)?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn softmax_back(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.f32(1, b)?;
+    c.same_shape((0, a), (1, b))?;
+    c.contiguous(0, a)?;
+    c.contiguous(1, b)?;
+    Ok(Output::like(op, a))
+}
+
+/// Sums `a` into the shape of `b`, the reverse of `ggml_repeat`.
+pub(crate) fn repeat_back(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.can_repeat(b, a)?;
+    c.f32(0, a)?;
+    c.contiguous_rows(0, a)?;
+    Ok(Output::new(op, a.type_(), b.ne()))
+}
+
+pub(crate) fn rope_back(op: &'static str, a: Tensor, pos: Tensor, n_dims: i32) -> Check {
+    rope_ext(op, a, pos, None, n_dims)
+}
+
+pub(crate) fn cross_entropy_loss(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.f32(1, b)?;
+    c.same_shape((0, a), (1, b))?;
+    c.contiguous(0, a)?;
+    c.contiguous(1, b)?;
+    Ok(Output::new(op, a.type_(), [1; 4]))
+}
+
+/// `a` and `b` are the operands of `ggml_cross_entropy_loss`, `c` the gradient of the loss.
+pub(crate) fn cross_entropy_loss_back(op: &'static str, a: Tensor, b: Tensor, c_: Tensor) -> Check {
+    let c = Checker(op);
+    cross_entropy_loss(op, a, b)?;
+    c.f32(2, c_)?;
+    c.nelements(1, c_.nelements())?;
+    Ok(Output::like(op, a))
+}
+
+pub(crate) fn add_cast(op: &'static str, a: Tensor, b: Tensor, type_: ggml_type) -> Check {
+    let c = Checker(op);
+    c.arg(
+        a.type_() == ggml_type_GGML_TYPE_F16 || unsafe { ggml_is_quantized(a.type_()) },
+        || String::from("only F16 and quantized tensors can be added to with a cast"),
+    )?;
+    c.f32(1, b)?;
+    // Unlike `ggml_add`, the CPU implementation can't broadcast.
+    c.same_shape((0, a), (1, b))?;
+    c.unit_stride(0, a)?;
+    c.unit_stride(1, b)?;
+    c.arg(
+        [ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16, a.type_()].contains(&type_),
+        || format!("can't add a type {} tensor into type {type_}", a.type_()),
+    )?;
+    Ok(Output::new(op, type_, a.ne()))
+}
+
+pub(crate) fn out_prod(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.arg(
+        a.type_() == ggml_type_GGML_TYPE_F32 || unsafe { ggml_is_quantized(a.type_()) },
+        || format!("operand 0 has unsupported type {}", a.type_()),
+    )?;
+    c.f32(1, b)?;
+    c.not_transposed(0, a)?;
+    c.dims_eq((0, 1, a), (1, 1, b))?;
+    c.dims_eq((0, 2, a), (1, 2, b))?;
+    c.dims_eq((0, 3, a), (1, 3, b))?;
+    c.unit_stride(0, a)?;
+    c.unit_stride(1, b)?;
+    let (ane, bne) = (a.ne(), b.ne());
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [ane[0], bne[0], bne[2], bne[3]],
+    ))
+}
+
+/// `ggml_mul_mat_id`: `as_` is a stack of matrices along `ne[2]`, `ids` picks one of them for
+/// each row of `b`.
+pub(crate) fn mul_mat_id(op: &'static str, as_: Tensor, b: Tensor, ids: Tensor) -> Check {
+    let c = Checker(op);
+    c.arg(is_matmul_type(as_.type_()), || {
+        format!("operand 0 has unsupported type {}", as_.type_())
+    })?;
+    c.f32(1, b)?;
+    c.types(2, ids, &[ggml_type_GGML_TYPE_I32])?;
+    c.not_transposed(0, as_)?;
+    c.max_rank(0, as_, 3)?;
+    c.max_rank(1, b, 3)?;
+    c.max_rank(2, ids, 2)?;
+    c.dims_eq((0, 0, as_), (1, 0, b))?;
+    c.dims_eq((2, 1, ids), (1, 2, b))?;
+    let (ids_ne0, b_ne1) = (ids.ne()[0], b.ne()[1]);
+    c.arg(b_ne1 > 0 && ids_ne0 % b_ne1 == 0, || {
+        format!("ids ne[0] = {ids_ne0} isn't a multiple of ne[1] = {b_ne1}")
+    })?;
+    c.unit_stride(0, as_)?;
+    c.unit_stride(1, b)?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [as_.ne()[1], ids_ne0, b.ne()[2], 1],
+    ))
+}
+
+/// `ggml_set` and `ggml_acc`: `b` is written to (or added to) the view of `a` with strides
+/// `nb` starting at `offset`.
+pub(crate) fn set(op: &'static str, a: Tensor, b: Tensor, nb: [usize; 3], offset: usize) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.f32(1, b)?;
+    c.contiguous(0, a)?;
+    c.unit_stride(1, b)?;
+    c.arg(b.nelements() <= a.nelements(), || {
+        format!(
+            "{} elements don't fit in {} elements",
+            b.nelements(),
+            a.nelements()
+        )
+    })?;
+    if b.nelements() > 0 {
+        match view_end(a.type_(), &b.ne(), &nb, offset) {
+            Some(end) if end <= a.nbytes() => {}
+            end => {
+                return c.err(ShapeErrorKind::ViewOutOfBounds {
+                    end: end.unwrap_or(usize::MAX),
+                    nbytes: a.nbytes(),
+                })
+            }
+        }
+    }
+    Ok(Output::like(op, a))
+}
+
+/// Whether the CPU implementation of `ggml_cpy` can convert between the types of `src` and
+/// `dst`.
+fn check_copy(c: Checker, src: Tensor, dst_type: ggml_type) -> Result<(), ShapeError> {
+    let src_type = src.type_();
+    let quantized = |type_| unsafe { ggml_is_quantized(type_) };
+    let ok = if src_type == dst_type {
+        // Quantized tensors are only copied as whole blocks.
+        !quantized(src_type) || src.is_contiguous()
+    } else if quantized(dst_type) {
+        let traits = unsafe { ggml_internal_get_type_traits(dst_type) };
+        src_type == ggml_type_GGML_TYPE_F32 && traits.from_float.is_some()
+    } else {
+        // F16 and BF16 are only converted to and from F32.
+        FLOAT_TYPES.contains(&src_type)
+            && FLOAT_TYPES.contains(&dst_type)
+            && (src_type == ggml_type_GGML_TYPE_F32 || dst_type == ggml_type_GGML_TYPE_F32)
+    };
+    c.arg(ok, || {
+        format!("can't convert type {src_type} to type {dst_type}")
+    })?;
+    if quantized(dst_type) && src_type != dst_type {
+        c.unit_stride(0, src)?;
+    }
+    Ok(())
+}
+
+/// `ggml_cpy`: the result is a view of `b`.
+pub(crate) fn cpy(op: &'static str, a: Tensor, b: Tensor) -> Check {
+    let c = Checker(op);
+    c.nelements(a.nelements(), b.nelements())?;
+    check_copy(c, a, b.type_())?;
+    if unsafe { ggml_is_quantized(b.type_()) } && a.type_() != b.type_() {
+        c.contiguous(1, b)?;
+    }
+    Ok(Output::view(b.type_(), b.ne()))
+}
+
+pub(crate) fn cast(op: &'static str, a: Tensor, type_: ggml_type) -> Check {
+    let c = Checker(op);
+    c.arg(type_ < ggml_type_GGML_TYPE_COUNT, || {
+        format!("invalid type {type_}")
+    })?;
+    check_copy(c, a, type_)?;
+    Ok(Output::new(op, type_, a.ne()))
+}
+
+/// `ggml_cont_1d` through `ggml_cont_4d`.
+pub(crate) fn cont_shape(op: &'static str, a: Tensor, shape: &[i64]) -> Check {
+    let c = Checker(op);
+    c.arg((1..=GGML_MAX_DIMS as usize).contains(&shape.len()), || {
+        format!("can't reshape to {} dimensions", shape.len())
+    })?;
+    c.nelements(a.nelements(), c.shape_nelements(shape)?)?;
+    Ok(Output::new(op, a.type_(), full_ne(shape)))
+}
+
+pub(crate) fn diag(op: &'static str, a: Tensor) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.unit_stride(0, a)?;
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    c.arg(ne1 == 1, || format!("ne[1] = {ne1} must be 1"))?;
+    Ok(Output::new(op, a.type_(), [ne0, ne0, ne2, ne3]))
+}
+
+pub(crate) fn diag_mask(op: &'static str, a: Tensor, n_past: i32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    c.arg(n_past >= 0, || format!("n_past = {n_past} is negative"))?;
+    Ok(Output::like(op, a))
+}
+
+/// Size of a convolution's output along one dimension, computed the same way as GGML
+/// (including C's rounding towards zero).
+fn conv_output_size(ins: i64, ks: i64, s: i32, p: i32, d: i32) -> i64 {
+    (ins + 2 * p as i64 - d as i64 * (ks - 1) - 1) / s as i64 + 1
+}
+
+/// `ggml_im2col`: `a` is the kernel, only its shape is used. 2D kernels are
+/// `[KW, KH, IC, OC]` and inputs `[W, H, IC, N]`, 1D kernels `[K, IC, OC]` and inputs
+/// `[W, IC, N]`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn im2col(
+    op: &'static str,
+    a: Tensor,
+    b: Tensor,
+    [s0, s1]: [i32; 2],
+    [p0, p1]: [i32; 2],
+    [d0, d1]: [i32; 2],
+    is_2d: bool,
+    dst_type: ggml_type,
+) -> Check {
+    let c = Checker(op);
+    c.f32(1, b)?;
+    c.contiguous(1, b)?;
+    c.arg(
+        [ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16].contains(&dst_type),
+        || format!("invalid destination type {dst_type}"),
+    )?;
+    let ne = im2col_ne(c, a.ne(), b.ne(), [s0, s1], [p0, p1], [d0, d1], is_2d)?;
+    Ok(Output::new(op, dst_type, ne))
+}
+
+/// The shape checks of [`im2col`] for a kernel of shape `ane` and an input of shape `bne`,
+/// which may be reshapes of the operands (see [`conv_depthwise_2d`]).
+fn im2col_ne(
+    c: Checker,
+    ane: Ne,
+    bne: Ne,
+    [s0, s1]: [i32; 2],
+    [p0, p1]: [i32; 2],
+    [d0, d1]: [i32; 2],
+    is_2d: bool,
+) -> Result<Ne, ShapeError> {
+    c.positive("s0", s0.into())?;
+    c.positive("d0", d0.into())?;
+    c.arg(p0 >= 0, || format!("p0 = {p0} is negative"))?;
+    let ow = conv_output_size(bne[0], ane[0], s0, p0, d0);
+    c.positive("output width", ow)?;
+    let dim = if is_2d { 2 } else { 1 };
+    if ane[dim] != bne[dim] {
+        return c.err(ShapeErrorKind::DimMismatch {
+            a: 0,
+            a_dim: dim,
+            a_ne: ane[dim],
+            b: 1,
+            b_dim: dim,
+            b_ne: bne[dim],
+        });
+    }
+    if is_2d {
+        c.positive("s1", s1.into())?;
+        c.positive("d1", d1.into())?;
+        c.arg(p1 >= 0, || format!("p1 = {p1} is negative"))?;
+        let oh = conv_output_size(bne[1], ane[1], s1, p1, d1);
+        c.positive("output height", oh)?;
+        Ok([ane[2] * ane[1] * ane[0], ow, oh, bne[3]])
+    } else {
+        Ok([ane[1] * ane[0], ow, bne[2], 1])
+    }
+}
+
+/// `ggml_conv_1d`, which is an `im2col` followed by a `mul_mat` with the kernel.
+pub(crate) fn conv_1d(op: &'static str, a: Tensor, b: Tensor, s0: i32, p0: i32, d0: i32) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16])?;
+    c.contiguous(0, a)?;
+    let col = im2col(
+        op,
+        a,
+        b,
+        [s0, 0],
+        [p0, 0],
+        [d0, 0],
+        false,
+        ggml_type_GGML_TYPE_F16,
+    )?;
+    let [k, ow, n, _] = col.ne;
+    let prod = Output::new(op, ggml_type_GGML_TYPE_F32, [ow * n, a.ne()[2], 1, 1]);
+    Ok(
+        Output::view(ggml_type_GGML_TYPE_F32, [ow, a.ne()[2], n, 1]).after([
+            Output::view(col.type_, [k, ow * n, 1, 1]),
+            col,
+            Output::view(a.type_(), [a.ne()[0] * a.ne()[1], a.ne()[2], 1, 1]),
+            prod,
+        ]),
+    )
+}
+
+/// `ggml_conv_2d`, which is an `im2col` followed by a `mul_mat` with the kernel and a
+/// permutation to `[OW, OH, OC, N]`.
+pub(crate) fn conv_2d(
+    op: &'static str,
+    a: Tensor,
+    b: Tensor,
+    s: [i32; 2],
+    p: [i32; 2],
+    d: [i32; 2],
+) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16])?;
+    c.contiguous(0, a)?;
+    let col = im2col(op, a, b, s, p, d, true, a.type_())?;
+    let [k, ow, oh, n] = col.ne;
+    let oc = a.ne()[3];
+    let f32 = ggml_type_GGML_TYPE_F32;
+    let col_view = Output::view(col.type_, [k, ow * oh * n, 1, 1]);
+    Ok(Output::new(op, f32, [ow, oh, oc, n]).after([
+        col,
+        col_view,
+        Output::view(a.type_(), [a.ne()[0] * a.ne()[1] * a.ne()[2], oc, 1, 1]),
+        Output::new(op, f32, [ow * oh * n, oc, 1, 1]),
+        Output::view(f32, [ow, oh, n, oc]),
+        Output::view(f32, [ow, oh, oc, n]),
+    ]))
+}
+
+/// `ggml_conv_transpose_1d`, which GGML only implements without padding and dilation.
+pub(crate) fn conv_transpose_1d(op: &'static str, a: Tensor, b: Tensor, s0: i32) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16])?;
+    c.f32(1, b)?;
+    c.max_rank(0, a, 3)?;
+    c.max_rank(1, b, 2)?;
+    c.dims_eq((0, 2, a), (1, 1, b))?;
+    c.unit_stride(0, a)?;
+    c.unit_stride(1, b)?;
+    c.positive("s0", s0.into())?;
+    let (ane, bne) = (a.ne(), b.ne());
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [(bne[0] - 1) * s0 as i64 + ane[0], ane[1], bne[2], 1],
+    ))
+}
+
+pub(crate) fn conv_transpose_2d_p0(op: &'static str, a: Tensor, b: Tensor, stride: i32) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F16])?;
+    c.f32(1, b)?;
+    c.dims_eq((0, 3, a), (1, 2, b))?;
+    c.unit_stride(0, a)?;
+    c.unit_stride(1, b)?;
+    c.positive("stride", stride.into())?;
+    let (ane, bne) = (a.ne(), b.ne());
+    let s = stride as i64;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [
+            (bne[0] - 1) * s + ane[0],
+            (bne[1] - 1) * s + ane[1],
+            ane[2],
+            bne[3],
+        ],
+    ))
+}
+
+fn pool_op(c: Checker, op: ggml_op_pool) -> Result<(), ShapeError> {
+    c.arg(
+        [ggml_op_pool_GGML_OP_POOL_MAX, ggml_op_pool_GGML_OP_POOL_AVG].contains(&op),
+        || format!("invalid pooling op {op}"),
+    )
+}
+
+/// `ggml_pool_1d`, which GGML only implements for windows that don't overlap (`k0 == s0`)
+/// and without padding.
+pub(crate) fn pool_1d(op: &'static str, a: Tensor, pool: ggml_op_pool, k0: i32, s0: i32) -> Check {
+    let c = Checker(op);
+    pool_op(c, pool)?;
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    c.positive("k0", k0.into())?;
+    c.arg(k0 == s0, || {
+        format!("stride {s0} must equal the kernel size {k0}")
+    })?;
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    let ow = (ne0 - k0 as i64) / s0 as i64 + 1;
+    c.arg(ne0 >= k0 as i64, || {
+        format!("kernel size {k0} is larger than ne[0] = {ne0}")
+    })?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [ow, ne1, ne2, ne3],
+    ))
+}
+
+pub(crate) fn pool_2d(
+    op: &'static str,
+    a: Tensor,
+    pool: ggml_op_pool,
+    [k0, k1]: [i32; 2],
+    [s0, s1]: [i32; 2],
+    [p0, p1]: [i32; 2],
+) -> Check {
+    let c = Checker(op);
+    pool_op(c, pool)?;
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    for (name, val) in [("k0", k0), ("k1", k1), ("s0", s0), ("s1", s1)] {
+        c.positive(name, val.into())?;
+    }
+    c.arg(p0 >= 0 && p1 >= 0, || {
+        format!("padding ({p0}, {p1}) is negative")
+    })?;
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    let size = |ins: i64, k: i32, s: i32, p: i32| (ins + 2 * p as i64 - k as i64) / s as i64 + 1;
+    let (ow, oh) = (size(ne0, k0, s0, p0), size(ne1, k1, s1, p1));
+    c.positive("output width", ow)?;
+    c.positive("output height", oh)?;
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, [ow, oh, ne2, ne3]))
+}
+
+/// `ggml_upscale_ext` (and `ggml_upscale`, which scales the first two dimensions): the result
+/// can't be smaller than `a`.
+pub(crate) fn upscale(op: &'static str, a: Tensor, ne: [i64; 4]) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.arg(
+        a.ne()
+            .iter()
+            .zip(ne)
+            .all(|(a, ne)| *a <= ne && ne <= i32::MAX as i64),
+        || format!("can't upscale {:?} to {ne:?}", a.ne()),
+    )?;
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, ne))
+}
+
+pub(crate) fn pad(op: &'static str, a: Tensor, p: [i32; 4]) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.unit_stride(0, a)?;
+    c.arg(p.iter().all(|p| *p >= 0), || {
+        format!("padding {p:?} is negative")
+    })?;
+    let mut ne = a.ne();
+    for (ne, p) in ne.iter_mut().zip(p) {
+        *ne = ne.checked_add(p.into()).ok_or_else(|| ShapeError {
+            op,
+            kind: ShapeErrorKind::Argument(format!("padding {p:?} is too large")),
+        })?;
+    }
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, ne))
+}
+
+pub(crate) fn timestep_embedding(op: &'static str, a: Tensor, dim: i32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.unit_stride(0, a)?;
+    c.max_rank(0, a, 1)?;
+    c.positive("dim", dim.into())?;
+    let dim = dim as i64 + dim as i64 % 2;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [dim, a.ne()[0], 1, 1],
+    ))
+}
+
+pub(crate) fn argsort(op: &'static str, a: Tensor, order: ggml_sort_order) -> Check {
+    let c = Checker(op);
+    c.arg(
+        [
+            ggml_sort_order_GGML_SORT_ORDER_ASC,
+            ggml_sort_order_GGML_SORT_ORDER_DESC,
+        ]
+        .contains(&order),
+        || format!("invalid sort order {order}"),
+    )?;
+    c.f32(0, a)?;
+    c.contiguous_rows(0, a)?;
+    c.arg(a.ne()[0] <= i32::MAX as i64, || {
+        format!("ne[0] = {} is too large", a.ne()[0])
+    })?;
+    Ok(Output::new(op, ggml_type_GGML_TYPE_I32, a.ne()))
+}
+
+/// `ggml_top_k`: a view of the first `k` indices of a descending `ggml_argsort`.
+pub(crate) fn top_k(op: &'static str, a: Tensor, k: i32) -> Check {
+    let c = Checker(op);
+    let sorted = argsort(op, a, ggml_sort_order_GGML_SORT_ORDER_DESC)?;
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    c.arg(k > 0 && k as i64 <= ne0, || {
+        format!("k = {k} out of range for ne[0] = {ne0}")
+    })?;
+    Ok(Output::view(ggml_type_GGML_TYPE_I32, [k as i64, ne1, ne2, ne3]).after([sorted]))
+}
+
+/// `ggml_flash_attn_ext`: `q` is `[D, N, H, B]`, `k` and `v` are `[D, M, H_kv, B_kv]` with `H`
+/// and `B` multiples of `H_kv` and `B_kv`. The result is `[D, H, N, B]`.
+pub(crate) fn flash_attn_ext(
+    op: &'static str,
+    q: Tensor,
+    k: Tensor,
+    v: Tensor,
+    mask: Option<Tensor>,
+    max_bias: f32,
+) -> Check {
+    let c = Checker(op);
+    c.f32(0, q)?;
+    c.types(1, k, &[ggml_type_GGML_TYPE_F16])?;
+    c.types(2, v, &[ggml_type_GGML_TYPE_F16])?;
+    c.dims_eq((0, 0, q), (1, 0, k))?;
+    c.dims_eq((0, 0, q), (2, 0, v))?;
+    c.dims_eq((1, 1, k), (2, 1, v))?;
+    c.can_repeat_dims(k, q, 2..4)?;
+    c.can_repeat_dims(v, q, 2..4)?;
+    c.unit_stride(0, q)?;
+    c.unit_stride(1, k)?;
+    c.unit_stride(2, v)?;
+    if let Some(mask) = mask {
+        c.types(3, mask, &[ggml_type_GGML_TYPE_F16])?;
+        c.contiguous(3, mask)?;
+        c.max_rank(3, mask, 2)?;
+        c.dims_eq((3, 0, mask), (1, 1, k))?;
+        let pad = GGML_KQ_MASK_PAD as i64;
+        let rows = (q.ne()[1] + pad - 1) / pad * pad;
+        c.arg(mask.ne()[1] >= rows, || {
+            format!(
+                "mask ne[1] = {} must be at least {rows} (ne[1] padded to GGML_KQ_MASK_PAD)",
+                mask.ne()[1]
+            )
+        })?;
+    } else {
+        c.arg(max_bias <= 0.0, || String::from("max_bias requires a mask"))?;
+    }
+    let qne = q.ne();
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [v.ne()[0], qne[2], qne[1], qne[3]],
+    ))
+}
+
+/// `ggml_flash_attn_back`: `q` is `[D, N, H, B]`, `k` is `[D, M, H_kv, B]`, `v` is
+/// `[M, D, H_kv, B]` and `d` (the gradient of the attention output) is `[D, N, H, B]`. The
+/// result holds the gradients of `q`, `k` and `v` one after the other, each padded to
+/// `GGML_MEM_ALIGN`.
+pub(crate) fn flash_attn_back(
+    op: &'static str,
+    q: Tensor,
+    k: Tensor,
+    v: Tensor,
+    d: Tensor,
+) -> Check {
+    let c = Checker(op);
+    for (i, t) in [q, k, v, d].into_iter().enumerate() {
+        c.f32(i, t)?;
+        c.unit_stride(i, t)?;
+    }
+    c.dims_eq((0, 0, q), (1, 0, k))?;
+    c.dims_eq((1, 1, k), (2, 0, v))?;
+    c.dims_eq((0, 0, q), (2, 1, v))?;
+    c.dims_eq((1, 2, k), (2, 2, v))?;
+    c.dims_eq((0, 3, q), (1, 3, k))?;
+    c.dims_eq((0, 3, q), (2, 3, v))?;
+    c.same_shape((0, q), (3, d))?;
+    c.can_repeat_dims(k, q, 2..3)?;
+    let align = GGML_MEM_ALIGN as usize;
+    let size = |t: Tensor| (t.nelements() as usize * 4).next_multiple_of(align);
+    let nelements = (size(q) + size(k) + size(v)) / 4;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [nelements as i64, 1, 1, 1],
+    ))
+}
+
+/// `ggml_arange`: the F32 vector `start, start + step, ...` up to but excluding `stop`.
+pub(crate) fn arange(op: &'static str, start: f32, stop: f32, step: f32) -> Check {
+    let c = Checker(op);
+    c.arg(
+        start.is_finite() && stop.is_finite() && stop > start,
+        || format!("invalid range {start}..{stop}"),
+    )?;
+    c.arg(step.is_finite() && step > 0.0, || {
+        format!("step = {step} must be positive")
+    })?;
+    // Computed the same way as GGML, which truncates it to an i64.
+    let steps = ((stop - start) / step).ceil();
+    c.arg(steps < i64::MAX as f32, || {
+        format!("{steps} steps is too many")
+    })?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [steps as i64, 1, 1, 1],
+    ))
+}
+
+/// `ggml_unary`: `unary_op` is one of the `GGML_UNARY_OP_*` operations.
+pub(crate) fn unary_op(op: &'static str, a: Tensor, unary_op: ggml_unary_op) -> Check {
+    Checker(op).arg(unary_op < ggml_unary_op_GGML_UNARY_OP_COUNT, || {
+        format!("invalid unary operation {unary_op}")
+    })?;
+    unary(op, a)
+}
+
+/// `ggml_get_rows_back`: the gradient of [`get_rows`] for the matrix `a` of rows picked by the
+/// I32 vector `b` from a matrix shaped like `c`.
+pub(crate) fn get_rows_back(op: &'static str, a: Tensor, b: Tensor, c_: Tensor) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F32, ggml_type_GGML_TYPE_F16])?;
+    c.types(1, b, &[ggml_type_GGML_TYPE_I32])?;
+    c.max_rank(0, a, 2)?;
+    c.max_rank(1, b, 1)?;
+    c.max_rank(2, c_, 2)?;
+    c.dims_eq((0, 0, a), (2, 0, c_))?;
+    c.dims_eq((0, 1, a), (1, 0, b))?;
+    c.unit_stride(0, a)?;
+    c.contiguous(1, b)?;
+    let cne = c_.ne();
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [cne[0], cne[1], 1, 1],
+    ))
+}
+
+/// `ggml_ssm_conv`: the Mamba convolution of the tokens `x` (`[d_inner, n_tokens]`) with the
+/// kernel `c_` (`[d_conv, d_inner]`), given the states `s` (`[d_conv - 1, d_inner, n_kv]`) and
+/// the I32 sequence ids `sq` (`[n_kv, n_tokens]`). The result holds the output and the new
+/// states one after the other.
+pub(crate) fn ssm_conv(op: &'static str, s: Tensor, x: Tensor, c_: Tensor, sq: Tensor) -> Check {
+    let c = Checker(op);
+    for (i, t) in [s, x, c_].into_iter().enumerate() {
+        c.f32(i, t)?;
+    }
+    c.types(3, sq, &[ggml_type_GGML_TYPE_I32])?;
+    c.max_rank(0, s, 3)?;
+    c.max_rank(1, x, 2)?;
+    c.max_rank(2, c_, 2)?;
+    c.max_rank(3, sq, 2)?;
+    c.arg(s.ne()[0] == c_.ne()[0] - 1, || {
+        format!(
+            "operand 0 ne[0] = {} must be operand 2 ne[0] - 1 = {}",
+            s.ne()[0],
+            c_.ne()[0] - 1
+        )
+    })?;
+    c.dims_eq((0, 1, s), (2, 1, c_))?;
+    c.dims_eq((1, 0, x), (2, 1, c_))?;
+    c.dims_eq((3, 0, sq), (0, 2, s))?;
+    c.dims_eq((3, 1, sq), (1, 1, x))?;
+    c.contiguous(0, s)?;
+    c.unit_stride(1, x)?;
+    c.contiguous(2, c_)?;
+    c.contiguous(3, sq)?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [x.nelements() + s.nelements(), 1, 1, 1],
+    ))
+}
+
+/// `ggml_ssm_scan`: the Mamba selective scan, with the states `s` (`[d_state, d_inner, n_kv]`),
+/// `x` and `dt` (`[d_inner, n_tokens]`), `a_` (`[d_state, d_inner]`), `b` and `c_`
+/// (`[d_state, n_tokens]`) and the I32 sequence ids `sq` (`[n_kv, n_tokens]`). The result holds
+/// the output and the new states one after the other.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ssm_scan(
+    op: &'static str,
+    s: Tensor,
+    x: Tensor,
+    dt: Tensor,
+    a_: Tensor,
+    b: Tensor,
+    c_: Tensor,
+    sq: Tensor,
+) -> Check {
+    let c = Checker(op);
+    for (i, t) in [s, x, dt, a_, b, c_].into_iter().enumerate() {
+        c.f32(i, t)?;
+    }
+    c.types(6, sq, &[ggml_type_GGML_TYPE_I32])?;
+    c.max_rank(0, s, 3)?;
+    c.max_rank(1, x, 2)?;
+    c.max_rank(3, a_, 2)?;
+    c.max_rank(4, b, 2)?;
+    c.max_rank(5, c_, 2)?;
+    c.max_rank(6, sq, 2)?;
+    c.same_shape((1, x), (2, dt))?;
+    c.dims_eq((1, 0, x), (0, 1, s))?;
+    c.dims_eq((3, 0, a_), (0, 0, s))?;
+    c.dims_eq((3, 1, a_), (0, 1, s))?;
+    for (i, t) in [(4, b), (5, c_)] {
+        c.dims_eq((i, 0, t), (0, 0, s))?;
+        c.dims_eq((i, 1, t), (1, 1, x))?;
+    }
+    c.dims_eq((6, 0, sq), (0, 2, s))?;
+    c.dims_eq((6, 1, sq), (1, 1, x))?;
+    for (i, t) in [(0, s), (1, x), (2, dt), (3, a_), (6, sq)] {
+        c.contiguous(i, t)?;
+    }
+    c.unit_stride(4, b)?;
+    c.unit_stride(5, c_)?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F32,
+        [x.nelements() + s.nelements(), 1, 1, 1],
+    ))
+}
+
+/// `ggml_win_part`: splits `ne[1]` and `ne[2]` into `w` x `w` windows, padded with zeros,
+/// stacked along `ne[3]`.
+pub(crate) fn win_part(op: &'static str, a: Tensor, w: i32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    c.max_rank(0, a, 3)?;
+    c.positive("w", w.into())?;
+    let [ne0, ne1, ne2, _] = a.ne();
+    let w = w as i64;
+    // GGML computes the number of windows with C ints.
+    let limit = i32::MAX as i64 - w;
+    let np = (ne1 + w - 1) / w * ((ne2 + w - 1) / w);
+    c.arg(
+        ne1 <= limit && ne2 <= limit && np <= i32::MAX as i64,
+        || format!("too many {w} x {w} windows in {ne1} x {ne2}"),
+    )?;
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, [ne0, w, w, np]))
+}
+
+/// `ggml_win_unpart`: the reverse of [`win_part`], cropping the windows to `w0` x `h0`.
+pub(crate) fn win_unpart(op: &'static str, a: Tensor, w0: i32, h0: i32, w: i32) -> Check {
+    let c = Checker(op);
+    c.f32(0, a)?;
+    c.contiguous(0, a)?;
+    c.positive("w", w.into())?;
+    c.positive("w0", w0.into())?;
+    c.positive("h0", h0.into())?;
+    let [ne0, ne1, ne2, ne3] = a.ne();
+    let (w0, h0, w) = (w0 as i64, h0 as i64, w as i64);
+    c.arg(ne1 == w && ne2 == w, || {
+        format!("windows are {ne1} x {ne2}, not {w} x {w}")
+    })?;
+    let np = (w0 + w - 1) / w * ((h0 + w - 1) / w);
+    c.arg(ne3 >= np, || {
+        format!("{ne3} windows don't cover {w0} x {h0}, that takes {np}")
+    })?;
+    Ok(Output::new(op, ggml_type_GGML_TYPE_F32, [ne0, w0, h0, 1]))
+}
+
+/// `ggml_get_rel_pos`: the relative position embeddings of `qh` queries and `kh` keys, which
+/// GGML requires to be the same, from the F16 table `a` with `2 * qh - 1` rows.
+pub(crate) fn get_rel_pos(op: &'static str, a: Tensor, qh: i32, kh: i32) -> Check {
+    let c = Checker(op);
+    c.types(0, a, &[ggml_type_GGML_TYPE_F16])?;
+    c.contiguous(0, a)?;
+    c.positive("qh", qh.into())?;
+    c.arg(qh == kh, || {
+        format!("qh = {qh} and kh = {kh} must be equal")
+    })?;
+    let rows = 2 * qh as i64 - 1;
+    c.arg(a.ne()[1] == rows, || {
+        format!("ne[1] = {} must be 2 * qh - 1 = {rows}", a.ne()[1])
+    })?;
+    Ok(Output::new(
+        op,
+        ggml_type_GGML_TYPE_F16,
+        [a.ne()[0], kh as i64, qh as i64, 1],
+    ))
+}
+
+/// `ggml_add_rel_pos`: adds the decomposed relative positions `pw` and `ph` (the same shape)
+/// to the attention `a`.
+pub(crate) fn add_rel_pos(op: &'static str, a: Tensor, pw: Tensor, ph: Tensor) -> Check {
+    let c = Checker(op);
+    for (i, t) in [a, pw, ph].into_iter().enumerate() {
+        c.f32(i, t)?;
+        c.contiguous(i, t)?;
+    }
+    c.same_shape((1, pw), (2, ph))?;
+    c.dims_eq((1, 3, pw), (0, 2, a))?;
+    let [pne0, pne1, pne2, _] = pw.ne();
+    let ane = a.ne();
+    c.arg(pne0.checked_mul(pne0) == Some(ane[0]), || {
+        format!("operand 0 ne[0] = {} must be {pne0} squared", ane[0])
+    })?;
+    c.arg(pne1.checked_mul(pne2) == Some(ane[1]), || {
+        format!("operand 0 ne[1] = {} must be {pne1} * {pne2}", ane[1])
+    })?;
+    Ok(Output::like(op, a))
+}
+
+/// `ggml_conv_depthwise_2d`: convolves each channel of `b` (`[W, H, C, N]`) with its own
+/// kernel in the F16 `a` (`[KW, KH, 1, C]`). This reshapes both, does an `im2col` and a
+/// `mul_mat`, and reshapes the result to `[OW, OH, C, N]`.
+pub(crate) fn conv_depthwise_2d(
+    op: &'static str,
+    a: Tensor,
+    b: Tensor,
+    s: [i32; 2],
+    p: [i32; 2],
+    d: [i32; 2],
+) -> Check {
+    let c = Checker(op);
+    // The kernel is multiplied with the F16 `im2col`.
+    c.types(0, a, &[ggml_type_GGML_TYPE_F16])?;
+    c.contiguous(0, a)?;
+    c.f32(1, b)?;
+    c.contiguous(1, b)?;
+    let (ane, bne) = (a.ne(), b.ne());
+    let channels = ane[2] * ane[3];
+    let new_a = [ane[0], ane[1], 1, channels];
+    let new_b = [bne[0], bne[1], 1, bne[2] * bne[3]];
+    let col = im2col_ne(c, new_a, new_b, s, p, d, true)?;
+    let [k, ow, oh, _] = col;
+    c.arg(channels > 0 && bne[2] % channels == 0, || {
+        format!(
+            "operand 1 ne[2] = {} must be a multiple of {channels} kernels",
+            bne[2]
+        )
+    })?;
+    let (f16, f32) = (ggml_type_GGML_TYPE_F16, ggml_type_GGML_TYPE_F32);
+    Ok(Output::view(f32, [ow, oh, bne[2], bne[3]]).after([
+        Output::view(a.type_(), new_a),
+        Output::view(f32, new_b),
+        Output::new(op, f16, col),
+        Output::view(f16, [k, ow * oh, bne[2], bne[3]]),
+        Output::view(a.type_(), [k, 1, channels, 1]),
+        Output::new(op, f32, [1, ow * oh, bne[2], bne[3]]),
+    ]))
+}
@@ -0,0 +1,485 @@
+use std::{
+    borrow::Cow,
+    ffi::{CStr, CString},
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, Range},
+    os::raw::c_int,
+    ptr::NonNull,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{
+    context::Context,
+    error::{Error, Result},
+    shape::Checker,
+    *,
+};
+
+/// Rust types that match the in-memory layout of a `ggml_type`.
+///
+/// # Safety
+/// `TYPE` must be a non-quantized type whose elements have the same size and representation
+/// as `Self`.
+pub unsafe trait TensorElement: Copy + 'static {
+    const TYPE: ggml_type;
+}
+
+unsafe impl TensorElement for f32 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_F32;
+}
+
+unsafe impl TensorElement for f64 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_F64;
+}
+
+unsafe impl TensorElement for i8 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_I8;
+}
+
+unsafe impl TensorElement for i16 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_I16;
+}
+
+unsafe impl TensorElement for i32 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_I32;
+}
+
+unsafe impl TensorElement for i64 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_I64;
+}
+
+#[cfg(feature = "half")]
+unsafe impl TensorElement for half::f16 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_F16;
+}
+
+#[cfg(feature = "half")]
+unsafe impl TensorElement for half::bf16 {
+    const TYPE: ggml_type = ggml_type_GGML_TYPE_BF16;
+}
+
+/// Byte ranges of tensor data currently borrowed by a [`TensorSlice`] (or array view), one
+/// entry per borrow along with whether it's exclusive. Handles are `Copy`, so whether memory
+/// can be read or written is tracked here rather than by the borrow checker.
+static BORROWED: Mutex<Vec<(Range<usize>, bool)>> = Mutex::new(Vec::new());
+
+fn borrowed() -> std::sync::MutexGuard<'static, Vec<(Range<usize>, bool)>> {
+    BORROWED.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Registers a borrow of tensor data until it's dropped.
+pub(crate) struct DataBorrow(Range<usize>, bool);
+
+impl DataBorrow {
+    /// A shared borrow, which fails with [`Error::DataBorrowed`] if the data is borrowed
+    /// exclusively.
+    pub(crate) fn new(range: Range<usize>) -> Result<Self> {
+        Self::register(range, false)
+    }
+
+    /// An exclusive borrow, which fails with [`Error::DataBorrowed`] if the data is borrowed at
+    /// all.
+    #[cfg(feature = "ndarray")]
+    pub(crate) fn new_mut(range: Range<usize>) -> Result<Self> {
+        Self::register(range, true)
+    }
+
+    fn register(range: Range<usize>, exclusive: bool) -> Result<Self> {
+        let mut borrowed = borrowed();
+        if borrowed
+            .iter()
+            .any(|(b, b_exclusive)| (exclusive || *b_exclusive) && overlaps(b, &range))
+        {
+            return Err(Error::DataBorrowed);
+        }
+        borrowed.push((range.clone(), exclusive));
+        Ok(Self(range, exclusive))
+    }
+}
+
+impl Drop for DataBorrow {
+    fn drop(&mut self) {
+        let mut borrowed = borrowed();
+        if let Some(i) = borrowed
+            .iter()
+            .position(|(range, exclusive)| *range == self.0 && *exclusive == self.1)
+        {
+            borrowed.swap_remove(i);
+        }
+    }
+}
+
+/// Fails with [`Error::DataBorrowed`] if any of `ranges` overlaps borrowed tensor data.
+pub(crate) fn ensure_writable(ranges: impl IntoIterator<Item = Range<usize>>) -> Result<()> {
+    let borrowed = borrowed();
+    if borrowed.is_empty() {
+        return Ok(());
+    }
+    for range in ranges {
+        if borrowed.iter().any(|(b, _)| overlaps(b, &range)) {
+            return Err(Error::DataBorrowed);
+        }
+    }
+    Ok(())
+}
+
+/// Fails with [`Error::DataBorrowed`] if any of `ranges` overlaps exclusively borrowed tensor
+/// data.
+pub(crate) fn ensure_readable(ranges: impl IntoIterator<Item = Range<usize>>) -> Result<()> {
+    let borrowed = borrowed();
+    if borrowed.iter().all(|(_, exclusive)| !exclusive) {
+        return Ok(());
+    }
+    for range in ranges {
+        if borrowed
+            .iter()
+            .any(|(b, exclusive)| *exclusive && overlaps(b, &range))
+        {
+            return Err(Error::DataBorrowed);
+        }
+    }
+    Ok(())
+}
+
+/// Tensor data borrowed by [`Tensor::as_slice`]. As long as it's alive, anything writing to the
+/// same memory ([`Tensor::copy_from_slice`], [`Graph::compute`](crate::Graph::compute)...)
+/// fails with [`Error::DataBorrowed`] instead.
+pub struct TensorSlice<'a, T> {
+    data: &'a [T],
+    _borrow: DataBorrow,
+}
+
+impl<T> Deref for TensorSlice<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.data
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TensorSlice<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.data.fmt(f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for TensorSlice<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.data == other.data
+    }
+}
+
+macro_rules! slice_eq {
+    ($($(#[$generics:ident])? $rhs:ty),* $(,)?) => {
+        $(
+            impl<T: PartialEq $(, const $generics: usize)?> PartialEq<$rhs> for TensorSlice<'_, T> {
+                fn eq(&self, other: &$rhs) -> bool {
+                    self.data[..] == other[..]
+                }
+            }
+        )*
+    };
+}
+
+slice_eq! { [T], &[T], Vec<T>, #[N] [T; N], #[N] &[T; N] }
+
+/// A handle to a tensor living in a [`Context`]. Handles are cheap to copy and borrow the
+/// context, so they can't outlive it. New tensors produced by operations on the handle
+/// (see `ops.rs`) are created in the same context.
+///
+/// The tensor data itself is shared between handles: it's borrowed at runtime by
+/// [`Tensor::as_slice`], and writing to it while it's borrowed fails.
+#[derive(Debug, Clone, Copy)]
+pub struct Tensor<'ctx> {
+    ptr: NonNull<ggml_tensor>,
+    ctx: &'ctx Context,
+}
+
+impl PartialEq for Tensor<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ptr == other.ptr
+    }
+}
+
+impl Eq for Tensor<'_> {}
+
+impl Hash for Tensor<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.ptr.hash(state)
+    }
+}
+
+impl<'ctx> Tensor<'ctx> {
+    /// Wraps a raw tensor pointer, returning `None` if it's NULL.
+    ///
+    /// # Safety
+    /// The tensor must be valid for as long as `ctx` is borrowed, usually because it was
+    /// allocated in `ctx`.
+    pub unsafe fn from_raw(ctx: &'ctx Context, ptr: *mut ggml_tensor) -> Option<Self> {
+        NonNull::new(ptr).map(|ptr| Self { ptr, ctx })
+    }
+
+    /// Wraps the result of a GGML operation on this tensor.
+    pub(crate) fn wrap(&self, ptr: *mut ggml_tensor) -> Self {
+        unsafe { Self::from_raw(self.ctx, ptr) }.expect("GGML operation returned NULL")
+    }
+
+    pub fn as_ptr(&self) -> *mut ggml_tensor {
+        self.ptr.as_ptr()
+    }
+
+    /// The context new tensors derived from this one are created in.
+    pub fn context(&self) -> &'ctx Context {
+        self.ctx
+    }
+
+    fn raw(&self) -> &ggml_tensor {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    pub fn type_(&self) -> ggml_type {
+        self.raw().type_
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        unsafe { CStr::from_ptr(ggml_type_name(self.type_())) }
+            .to_str()
+            .unwrap_or("?")
+    }
+
+    /// Number of dimensions, ignoring trailing dimensions of size 1 (`ggml_n_dims`).
+    pub fn n_dims(&self) -> usize {
+        unsafe { ggml_n_dims(self.as_ptr()) as usize }
+    }
+
+    /// Number of elements in each dimension, `ne[0]` (the fastest changing) first.
+    pub fn ne(&self) -> [i64; GGML_MAX_DIMS as usize] {
+        self.raw().ne
+    }
+
+    /// Stride in bytes for each dimension.
+    pub fn nb(&self) -> [usize; GGML_MAX_DIMS as usize] {
+        self.raw().nb
+    }
+
+    /// The first [`Tensor::n_dims`] entries of [`Tensor::ne`].
+    pub fn shape(&self) -> &[i64] {
+        &self.raw().ne[..self.n_dims()]
+    }
+
+    pub fn nelements(&self) -> i64 {
+        unsafe { ggml_nelements(self.as_ptr()) }
+    }
+
+    pub fn nrows(&self) -> i64 {
+        unsafe { ggml_nrows(self.as_ptr()) }
+    }
+
+    pub fn nbytes(&self) -> usize {
+        unsafe { ggml_nbytes(self.as_ptr()) }
+    }
+
+    pub fn is_contiguous(&self) -> bool {
+        unsafe { ggml_is_contiguous(self.as_ptr()) }
+    }
+
+    pub fn name(&self) -> Cow<'_, str> {
+        unsafe { CStr::from_ptr(ggml_get_name(self.as_ptr())) }.to_string_lossy()
+    }
+
+    /// Sets the tensor's name. GGML truncates names to `GGML_MAX_NAME - 1` bytes.
+    pub fn set_name(self, name: &str) -> Self {
+        let name = CString::new(name).expect("Tensor name contains a NUL byte");
+        unsafe { ggml_set_name(self.as_ptr(), name.as_ptr()) };
+        self
+    }
+
+    /// The operation that produces this tensor, `GGML_OP_NONE` for leaves.
+    pub fn op(&self) -> ggml_op {
+        self.raw().op
+    }
+
+    /// Human readable name of the operation (`ggml_op_desc`), which includes unary ops.
+    pub fn op_desc(&self) -> &'static str {
+        unsafe { CStr::from_ptr(ggml_op_desc(self.as_ptr())) }
+            .to_str()
+            .unwrap_or("?")
+    }
+
+    /// The tensor's operands.
+    pub fn sources(&self) -> impl Iterator<Item = Tensor<'ctx>> + '_ {
+        self.raw()
+            .src
+            .iter()
+            .map_while(|src| unsafe { Tensor::from_raw(self.ctx, *src) })
+    }
+
+    /// For views, the tensor whose data this one points into.
+    pub fn view_src(&self) -> Option<Tensor<'ctx>> {
+        unsafe { Tensor::from_raw(self.ctx, self.raw().view_src) }
+    }
+
+    /// Marks the tensor as a parameter to optimize or differentiate by (`ggml_set_param`),
+    /// which allocates its gradient. Only operations on tensors with gradients get gradients
+    /// themselves, so this has to be done before using the tensor in any operations.
+    pub fn set_param(self) -> Result<Self> {
+        if self.is_param() {
+            return Ok(self);
+        }
+        Checker("ggml_set_param").arg(self.grad().is_none(), || {
+            "tensor already has a gradient".to_owned()
+        })?;
+        self.ctx.ensure_room(self.type_(), &self.ne(), false)?;
+        unsafe { ggml_set_param(self.ctx.as_ptr(), self.as_ptr()) };
+        Ok(self)
+    }
+
+    pub fn is_param(&self) -> bool {
+        self.raw().flags & ggml_tensor_flag_GGML_TENSOR_FLAG_PARAM as i32 != 0
+    }
+
+    /// The tensor's gradient, if it's a parameter or depends on one.
+    pub fn grad(&self) -> Option<Tensor<'ctx>> {
+        unsafe { Tensor::from_raw(self.ctx, self.raw().grad) }
+    }
+
+    pub fn data_ptr(&self) -> *mut std::os::raw::c_void {
+        self.raw().data
+    }
+
+    /// The addresses of the tensor's data.
+    pub(crate) fn data_range(&self) -> Range<usize> {
+        let start = self.data_ptr() as usize;
+        start..start + self.nbytes()
+    }
+
+    fn check_element<T: TensorElement>(&self) -> Result<()> {
+        if self.type_() != T::TYPE {
+            return Err(Error::TypeMismatch {
+                expected: T::TYPE,
+                actual: self.type_(),
+            });
+        }
+        if !self.is_contiguous() {
+            return Err(Error::NotContiguous);
+        }
+        if self.data_ptr().is_null() {
+            return Err(Error::NoData);
+        }
+        Ok(())
+    }
+
+    /// Borrows the tensor data, which must be contiguous and of type `T`. Fails with
+    /// [`Error::DataBorrowed`] if it's mutably borrowed as an array.
+    pub fn as_slice<T: TensorElement>(&self) -> Result<TensorSlice<'_, T>> {
+        self.check_element::<T>()?;
+        Ok(TensorSlice {
+            data: unsafe {
+                std::slice::from_raw_parts(self.data_ptr().cast(), self.nelements() as usize)
+            },
+            _borrow: DataBorrow::new(self.data_range())?,
+        })
+    }
+
+    /// Overwrites the tensor data, which must be contiguous and of type `T`.
+    pub fn copy_from_slice<T: TensorElement>(&self, src: &[T]) -> Result<()> {
+        self.check_element::<T>()?;
+        if src.len() != self.nelements() as usize {
+            return Err(Error::LengthMismatch {
+                expected: self.nelements() as usize,
+                actual: src.len(),
+            });
+        }
+        ensure_writable([self.data_range()])?;
+        unsafe { std::ptr::copy_nonoverlapping(src.as_ptr(), self.data_ptr().cast(), src.len()) };
+        Ok(())
+    }
+
+    fn nd_index<const N: usize>(&self, index: [usize; N]) -> Result<[c_int; 4]> {
+        assert!(
+            N <= GGML_MAX_DIMS as usize,
+            "Index has more than {GGML_MAX_DIMS} dimensions"
+        );
+        // `ggml_get_f32_nd` and friends abort on anything else.
+        let supported = [
+            ggml_type_GGML_TYPE_I8,
+            ggml_type_GGML_TYPE_I16,
+            ggml_type_GGML_TYPE_I32,
+            ggml_type_GGML_TYPE_F16,
+            ggml_type_GGML_TYPE_BF16,
+            ggml_type_GGML_TYPE_F32,
+        ];
+        if !supported.contains(&self.type_()) {
+            return Err(Error::UnsupportedType(self.type_()));
+        }
+        if self.data_ptr().is_null() {
+            return Err(Error::NoData);
+        }
+        let ne = self.ne();
+        let mut result = [0; 4];
+        for (dim, idx) in index.into_iter().enumerate() {
+            assert!(
+                (idx as i64) < ne[dim],
+                "Index {idx} out of bounds for dimension {dim} of size {}",
+                ne[dim]
+            );
+            result[dim] = idx as c_int;
+        }
+        Ok(result)
+    }
+
+    /// The address of the element at `index`, for checking it can be read or written.
+    fn element_range(&self, [i0, i1, i2, i3]: [c_int; 4]) -> Range<usize> {
+        let nb = self.nb();
+        let start = self.data_ptr() as usize
+            + [i0, i1, i2, i3]
+                .iter()
+                .zip(nb)
+                .map(|(i, nb)| *i as usize * nb)
+                .sum::<usize>();
+        start..start + unsafe { ggml_type_size(self.type_()) }
+    }
+
+    /// Reads one element as `f32`, converting from the tensor's type. Missing trailing indices
+    /// are zero, so `t.get_f32([i, j])` reads `ne[0]` index `i` and `ne[1]` index `j`.
+    ///
+    /// Fails for quantized, 64 bit and other types GGML can't convert and panics if the index
+    /// is out of bounds.
+    pub fn get_f32<const N: usize>(&self, index: [usize; N]) -> Result<f32> {
+        let index = self.nd_index(index)?;
+        ensure_readable([self.element_range(index)])?;
+        let [i0, i1, i2, i3] = index;
+        Ok(unsafe { ggml_get_f32_nd(self.as_ptr(), i0, i1, i2, i3) })
+    }
+
+    /// Writes one element, see [`Tensor::get_f32`].
+    pub fn set_f32<const N: usize>(&self, index: [usize; N], value: f32) -> Result<()> {
+        let index = self.nd_index(index)?;
+        ensure_writable([self.element_range(index)])?;
+        let [i0, i1, i2, i3] = index;
+        unsafe { ggml_set_f32_nd(self.as_ptr(), i0, i1, i2, i3, value) };
+        Ok(())
+    }
+
+    /// Reads one element as `i32`, see [`Tensor::get_f32`].
+    pub fn get_i32<const N: usize>(&self, index: [usize; N]) -> Result<i32> {
+        let index = self.nd_index(index)?;
+        ensure_readable([self.element_range(index)])?;
+        let [i0, i1, i2, i3] = index;
+        Ok(unsafe { ggml_get_i32_nd(self.as_ptr(), i0, i1, i2, i3) })
+    }
+
+    /// Writes one element, see [`Tensor::get_f32`].
+    pub fn set_i32<const N: usize>(&self, index: [usize; N], value: i32) -> Result<()> {
+        let index = self.nd_index(index)?;
+        ensure_writable([self.element_range(index)])?;
+        let [i0, i1, i2, i3] = index;
+        unsafe { ggml_set_i32_nd(self.as_ptr(), i0, i1, i2, i3, value) };
+        Ok(())
+    }
+}
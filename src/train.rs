@@ -0,0 +1,685 @@
+//! Optimizing parameters with GGML's Adam and L-BFGS implementations (`ggml_opt_resume_g`).
+//!
+//! Mark the tensors to optimize with [`Tensor::set_param`], build a scalar loss from them and
+//! pass it to [`Trainer::new`], which builds the forward and backward graphs and allocates the
+//! optimizer state. Each call to [`Trainer::train`] then runs up to the configured number of
+//! iterations, continuing where the previous call left off.
+//!
+//! [`Trainer::save_checkpoint`] writes the parameters and the optimizer state to a GGUF file
+//! (using the key and tensor names of llama.cpp's training examples), which
+//! [`Trainer::load_checkpoint`] restores into a trainer built the same way, so training can be
+//! resumed later.
+
+use std::{
+    cell::Cell,
+    fmt,
+    ops::{ControlFlow, Range},
+    os::raw::c_int,
+    path::Path,
+};
+
+use crate::{
+    callback::{opt_trampoline, Callback, OptFn},
+    context::Context,
+    error::{Error, Result},
+    gguf::{GgufFile, GgufWriter},
+    graph::Graph,
+    shape::Checker,
+    tensor::{ensure_readable, ensure_writable, Tensor},
+    *,
+};
+
+/// Adam settings, see `ggml_opt_params.adam`. The defaults are GGML's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AdamConfig {
+    /// Iterations per call to [`Trainer::train`].
+    pub n_iter: usize,
+    /// Initial learning rate multiplier, which the callback can change.
+    pub sched: f32,
+    /// Weight decay, applied to parameters with at least `decay_min_ndim` dimensions.
+    pub decay: f32,
+    pub decay_min_ndim: usize,
+    /// Learning rate.
+    pub alpha: f32,
+    pub beta1: f32,
+    pub beta2: f32,
+    pub eps: f32,
+    /// Stops when the relative change of the loss is below this.
+    pub eps_f: f32,
+    pub eps_g: f32,
+    /// Clips the gradient norm to this if positive.
+    pub gclip: f32,
+}
+
+impl Default for AdamConfig {
+    fn default() -> Self {
+        let adam = unsafe { ggml_opt_default_params(ggml_opt_type_GGML_OPT_TYPE_ADAM) }.adam;
+        Self {
+            n_iter: adam.n_iter as usize,
+            sched: adam.sched,
+            decay: adam.decay,
+            decay_min_ndim: adam.decay_min_ndim as usize,
+            alpha: adam.alpha,
+            beta1: adam.beta1,
+            beta2: adam.beta2,
+            eps: adam.eps,
+            eps_f: adam.eps_f,
+            eps_g: adam.eps_g,
+            gclip: adam.gclip,
+        }
+    }
+}
+
+/// The L-BFGS line search conditions (`ggml_linesearch`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Linesearch {
+    Armijo,
+    Wolfe,
+    StrongWolfe,
+}
+
+impl Linesearch {
+    fn to_raw(self) -> ggml_linesearch {
+        match self {
+            Self::Armijo => ggml_linesearch_GGML_LINESEARCH_BACKTRACKING_ARMIJO,
+            Self::Wolfe => ggml_linesearch_GGML_LINESEARCH_BACKTRACKING_WOLFE,
+            Self::StrongWolfe => ggml_linesearch_GGML_LINESEARCH_BACKTRACKING_STRONG_WOLFE,
+        }
+    }
+
+    fn from_raw(raw: ggml_linesearch) -> Self {
+        if raw == ggml_linesearch_GGML_LINESEARCH_BACKTRACKING_ARMIJO {
+            Self::Armijo
+        } else if raw == ggml_linesearch_GGML_LINESEARCH_BACKTRACKING_STRONG_WOLFE {
+            Self::StrongWolfe
+        } else {
+            Self::Wolfe
+        }
+    }
+}
+
+/// L-BFGS settings, see `ggml_opt_params.lbfgs`. The defaults are GGML's.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LbfgsConfig {
+    /// Number of corrections kept to approximate the inverse Hessian.
+    pub m: usize,
+    /// Iterations per call to [`Trainer::train`].
+    pub n_iter: usize,
+    pub max_linesearch: usize,
+    /// Stops when the gradient norm relative to the parameter norm is below this.
+    pub eps: f32,
+    pub ftol: f32,
+    /// Has to be between `ftol` and 1 for the Wolfe line searches.
+    pub wolfe: f32,
+    pub min_step: f32,
+    pub max_step: f32,
+    pub linesearch: Linesearch,
+}
+
+impl Default for LbfgsConfig {
+    fn default() -> Self {
+        let lbfgs = unsafe { ggml_opt_default_params(ggml_opt_type_GGML_OPT_TYPE_LBFGS) }.lbfgs;
+        Self {
+            m: lbfgs.m as usize,
+            n_iter: lbfgs.n_iter as usize,
+            max_linesearch: lbfgs.max_linesearch as usize,
+            eps: lbfgs.eps,
+            ftol: lbfgs.ftol,
+            wolfe: lbfgs.wolfe,
+            min_step: lbfgs.min_step,
+            max_step: lbfgs.max_step,
+            linesearch: Linesearch::from_raw(lbfgs.linesearch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Optimizer {
+    Adam(AdamConfig),
+    Lbfgs(LbfgsConfig),
+}
+
+/// Settings for a [`Trainer`], `ggml_opt_params` without the magic numbers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OptConfig {
+    pub optimizer: Optimizer,
+    /// Threads used to compute the graphs.
+    pub n_threads: usize,
+    /// Nodes (and leafs) the forward and backward graphs have room for.
+    pub graph_size: usize,
+    /// With `past > 0`, stops when the loss improved by less than `delta` (relative) over the
+    /// last `past` iterations.
+    pub past: usize,
+    pub delta: f32,
+    /// Stops after this many iterations without improvement, if non-zero (Adam only).
+    pub max_no_improvement: usize,
+    /// Number of times the loss is computed and the gradients accumulated per iteration.
+    pub n_gradient_accumulation: usize,
+}
+
+impl OptConfig {
+    /// GGML's defaults for Adam with the given Adam settings.
+    pub fn adam(adam: AdamConfig) -> Self {
+        Self::defaults(Optimizer::Adam(adam))
+    }
+
+    /// GGML's defaults for L-BFGS with the given L-BFGS settings.
+    pub fn lbfgs(lbfgs: LbfgsConfig) -> Self {
+        Self::defaults(Optimizer::Lbfgs(lbfgs))
+    }
+
+    fn defaults(optimizer: Optimizer) -> Self {
+        let params = unsafe { ggml_opt_default_params(Self::opt_type(&optimizer)) };
+        Self {
+            optimizer,
+            n_threads: params.n_threads as usize,
+            graph_size: params.graph_size,
+            past: params.past as usize,
+            delta: params.delta,
+            max_no_improvement: params.max_no_improvement as usize,
+            n_gradient_accumulation: params.n_gradient_accumulation as usize,
+        }
+    }
+
+    fn opt_type(optimizer: &Optimizer) -> ggml_opt_type {
+        match optimizer {
+            Optimizer::Adam(_) => ggml_opt_type_GGML_OPT_TYPE_ADAM,
+            Optimizer::Lbfgs(_) => ggml_opt_type_GGML_OPT_TYPE_LBFGS,
+        }
+    }
+
+    /// The equivalent `ggml_opt_params`.
+    pub fn to_params(&self) -> ggml_opt_params {
+        let mut params = unsafe { ggml_opt_default_params(Self::opt_type(&self.optimizer)) };
+        params.n_threads = self.n_threads.max(1) as c_int;
+        params.graph_size = self.graph_size;
+        params.past = self.past as c_int;
+        params.delta = self.delta;
+        params.max_no_improvement = self.max_no_improvement as c_int;
+        params.n_gradient_accumulation = self.n_gradient_accumulation.max(1) as c_int;
+        match self.optimizer {
+            Optimizer::Adam(adam) => {
+                params.adam.n_iter = adam.n_iter as c_int;
+                params.adam.sched = adam.sched;
+                params.adam.decay = adam.decay;
+                params.adam.decay_min_ndim = adam.decay_min_ndim as c_int;
+                params.adam.alpha = adam.alpha;
+                params.adam.beta1 = adam.beta1;
+                params.adam.beta2 = adam.beta2;
+                params.adam.eps = adam.eps;
+                params.adam.eps_f = adam.eps_f;
+                params.adam.eps_g = adam.eps_g;
+                params.adam.gclip = adam.gclip;
+            }
+            Optimizer::Lbfgs(lbfgs) => {
+                params.lbfgs.m = lbfgs.m as c_int;
+                params.lbfgs.n_iter = lbfgs.n_iter as c_int;
+                params.lbfgs.max_linesearch = lbfgs.max_linesearch as c_int;
+                params.lbfgs.eps = lbfgs.eps;
+                params.lbfgs.ftol = lbfgs.ftol;
+                params.lbfgs.wolfe = lbfgs.wolfe;
+                params.lbfgs.min_step = lbfgs.min_step;
+                params.lbfgs.max_step = lbfgs.max_step;
+                params.lbfgs.linesearch = lbfgs.linesearch.to_raw();
+            }
+        }
+        params
+    }
+}
+
+/// What the callback passed to [`Trainer::train_with`] is called with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Iteration {
+    /// Number of parameter updates done so far, including previous calls to `train`.
+    pub iter: usize,
+    /// Index of the gradient accumulation step about to be computed.
+    pub accum_step: usize,
+    /// The most recently computed loss, NaN before the first one.
+    pub loss: f32,
+    /// Learning rate multiplier for the next update (Adam only), which the callback can
+    /// change to implement a schedule.
+    pub sched: f32,
+}
+
+/// How a call to [`Trainer::train`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// One of the convergence criteria was met.
+    Converged,
+    /// All iterations ran without converging. Training can be continued by calling `train`
+    /// again.
+    DidNotConverge,
+    /// The callback stopped training.
+    Stopped,
+}
+
+/// Optimizes the parameters a loss depends on, see the [module docs](self).
+pub struct Trainer<'ctx> {
+    loss: Tensor<'ctx>,
+    params: Vec<Tensor<'ctx>>,
+    forward: Graph<'ctx>,
+    backward: Graph<'ctx>,
+    config: OptConfig,
+    // Boxed since GGML keeps a pointer to it while optimizing.
+    opt: Box<ggml_opt_context>,
+    // Holds the optimizer state tensors (`opt.adam.m` and so on).
+    state: Context,
+}
+
+impl<'ctx> Trainer<'ctx> {
+    /// Builds the graphs for `loss`, which must be an F32 scalar depending on at least one
+    /// parameter, and initializes the optimizer (`ggml_opt_init`). The gradient operations
+    /// are allocated in the loss' context, see [`Graph::backward`].
+    pub fn new(loss: Tensor<'ctx>, config: OptConfig) -> Result<Self> {
+        if loss.type_() != ggml_type_GGML_TYPE_F32 {
+            return Err(Error::TypeMismatch {
+                expected: ggml_type_GGML_TYPE_F32,
+                actual: loss.type_(),
+            });
+        }
+        if loss.nelements() != 1 {
+            return Err(Error::InvalidShape {
+                type_: loss.type_(),
+                shape: loss.shape().to_vec(),
+            });
+        }
+        if loss.grad().is_none() {
+            return Err(Error::NoGradients);
+        }
+
+        let mut forward = Graph::with_size(loss.context(), config.graph_size, true)?;
+        forward.build_forward(&loss)?;
+        let params = forward.nodes().filter(Tensor::is_param).collect::<Vec<_>>();
+        Checker("ggml_opt").arg(params.len() < GGML_MAX_PARAMS as usize, || {
+            format!("more than {} parameters", GGML_MAX_PARAMS - 1)
+        })?;
+        let backward = forward.backward(true)?;
+
+        let nx = params.iter().map(Tensor::nelements).sum::<i64>();
+        let opt_params = config.to_params();
+        let opt_ctx = Context::new(state_size(&opt_params, nx))?;
+        // An all zero `ggml_opt_context` is valid, `ggml_opt_init` sets everything it uses.
+        let mut opt = Box::new(unsafe { std::mem::zeroed::<ggml_opt_context>() });
+        unsafe { ggml_opt_init(opt_ctx.as_ptr(), &mut *opt, opt_params, nx) };
+        opt.loss_before = f32::NAN;
+        opt.loss_after = f32::NAN;
+        Ok(Self {
+            loss,
+            params,
+            forward,
+            backward,
+            config,
+            opt,
+            state: opt_ctx,
+        })
+    }
+
+    pub fn loss(&self) -> Tensor<'ctx> {
+        self.loss
+    }
+
+    /// The parameters being optimized, in the order they appear in the forward graph.
+    pub fn params(&self) -> &[Tensor<'ctx>] {
+        &self.params
+    }
+
+    pub fn forward(&self) -> &Graph<'ctx> {
+        &self.forward
+    }
+
+    pub fn backward(&self) -> &Graph<'ctx> {
+        &self.backward
+    }
+
+    pub fn config(&self) -> &OptConfig {
+        &self.config
+    }
+
+    /// The raw optimizer state.
+    pub fn opt_context(&self) -> &ggml_opt_context {
+        &self.opt
+    }
+
+    /// Number of parameter updates done so far.
+    pub fn iter(&self) -> usize {
+        self.opt.iter as usize
+    }
+
+    /// The loss at the start of the last call to `train`, NaN before the first.
+    pub fn loss_before(&self) -> f32 {
+        self.opt.loss_before
+    }
+
+    /// The loss at the end of the last call to `train`, NaN before the first.
+    pub fn loss_after(&self) -> f32 {
+        self.opt.loss_after
+    }
+
+    /// Runs the optimizer for up to the configured number of iterations. Fails with
+    /// [`Error::DataBorrowed`] if the parameters are borrowed and with [`Error::NoData`] if a
+    /// tensor of the graphs has no data.
+    pub fn train(&mut self) -> Result<Outcome> {
+        let opt = &mut *self.opt as *mut ggml_opt_context;
+        self.run(opt, None)
+    }
+
+    /// Like [`Trainer::train`], calling `callback` before each computation of the loss. That's
+    /// once per gradient accumulation step for Adam, while L-BFGS also calls it for each step
+    /// of the line search. Returning [`ControlFlow::Break`] stops training, as does keeping the
+    /// parameters borrowed after the callback returns, which fails with
+    /// [`Error::DataBorrowed`].
+    pub fn train_with(
+        &mut self,
+        mut callback: impl FnMut(&mut Iteration) -> ControlFlow<()>,
+    ) -> Result<Outcome> {
+        let opt = &mut *self.opt as *mut ggml_opt_context;
+        // The callback may borrow the parameters (or mutably borrow the inputs), which mustn't
+        // outlive it.
+        let written = self.written();
+        let read = self.read();
+        let borrowed = Cell::new(false);
+        let fun = |accum_step: c_int, sched: &mut f32, cancel: &mut bool| {
+            // GGML only updates the counters between calls.
+            let opt = unsafe { &*opt };
+            let mut iteration = Iteration {
+                iter: opt.iter as usize,
+                accum_step: accum_step as usize,
+                loss: opt.loss_after,
+                sched: *sched,
+            };
+            *cancel = callback(&mut iteration).is_break();
+            *sched = iteration.sched;
+            if ensure_writable(written.iter().cloned()).is_err()
+                || ensure_readable(read.iter().cloned()).is_err()
+            {
+                borrowed.set(true);
+                *cancel = true;
+            }
+        };
+        let outcome = self.run(opt, Some(Callback::<OptFn>::new(Box::new(fun))))?;
+        if borrowed.get() {
+            return Err(Error::DataBorrowed);
+        }
+        Ok(outcome)
+    }
+
+    /// The data the optimizer writes to: the parameters and the nodes of the backward graph.
+    fn written(&self) -> Vec<Range<usize>> {
+        self.params
+            .iter()
+            .copied()
+            .chain(self.backward.nodes())
+            .map(|tensor| tensor.data_range())
+            .collect()
+    }
+
+    /// The data the optimizer only reads: the leafs of the backward graph.
+    fn read(&self) -> Vec<Range<usize>> {
+        self.backward
+            .leafs()
+            .map(|tensor| tensor.data_range())
+            .collect()
+    }
+
+    /// `opt` is `self.opt`, which the callback may also read.
+    fn run(
+        &mut self,
+        opt: *mut ggml_opt_context,
+        mut callback: Option<Box<Callback<OptFn>>>,
+    ) -> Result<Outcome> {
+        self.backward.ensure_data()?;
+        ensure_writable(self.written())?;
+        ensure_readable(self.read())?;
+        // `ggml_opt_resume_g` allocates its work buffer in the context it's given every time
+        // it's called, so it gets a new one sized for it.
+        let n_threads = unsafe { (*opt).params.n_threads };
+        let plan = unsafe { ggml_graph_plan(self.backward.as_ptr(), n_threads) };
+        let work = Context::new(plan.work_size + GGML_OBJECT_SIZE + GGML_MEM_ALIGN as usize)?;
+        let (trampoline, userdata) = match &mut callback {
+            Some(cb) => (Some(opt_trampoline as _), cb.userdata()),
+            None => (None, std::ptr::null_mut()),
+        };
+        let result = unsafe {
+            ggml_opt_resume_g(
+                work.as_ptr(),
+                opt,
+                self.loss.as_ptr(),
+                self.forward.as_ptr(),
+                self.backward.as_ptr(),
+                trampoline,
+                userdata,
+            )
+        };
+        if let Some(cb) = &callback {
+            cb.resume_panic();
+        }
+        if let Some(payload) = custom::take_panic(self.backward.nodes()) {
+            std::panic::resume_unwind(payload);
+        }
+        match result {
+            result if result == ggml_opt_result_GGML_OPT_RESULT_OK => Ok(Outcome::Converged),
+            result if result == ggml_opt_result_GGML_OPT_RESULT_DID_NOT_CONVERGE => {
+                Ok(Outcome::DidNotConverge)
+            }
+            result if result == ggml_opt_result_GGML_OPT_RESULT_CANCEL => Ok(Outcome::Stopped),
+            result => Err(Error::Optimizer(result)),
+        }
+    }
+}
+
+const KEY_FILE_VERSION: &str = "optimizer.file_version";
+const KEY_TYPE: &str = "optimizer.type";
+const KEY_PAST: &str = "optimizer.convergence_past_count";
+const KEY_NX: &str = "optimizer.parameter_count";
+const KEY_ITER: &str = "optimizer.iteration_count";
+const KEY_JUST_INITIALIZED: &str = "optimizer.just_initialized";
+const KEY_ADAM_BEST_LOSS: &str = "optimizer.adam.best_loss";
+const KEY_ADAM_PREVIOUS_LOSS: &str = "optimizer.adam.previous_loss";
+const KEY_ADAM_NO_IMPROVEMENT: &str = "optimizer.adam.no_improvement_count";
+const KEY_LBFGS_M: &str = "optimizer.lbfgs.approx_hessian_count";
+const KEY_LBFGS_BEST_LOSS: &str = "optimizer.lbfgs.best_loss";
+const KEY_LBFGS_STEP: &str = "optimizer.lbfgs.line_search_step";
+const KEY_LBFGS_J: &str = "optimizer.lbfgs.line_search_j";
+const KEY_LBFGS_K: &str = "optimizer.lbfgs.line_search_k";
+const KEY_LBFGS_END: &str = "optimizer.lbfgs.line_search_end";
+const KEY_LBFGS_NO_IMPROVEMENT: &str = "optimizer.lbfgs.no_improvement_count";
+
+const CHECKPOINT_VERSION: u32 = 0;
+
+impl<'ctx> Trainer<'ctx> {
+    fn type_name(&self) -> &'static str {
+        match self.config.optimizer {
+            Optimizer::Adam(_) => "adam",
+            Optimizer::Lbfgs(_) => "lbfgs",
+        }
+    }
+
+    /// The optimizer state and parameter tensors along with their names in a checkpoint.
+    /// Unnamed parameters are stored as `parameter.<index>`.
+    fn checkpoint_tensors(&self) -> Vec<(String, Tensor<'_>)> {
+        let (adam, lbfgs) = (&self.opt.adam, &self.opt.lbfgs);
+        let state = match self.config.optimizer {
+            Optimizer::Adam(_) => vec![
+                ("optimizer.adam.first_moments", adam.m),
+                ("optimizer.adam.second_moments", adam.v),
+                ("optimizer.adam.past_loss_values", adam.pf),
+            ],
+            Optimizer::Lbfgs(_) => vec![
+                ("optimizer.lbfgs.current_parameters", lbfgs.x),
+                ("optimizer.lbfgs.previous_parameters", lbfgs.xp),
+                ("optimizer.lbfgs.current_gradients", lbfgs.g),
+                ("optimizer.lbfgs.previous_gradients", lbfgs.gp),
+                ("optimizer.lbfgs.search_direction", lbfgs.d),
+                ("optimizer.lbfgs.past_loss_values", lbfgs.pf),
+                ("optimizer.lbfgs.memory_alpha", lbfgs.lmal),
+                ("optimizer.lbfgs.memory_ys", lbfgs.lmys),
+                ("optimizer.lbfgs.memory_s", lbfgs.lms),
+                ("optimizer.lbfgs.memory_y", lbfgs.lmy),
+            ],
+        };
+        // The past loss values only exist with `past > 0`.
+        let state = state.into_iter().filter_map(|(name, ptr)| {
+            let tensor = unsafe { Tensor::from_raw(&self.state, ptr) }?;
+            Some((name.to_string(), tensor))
+        });
+        let params = self.params.iter().enumerate().map(|(i, param)| {
+            let name = param.name();
+            let name = if name.is_empty() {
+                format!("parameter.{i}")
+            } else {
+                name.into_owned()
+            };
+            (name, *param)
+        });
+        state.chain(params).collect()
+    }
+
+    /// Writes the parameters and the optimizer state to a GGUF file at `path`, see the
+    /// [module docs](self). Parameters are stored under their names, which have to be unique.
+    pub fn save_checkpoint(&self, path: impl AsRef<Path>) -> Result<()> {
+        let tensors = self.checkpoint_tensors();
+        let mut gguf = GgufWriter::new(tensors.len())?;
+        let opt = &*self.opt;
+        gguf.set_u32(KEY_FILE_VERSION, CHECKPOINT_VERSION);
+        gguf.set_str(KEY_TYPE, self.type_name());
+        gguf.set_u32(KEY_PAST, opt.params.past as u32);
+        gguf.set_u64(KEY_NX, opt.nx as u64);
+        gguf.set_u32(KEY_ITER, opt.iter as u32);
+        gguf.set_bool(KEY_JUST_INITIALIZED, opt.just_initialized);
+        match self.config.optimizer {
+            Optimizer::Adam(_) => {
+                gguf.set_f32(KEY_ADAM_BEST_LOSS, opt.adam.fx_best);
+                gguf.set_f32(KEY_ADAM_PREVIOUS_LOSS, opt.adam.fx_prev);
+                gguf.set_u32(KEY_ADAM_NO_IMPROVEMENT, opt.adam.n_no_improvement as u32);
+            }
+            Optimizer::Lbfgs(_) => {
+                gguf.set_u32(KEY_LBFGS_M, opt.params.lbfgs.m as u32);
+                gguf.set_f32(KEY_LBFGS_BEST_LOSS, opt.lbfgs.fx_best);
+                gguf.set_f32(KEY_LBFGS_STEP, opt.lbfgs.step);
+                gguf.set_i32(KEY_LBFGS_J, opt.lbfgs.j);
+                gguf.set_i32(KEY_LBFGS_K, opt.lbfgs.k);
+                gguf.set_i32(KEY_LBFGS_END, opt.lbfgs.end);
+                gguf.set_u32(KEY_LBFGS_NO_IMPROVEMENT, opt.lbfgs.n_no_improvement as u32);
+            }
+        }
+        for (name, tensor) in tensors {
+            gguf.add_tensor(&name, tensor)?;
+        }
+        gguf.write(path.as_ref())
+    }
+
+    /// Restores the parameters and the optimizer state from a checkpoint written by
+    /// [`Trainer::save_checkpoint`]. The checkpoint has to be for the same optimizer, number
+    /// of parameters and `past` (and `m` for L-BFGS), with tensors of the same types and
+    /// shapes. Nothing is changed if it isn't.
+    pub fn load_checkpoint(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let file = GgufFile::open(path.as_ref())?;
+        fn get<T>(val: Option<T>, key: &str) -> Result<T> {
+            val.ok_or_else(|| Error::Checkpoint(format!("missing or invalid {key}")))
+        }
+        fn expect<T: PartialEq + fmt::Debug>(actual: T, expected: T, key: &str) -> Result<()> {
+            if actual == expected {
+                Ok(())
+            } else {
+                Err(Error::Checkpoint(format!(
+                    "{key} is {actual:?}, expected {expected:?}"
+                )))
+            }
+        }
+
+        let version = get(file.get_u32(KEY_FILE_VERSION), KEY_FILE_VERSION)?;
+        expect(version, CHECKPOINT_VERSION, KEY_FILE_VERSION)?;
+        let type_name = get(file.get_str(KEY_TYPE), KEY_TYPE)?;
+        expect(type_name.as_str(), self.type_name(), KEY_TYPE)?;
+        expect(
+            get(file.get_u64(KEY_NX), KEY_NX)?,
+            self.opt.nx as u64,
+            KEY_NX,
+        )?;
+        let past = self.opt.params.past as u32;
+        expect(get(file.get_u32(KEY_PAST), KEY_PAST)?, past, KEY_PAST)?;
+        let iter = get(file.get_u32(KEY_ITER), KEY_ITER)?;
+        let just_initialized = get(file.get_bool(KEY_JUST_INITIALIZED), KEY_JUST_INITIALIZED)?;
+
+        let mut adam = self.opt.adam;
+        let mut lbfgs = self.opt.lbfgs;
+        match self.config.optimizer {
+            Optimizer::Adam(_) => {
+                adam.fx_best = get(file.get_f32(KEY_ADAM_BEST_LOSS), KEY_ADAM_BEST_LOSS)?;
+                adam.fx_prev = get(file.get_f32(KEY_ADAM_PREVIOUS_LOSS), KEY_ADAM_PREVIOUS_LOSS)?;
+                let n = get(
+                    file.get_u32(KEY_ADAM_NO_IMPROVEMENT),
+                    KEY_ADAM_NO_IMPROVEMENT,
+                )?;
+                adam.n_no_improvement = n as c_int;
+            }
+            Optimizer::Lbfgs(_) => {
+                let m = self.opt.params.lbfgs.m as u32;
+                expect(get(file.get_u32(KEY_LBFGS_M), KEY_LBFGS_M)?, m, KEY_LBFGS_M)?;
+                lbfgs.fx_best = get(file.get_f32(KEY_LBFGS_BEST_LOSS), KEY_LBFGS_BEST_LOSS)?;
+                lbfgs.step = get(file.get_f32(KEY_LBFGS_STEP), KEY_LBFGS_STEP)?;
+                lbfgs.j = get(file.get_i32(KEY_LBFGS_J), KEY_LBFGS_J)?;
+                lbfgs.k = get(file.get_i32(KEY_LBFGS_K), KEY_LBFGS_K)?;
+                lbfgs.end = get(file.get_i32(KEY_LBFGS_END), KEY_LBFGS_END)?;
+                let n = get(
+                    file.get_u32(KEY_LBFGS_NO_IMPROVEMENT),
+                    KEY_LBFGS_NO_IMPROVEMENT,
+                )?;
+                lbfgs.n_no_improvement = n as c_int;
+            }
+        }
+
+        // Check every tensor before copying any of them.
+        let tensors = self
+            .checkpoint_tensors()
+            .into_iter()
+            .map(|(name, dst)| {
+                let src = get(file.tensor(&name), &name)?;
+                expect(src.type_(), dst.type_(), &name)?;
+                expect(src.ne(), dst.ne(), &name)?;
+                Ok((src, dst))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        ensure_writable(tensors.iter().map(|(_, dst)| dst.data_range()))?;
+        for (src, dst) in tensors {
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    src.data_ptr().cast::<u8>(),
+                    dst.data_ptr().cast::<u8>(),
+                    dst.nbytes(),
+                )
+            };
+        }
+        self.opt.iter = iter as c_int;
+        self.opt.just_initialized = just_initialized;
+        self.opt.adam = adam;
+        self.opt.lbfgs = lbfgs;
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Trainer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Trainer")
+            .field("loss", &self.loss)
+            .field("params", &self.params.len())
+            .field("config", &self.config)
+            .field("iter", &self.iter())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Memory `ggml_opt_init` needs for the state of `nx` parameters.
+fn state_size(params: &ggml_opt_params, nx: i64) -> usize {
+    let (nx, past) = (nx as usize, params.past as usize);
+    let (n_tensors, n_floats) = if params.type_ == ggml_opt_type_GGML_OPT_TYPE_ADAM {
+        // g, m, v and pf
+        (4, 3 * nx + past)
+    } else {
+        // x, xp, g, gp, d, pf, lmal, lmys, lms and lmy
+        let m = params.lbfgs.m as usize;
+        (10, 5 * nx + past + 2 * m + 2 * m * nx)
+    };
+    let overhead = unsafe { ggml_tensor_overhead() } + GGML_MEM_ALIGN as usize;
+    n_tensors * overhead + n_floats * std::mem::size_of::<f32>()
+}
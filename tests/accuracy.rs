@@ -0,0 +1,96 @@
+use ggml_sys_bleedingedge::{accuracy::*, *};
+
+fn stats(report: &Report, source: &str, type_: ggml_type) -> Stats {
+    report
+        .entries
+        .iter()
+        .find(|entry| entry.source == source && entry.stats.type_ == type_)
+        .unwrap_or_else(|| panic!("No {source} entry for type {type_}"))
+        .stats
+}
+
+#[test]
+fn accuracy_report_types() {
+    let types = report_types();
+    for type_ in [
+        ggml_type_GGML_TYPE_F32,
+        ggml_type_GGML_TYPE_F16,
+        ggml_type_GGML_TYPE_Q4_0,
+        ggml_type_GGML_TYPE_Q6_K,
+        ggml_type_GGML_TYPE_IQ2_XXS,
+        ggml_type_GGML_TYPE_IQ4_XS,
+    ] {
+        assert!(types.contains(&type_), "{type_} missing");
+    }
+    assert!(!types.contains(&ggml_type_GGML_TYPE_I32));
+}
+
+#[test]
+fn accuracy_compare() {
+    let stats = Stats::compare(ggml_type_GGML_TYPE_F32, &[1.0, 2.0], &[1.0, 2.0]);
+    assert_eq!(stats.rmse, 0.0);
+    assert_eq!(stats.max_error, 0.0);
+    assert!((stats.cosine - 1.0).abs() < 1e-12);
+    assert_eq!(stats.bits_per_weight, 32.0);
+
+    let stats = Stats::compare(ggml_type_GGML_TYPE_F32, &[1.0, 0.0], &[0.0, 1.0]);
+    assert_eq!(stats.rmse, 1.0);
+    assert_eq!(stats.max_error, 1.0);
+    assert_eq!(stats.cosine, 0.0);
+}
+
+#[test]
+fn accuracy_distributions_are_reproducible() {
+    for dist in Distribution::ALL {
+        let sample = dist.sample(1000, 7);
+        assert_eq!(sample, dist.sample(1000, 7));
+        assert_ne!(sample, dist.sample(1000, 8));
+        let mean = sample.iter().sum::<f32>() / 1000.0;
+        assert!(mean.abs() < 0.2, "{} mean {mean}", dist.name());
+    }
+    let uniform = Distribution::Uniform.sample(1000, 0);
+    assert!(uniform.iter().all(|x| (-1.0..1.0).contains(x)));
+}
+
+#[test]
+fn accuracy_report() {
+    let types = report_types();
+    let mut report = Report::new();
+    report.add_distributions(256, 8, &types).unwrap();
+    assert_eq!(report.entries.len(), types.len() * Distribution::ALL.len());
+
+    let q8 = stats(&report, "normal", ggml_type_GGML_TYPE_Q8_0);
+    let q4 = stats(&report, "normal", ggml_type_GGML_TYPE_Q4_0);
+    let iq2 = stats(&report, "normal", ggml_type_GGML_TYPE_IQ2_XXS);
+    assert_eq!(q8.bits_per_weight, 8.5);
+    assert_eq!(q4.bits_per_weight, 4.5);
+    assert!(q8.rmse < q4.rmse && q4.rmse < iq2.rmse);
+    assert!(q8.cosine > 0.999 && iq2.cosine > 0.8);
+    assert_eq!(stats(&report, "uniform", ggml_type_GGML_TYPE_F32).rmse, 0.0);
+
+    let table = report.to_table();
+    assert!(table.starts_with("source"));
+    assert_eq!(table.lines().count(), report.entries.len() + 1);
+    let json = report.to_json();
+    assert!(
+        json.contains("{\"source\": \"laplace\", \"type\": \"q4_K\", \"bits_per_weight\": 4.5,")
+    );
+}
+
+#[test]
+fn accuracy_skips_unaligned_rows() {
+    let src = Distribution::Normal.sample(64 * 2, 0);
+    let types = [ggml_type_GGML_TYPE_Q8_0, ggml_type_GGML_TYPE_Q4_K];
+    let mut report = Report::new();
+    report.add("small", &src, &[64, 2], &types).unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].stats.type_, ggml_type_GGML_TYPE_Q8_0);
+}
+
+#[test]
+fn accuracy_missing_gguf() {
+    let err = Report::new()
+        .add_gguf("does-not-exist.gguf", &report_types(), |_| true)
+        .unwrap_err();
+    assert!(matches!(err, Error::GgufLoad { .. }));
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "ndarray")]
+
+use ggml_sys_bleedingedge::*;
+use ndarray::{arr2, Array, ArrayD, IxDyn};
+
+#[test]
+fn array_axis_order() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = arr2(&[
+        [0.0f32, 1.0, 2.0, 3.0],
+        [4.0, 5.0, 6.0, 7.0],
+        [8.0, 9.0, 10.0, 11.0],
+    ]);
+    let t = ctx.new_tensor_from_array(&a).unwrap();
+    assert_eq!(t.ne(), [4, 3, 1, 1]);
+    // The last array axis is ne[0].
+    assert_eq!(t.get_f32([3, 1]).unwrap(), a[[1, 3]]);
+
+    let data = t.as_array::<f32>().unwrap();
+    let view = data.view();
+    assert_eq!(view.shape(), &[3, 4]);
+    assert_eq!(view, a.into_dyn());
+}
+
+#[test]
+fn array_strided_views() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = Array::from_shape_fn((2, 3), |(i, j)| (i * 3 + j) as i32);
+    let t = ctx.new_tensor_from_array(&a).unwrap();
+
+    // A transpose is a zero-copy view with swapped strides.
+    let transposed = t.transpose();
+    let data = transposed.as_array::<i32>().unwrap();
+    let view = data.view();
+    assert_eq!(view, a.t().into_dyn());
+    assert_eq!(view.as_ptr(), t.data_ptr().cast::<i32>().cast_const());
+
+    // The second column.
+    let cols = t.view(&[1, 2], &[3 * 4], 4);
+    assert_eq!(
+        cols.as_array::<i32>().unwrap().view(),
+        arr2(&[[1], [4]]).into_dyn()
+    );
+
+    assert!(matches!(
+        t.as_array::<f32>(),
+        Err(Error::TypeMismatch { .. })
+    ));
+
+    // The transposed view still borrows the data.
+    assert!(matches!(
+        t.copy_from_slice(&[0i32; 6]),
+        Err(Error::DataBorrowed)
+    ));
+    drop(data);
+    t.copy_from_slice(&[0i32; 6]).unwrap();
+}
+
+#[test]
+fn array_mut_and_copy_in() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 2, 3, 4).unwrap();
+    t.copy_from_array(&ArrayD::from_elem(IxDyn(&[4, 3, 2]), 1.0f32))
+        .unwrap();
+    let mut array = t.as_array_mut::<f32>().unwrap();
+    array.view_mut()[[3, 2, 1]] = 5.0;
+    // Nothing else can read or write the data while it's mutably borrowed.
+    assert!(matches!(t.get_f32([0]), Err(Error::DataBorrowed)));
+    assert!(matches!(t.as_slice::<f32>(), Err(Error::DataBorrowed)));
+    assert!(matches!(t.as_array::<f32>(), Err(Error::DataBorrowed)));
+    assert!(matches!(t.as_array_mut::<f32>(), Err(Error::DataBorrowed)));
+    assert!(matches!(t.set_f32([0], 1.0), Err(Error::DataBorrowed)));
+    drop(array);
+    assert_eq!(t.get_f32([1, 2, 3]).unwrap(), 5.0);
+    let data = t.as_slice::<f32>().unwrap();
+    assert!(matches!(t.as_array_mut::<f32>(), Err(Error::DataBorrowed)));
+    drop(data);
+
+    // Non-standard layouts are copied in logical order.
+    let a = arr2(&[[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let t = ctx.new_tensor_from_array(&a.t()).unwrap();
+    assert_eq!(
+        t.as_slice::<f32>().unwrap(),
+        &[1.0, 3.0, 5.0, 2.0, 4.0, 6.0]
+    );
+
+    assert!(matches!(
+        t.copy_from_array(&a),
+        Err(Error::InvalidShape { .. })
+    ));
+}
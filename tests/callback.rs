@@ -0,0 +1,76 @@
+use std::{
+    ffi::CString,
+    panic,
+    sync::{Arc, Mutex},
+};
+
+use ggml_sys_bleedingedge::{callback::*, *};
+
+#[test]
+fn callback_log() {
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let sink = lines.clone();
+    let mut cb = Callback::<LogFn>::new(Box::new(move |level, text: &str| {
+        sink.lock().unwrap().push((level, text.to_string()))
+    }));
+    let text = CString::new("hello\n").unwrap();
+    unsafe {
+        log_trampoline(
+            ggml_log_level_GGML_LOG_LEVEL_INFO,
+            text.as_ptr(),
+            cb.userdata(),
+        )
+    };
+    assert_eq!(
+        *lines.lock().unwrap(),
+        [(ggml_log_level_GGML_LOG_LEVEL_INFO, String::from("hello\n"))]
+    );
+}
+
+#[test]
+fn callback_opt_panic_cancels() {
+    let mut cb = Callback::<OptFn>::new(Box::new(|step, sched: &mut f32, _cancel: &mut bool| {
+        *sched = 0.5;
+        if step > 1 {
+            panic!("step {step}");
+        }
+    }));
+    let (mut sched, mut cancel) = (1.0, false);
+    unsafe { opt_trampoline(cb.userdata(), 1, &mut sched, &mut cancel) };
+    assert_eq!((sched, cancel), (0.5, false));
+    cb.resume_panic();
+
+    unsafe { opt_trampoline(cb.userdata(), 2, &mut sched, &mut cancel) };
+    assert!(cancel);
+    let payload = panic::catch_unwind(panic::AssertUnwindSafe(|| cb.resume_panic())).unwrap_err();
+    assert_eq!(payload.downcast_ref::<String>().unwrap(), "step 2");
+    // The panic is only raised once.
+    cb.resume_panic();
+}
+
+#[test]
+#[should_panic(expected = "abort callback")]
+fn callback_abort_panic_is_raised_after_compute() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&a.sqr()).unwrap();
+    graph.set_abort_callback(|| panic!("abort callback"));
+    let _ = graph.compute(1);
+}
+
+#[cfg(feature = "llamacpp_api")]
+#[test]
+fn callback_progress() {
+    let mut seen = Vec::new();
+    let mut cb = Callback::<ProgressFn>::new(Box::new(|progress| {
+        seen.push(progress);
+        progress < 0.5
+    }));
+    unsafe {
+        assert!(progress_trampoline(0.25, cb.userdata()));
+        assert!(!progress_trampoline(0.75, cb.userdata()));
+    }
+    drop(cb);
+    assert_eq!(seen, [0.25, 0.75]);
+}
@@ -0,0 +1,139 @@
+use std::path::PathBuf;
+
+use ggml_sys_bleedingedge::{train::*, *};
+
+/// `sum((w * x - target)^2)` with `w` as the parameter.
+fn problem(ctx: &Context) -> (Tensor<'_>, Tensor<'_>) {
+    let w = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, 4)
+        .unwrap()
+        .set_param()
+        .unwrap()
+        .set_name("w");
+    w.copy_from_slice(&[0.5f32, -0.25, 1.0, 0.0]).unwrap();
+    let x = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    x.copy_from_slice(&[1.0f32, 2.0, -1.5, 0.5]).unwrap();
+    let target = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    target.copy_from_slice(&[2.0f32, -1.0, 3.0, 1.0]).unwrap();
+    (w, (w * x - target).sqr().sum())
+}
+
+fn adam(n_iter: usize) -> OptConfig {
+    OptConfig {
+        n_threads: 1,
+        past: 3,
+        max_no_improvement: 0,
+        ..OptConfig::adam(AdamConfig {
+            n_iter,
+            alpha: 0.05,
+            // Never stop early, the runs have to do the same number of iterations.
+            eps_f: 0.0,
+            ..Default::default()
+        })
+    }
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ggml-{name}-{}.gguf", std::process::id()))
+}
+
+#[test]
+fn checkpoint_resume_matches_uninterrupted() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (w, loss) = problem(&ctx);
+    let mut trainer = Trainer::new(loss, adam(20)).unwrap();
+    trainer.train().unwrap();
+    assert_eq!(trainer.iter(), 20);
+    let expected = w.as_slice::<f32>().unwrap().to_vec();
+
+    let path = temp_path("resume");
+    {
+        let ctx = Context::new(16 * 1024 * 1024).unwrap();
+        let (_, loss) = problem(&ctx);
+        let mut trainer = Trainer::new(loss, adam(10)).unwrap();
+        trainer.train().unwrap();
+        trainer.save_checkpoint(&path).unwrap();
+    }
+
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (w, loss) = problem(&ctx);
+    let mut trainer = Trainer::new(loss, adam(10)).unwrap();
+    trainer.load_checkpoint(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(trainer.iter(), 10);
+    assert_ne!(w.as_slice::<f32>().unwrap(), expected);
+    trainer.train().unwrap();
+    assert_eq!(trainer.iter(), 20);
+    assert_eq!(w.as_slice::<f32>().unwrap(), expected);
+}
+
+#[test]
+fn checkpoint_lbfgs_round_trip() {
+    let config = OptConfig {
+        n_threads: 1,
+        ..OptConfig::lbfgs(LbfgsConfig {
+            n_iter: 2,
+            ..Default::default()
+        })
+    };
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (w, loss) = problem(&ctx);
+    let mut trainer = Trainer::new(loss, config).unwrap();
+    trainer.train().unwrap();
+    let path = temp_path("lbfgs");
+    trainer.save_checkpoint(&path).unwrap();
+
+    let ctx2 = Context::new(16 * 1024 * 1024).unwrap();
+    let (w2, loss2) = problem(&ctx2);
+    let mut resumed = Trainer::new(loss2, config).unwrap();
+    resumed.load_checkpoint(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(w2.as_slice::<f32>().unwrap(), w.as_slice::<f32>().unwrap());
+
+    let (a, b) = (trainer.opt_context(), resumed.opt_context());
+    assert_eq!(a.iter, b.iter);
+    assert_eq!(
+        (a.lbfgs.j, a.lbfgs.k, a.lbfgs.end),
+        (b.lbfgs.j, b.lbfgs.k, b.lbfgs.end)
+    );
+    assert_eq!(a.lbfgs.step, b.lbfgs.step);
+    let memory = |opt: &ggml_opt_context| {
+        let lms = opt.lbfgs.lms;
+        let len = unsafe { ggml_nelements(lms) } as usize;
+        unsafe { std::slice::from_raw_parts((*lms).data.cast::<f32>(), len) }.to_vec()
+    };
+    assert_eq!(memory(a), memory(b));
+}
+
+#[test]
+fn checkpoint_rejects_mismatch() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (_, loss) = problem(&ctx);
+    let trainer = Trainer::new(loss, adam(1)).unwrap();
+    let path = temp_path("mismatch");
+    trainer.save_checkpoint(&path).unwrap();
+
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (w, loss) = problem(&ctx);
+    let mut lbfgs = Trainer::new(loss, OptConfig::lbfgs(LbfgsConfig::default())).unwrap();
+    assert!(matches!(
+        lbfgs.load_checkpoint(&path),
+        Err(Error::Checkpoint(_))
+    ));
+
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (_, loss) = problem(&ctx);
+    let mut trainer = Trainer::new(loss, OptConfig { past: 0, ..adam(1) }).unwrap();
+    assert!(matches!(
+        trainer.load_checkpoint(&path),
+        Err(Error::Checkpoint(_))
+    ));
+    std::fs::remove_file(&path).unwrap();
+    // Nothing was loaded.
+    assert_eq!(w.as_slice::<f32>().unwrap(), [0.5, -0.25, 1.0, 0.0]);
+
+    assert_eq!(
+        trainer.load_checkpoint(&path),
+        Err(Error::GgufLoad { path: path.clone() })
+    );
+}
@@ -0,0 +1,52 @@
+use ggml_sys_bleedingedge::*;
+
+#[test]
+fn context_reports_memory() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    assert_eq!(ctx.mem_size(), 1024 * 1024);
+    assert_eq!(ctx.used_mem(), 0);
+
+    ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 16, 16).unwrap();
+    assert!(ctx.used_mem() >= 16 * 16 * 4);
+    assert_eq!(ctx.tensors().count(), 1);
+}
+
+#[test]
+fn context_out_of_memory() {
+    let ctx = Context::new(4096).unwrap();
+    assert!(matches!(
+        ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4096),
+        Err(Error::OutOfMemory { .. })
+    ));
+    // Nothing should have been allocated.
+    assert_eq!(ctx.used_mem(), 0);
+}
+
+#[test]
+fn context_no_alloc() {
+    let ctx = Context::new_no_alloc(unsafe { ggml_tensor_overhead() } * 4).unwrap();
+    let t = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 1 << 20).unwrap();
+    assert!(unsafe { (*t.as_ptr()).data.is_null() });
+}
+
+#[test]
+fn context_invalid_shape() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    // Q8_0 blocks are 32 elements.
+    assert!(matches!(
+        ctx.new_tensor_1d(ggml_type_GGML_TYPE_Q8_0, 31),
+        Err(Error::InvalidShape { .. })
+    ));
+    assert!(ctx.new_tensor(ggml_type_GGML_TYPE_F32, &[]).is_err());
+    assert!(ctx
+        .new_tensor(ggml_type_GGML_TYPE_F32, &[1, 1, 1, 1, 1])
+        .is_err());
+}
+
+#[test]
+fn contexts_are_released() {
+    // More than GGML_MAX_CONTEXTS in sequence is fine as long as they're dropped.
+    for _ in 0..GGML_MAX_CONTEXTS * 2 {
+        Context::new(1024).unwrap();
+    }
+}
@@ -0,0 +1,155 @@
+use ggml_sys_bleedingedge::{cost::OpCost, *};
+
+fn cost(ctx: &Context, out: Tensor) -> CostReport {
+    let mut graph = Graph::new(ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    graph.cost()
+}
+
+fn op(report: &CostReport, op: &str) -> OpCost {
+    report
+        .by_op()
+        .into_iter()
+        .find(|cost| cost.op == op)
+        .unwrap()
+}
+
+#[test]
+fn cost_mul_mat() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let (k, m, n) = (64, 32, 4);
+    let x = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, k, n).unwrap();
+    let read = |type_| {
+        let w = ctx.new_tensor_2d(type_, k, m).unwrap();
+        let report = cost(&ctx, w.matmul(x));
+        assert_eq!(report.nodes.len(), 1);
+        assert_eq!(report.flops(), 2 * (k * m * n) as u64);
+        assert_eq!(report.bytes_written(), (m * n * 4) as u64);
+        assert_eq!(report.leaf_memory, (w.nbytes() + x.nbytes()) as u64);
+        report.bytes_read()
+    };
+    assert_eq!(
+        read(ggml_type_GGML_TYPE_F32),
+        (k * m * 4 + k * n * 4) as u64
+    );
+    // 34 bytes per block of 32.
+    assert_eq!(
+        read(ggml_type_GGML_TYPE_Q8_0),
+        (m * 2 * 34 + k * n * 4) as u64
+    );
+}
+
+#[test]
+fn cost_peak_memory() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 256).unwrap();
+    let b = a.sqr();
+    let c = b.sqr();
+    let report = cost(&ctx, c.reshape(&[16, 16]).sqr());
+    let ops = report.nodes.iter().map(|node| node.op).collect::<Vec<_>>();
+    assert_eq!(ops, ["SQR", "SQR", "RESHAPE", "SQR"]);
+    // `b` is freed once `c` is computed, `c` once the last node (read through the reshape) is.
+    let live = report
+        .nodes
+        .iter()
+        .map(|node| node.live_memory)
+        .collect::<Vec<_>>();
+    assert_eq!(live, [1024, 2048, 1024, 2048]);
+    assert_eq!((report.peak_memory, report.peak_node), (2048, Some(1)));
+    assert_eq!(report.leaf_memory, 1024);
+
+    let reshape = &report.nodes[2];
+    assert_eq!(
+        (reshape.flops, reshape.bytes_read, reshape.bytes_written),
+        (0, 0, 0)
+    );
+    assert_eq!(report.flops(), 3 * 256);
+}
+
+#[test]
+fn cost_by_op() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let w1 = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 8, 16).unwrap();
+    let w2 = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 16, 8).unwrap();
+    let x = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 8, 2).unwrap();
+    let h = w1.matmul(x).relu();
+    let report = cost(&ctx, w2.matmul(h).softmax_ext(None, 1.0, 0.0));
+
+    let ops = report.by_op();
+    let names = ops.iter().map(|cost| cost.op).collect::<Vec<_>>();
+    assert_eq!(names, ["MUL_MAT", "RELU", "SOFT_MAX"]);
+    let mul_mat = op(&report, "MUL_MAT");
+    assert_eq!(mul_mat.count, 2);
+    assert_eq!(mul_mat.flops, 2 * (8 * 16 * 2) * 2);
+    assert_eq!(op(&report, "RELU").flops, 16 * 2);
+    assert_eq!(
+        ops.iter().map(|cost| cost.flops).sum::<u64>(),
+        report.flops()
+    );
+
+    let table = report.to_table();
+    assert!(table.starts_with("op         count"));
+    assert!(table.contains("\nMUL_MAT        2"));
+    assert!(table.contains(&format!(
+        "\ntotal          4  {:>16}  100.0%",
+        report.flops()
+    )));
+    assert!(table.contains(&format!(
+        "peak intermediate memory: {} bytes",
+        report.peak_memory
+    )));
+    assert_eq!(report.to_string(), table);
+
+    let json = report.to_json();
+    assert!(json.starts_with(&format!("{{\"flops\": {}, ", report.flops())));
+    assert!(json.contains(&format!(
+        "\n  {{\"op\": \"MUL_MAT\", \"count\": 2, \"flops\": {}, \"bytes_read\": {}, \
+         \"bytes_written\": {}}},",
+        mul_mat.flops, mul_mat.bytes_read, mul_mat.bytes_written
+    )));
+    assert!(json.ends_with("}\n]}\n"));
+}
+
+#[test]
+fn cost_flash_attn_ext() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let (d, n_q, n_kv, heads) = (64, 4, 16, 2);
+    let q = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, d, n_q, heads)
+        .unwrap();
+    let k = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F16, d, n_kv, heads)
+        .unwrap();
+    let v = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F16, d, n_kv, heads)
+        .unwrap();
+    let out = q.flash_attn_ext(k, v, None, 0.125, 0.0);
+    let report = cost(&ctx, out);
+    let scores = (n_q * n_kv * heads) as u64;
+    assert_eq!(report.flops(), scores * (4 * d as u64 + 5));
+    assert_eq!(
+        report.bytes_read(),
+        (q.nbytes() + k.nbytes() + v.nbytes()) as u64
+    );
+    assert_eq!(report.bytes_written(), out.nbytes() as u64);
+}
+
+#[test]
+fn cost_conv_2d() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let (kw, kh, ic, oc) = (3, 3, 4, 8);
+    let kernel = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F16, kw, kh, ic, oc)
+        .unwrap();
+    let input = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 10, 10, ic, 2)
+        .unwrap();
+    let out = kernel.conv_2d(input, [1, 1], [0, 0], [1, 1]);
+    assert_eq!(out.ne(), [8, 8, oc, 2]);
+    // The convolution is an `im2col` copy and a `mul_mat`, which does all the work.
+    let report = cost(&ctx, out);
+    assert_eq!(op(&report, "IM2COL").flops, 0);
+    let flops = 2 * (kw * kh * ic * oc * 8 * 8 * 2) as u64;
+    assert_eq!(op(&report, "MUL_MAT").flops, flops);
+    assert_eq!(report.flops(), flops);
+}
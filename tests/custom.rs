@@ -0,0 +1,104 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use ggml_sys_bleedingedge::*;
+
+#[test]
+fn custom_op_split_between_threads() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 7).unwrap();
+    a.copy_from_slice(&(0..28).map(|i| i as f32).collect::<Vec<_>>())
+        .unwrap();
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let op_calls = calls.clone();
+    let doubled = a
+        .map_custom1(
+            Arc::new(move |dst, a, _ith, _nth| {
+                op_calls.fetch_add(1, Ordering::Relaxed);
+                assert_eq!((a.ne(), a.nrows()), (dst.ne(), 7));
+                let rows = dst.rows();
+                let src = a.as_slice::<f32>().unwrap();
+                let src = &src[rows.start * 4..rows.end * 4];
+                for (d, s) in dst.as_mut_slice::<f32>().unwrap().iter_mut().zip(src) {
+                    *d = s * 2.0;
+                }
+            }),
+            None,
+        )
+        .unwrap();
+
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&doubled).unwrap();
+    graph.compute(3).unwrap();
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    assert_eq!(
+        doubled.as_slice::<f32>().unwrap(),
+        (0..28).map(|i| i as f32 * 2.0).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn custom_op_three_operands() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let [a, b, c] = [1.0f32, 2.0, 3.0].map(|val| {
+        let t = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 8).unwrap();
+        t.copy_from_slice(&[val; 8]).unwrap();
+        t
+    });
+    let fma = a
+        .map_custom3(
+            b,
+            c,
+            Arc::new(|dst, a, b, c, _ith, _nth| {
+                let (a, b, c) = (
+                    a.as_slice::<f32>().unwrap(),
+                    b.as_slice::<f32>().unwrap(),
+                    c.as_slice::<f32>().unwrap(),
+                );
+                for (i, d) in dst.as_mut_slice::<f32>().unwrap().iter_mut().enumerate() {
+                    *d = a[i] * b[i] + c[i];
+                }
+            }),
+            Some(1),
+        )
+        .unwrap();
+
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&fma).unwrap();
+    graph.compute(4).unwrap();
+    assert_eq!(fma.as_slice::<f32>().unwrap(), &[5.0; 8]);
+}
+
+#[test]
+#[should_panic(expected = "panic in custom op")]
+fn custom_op_panic_is_raised_after_compute() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 8).unwrap();
+    let b = a
+        .map_custom1(Arc::new(|_, _, _, _| panic!("panic in custom op")), None)
+        .unwrap();
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&b).unwrap();
+    let _ = graph.compute(2);
+}
+
+#[test]
+#[should_panic(expected = "panic in other context")]
+fn custom_op_panic_from_other_context() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let other = Context::new(1024 * 1024).unwrap();
+    let a = other.new_tensor_1d(ggml_type_GGML_TYPE_F32, 8).unwrap();
+    let b = a
+        .map_custom1(
+            Arc::new(|_, _, _, _| panic!("panic in other context")),
+            None,
+        )
+        .unwrap();
+    // The graph lives in `ctx`, the operation (and its userdata) in `other`.
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&b).unwrap();
+    let _ = graph.compute(1);
+}
@@ -0,0 +1,152 @@
+use ggml_sys_bleedingedge::{dump::GraphDump, *};
+
+/// `relu(w x^T)` with `x` reshaped and transposed first.
+fn build(ctx: &Context) -> Graph<'_> {
+    let w = ctx
+        .new_tensor_2d(ggml_type_GGML_TYPE_F16, 2, 2)
+        .unwrap()
+        .set_name("w");
+    let x = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, 6)
+        .unwrap()
+        .set_name("x");
+    let t = x.reshape(&[3, 2]).transpose().cont();
+    let out = w.matmul(t).relu().set_name("out");
+    let mut graph = Graph::new(ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    graph
+}
+
+fn find<'a>(dump: &'a GraphDump, name: &str) -> &'a dump::DumpNode {
+    dump.nodes.iter().find(|node| node.name == name).unwrap()
+}
+
+fn ops(dump: &GraphDump) -> Vec<&str> {
+    dump.nodes.iter().map(|node| node.op).collect()
+}
+
+#[test]
+fn dump_collects_tensors() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let graph = build(&ctx);
+    let dump = graph.dump(DumpOptions::default());
+    assert_eq!(
+        ops(&dump),
+        [
+            "NONE",
+            "NONE",
+            "RESHAPE",
+            "TRANSPOSE",
+            "CONT",
+            "MUL_MAT",
+            "RELU"
+        ]
+    );
+    for (i, node) in dump.nodes.iter().enumerate() {
+        assert_eq!(node.id, i);
+        assert_eq!(node.leaf, i < 2);
+        assert!(!node.param);
+        assert!(node.sources.iter().all(|(_, src)| *src < i));
+    }
+
+    let (w, x) = (find(&dump, "w"), find(&dump, "x"));
+    assert_eq!((w.type_name, &w.shape[..]), ("f16", &[2, 2][..]));
+    assert_eq!((x.type_name, &x.shape[..]), ("f32", &[6][..]));
+    assert_eq!(dump.nodes[2].sources, [(0, x.id)]);
+    assert_eq!(dump.nodes[2].shape, [3, 2]);
+    assert_eq!(dump.nodes[3].sources, [(0, 2)]);
+    assert_eq!(dump.nodes[3].shape, [2, 3]);
+    assert_eq!(dump.nodes[5].sources, [(0, w.id), (1, 4)]);
+    let out = find(&dump, "out");
+    assert_eq!(
+        (out.id, out.op, &out.sources[..]),
+        (6, "RELU", &[(0, 5)][..])
+    );
+}
+
+#[test]
+fn dump_collapses_views_and_reshapes() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let graph = build(&ctx);
+
+    let options = DumpOptions {
+        collapse_views: true,
+        ..Default::default()
+    };
+    let dump = graph.dump(options);
+    assert_eq!(
+        ops(&dump),
+        ["NONE", "NONE", "RESHAPE", "CONT", "MUL_MAT", "RELU"]
+    );
+    assert_eq!(dump.nodes[3].sources, [(0, 2)]);
+
+    let options = DumpOptions {
+        collapse_reshapes: true,
+        ..options
+    };
+    let dump = graph.dump(options);
+    assert_eq!(ops(&dump), ["NONE", "NONE", "CONT", "MUL_MAT", "RELU"]);
+    // The copy reads `x` directly.
+    assert_eq!(dump.nodes[2].sources, [(0, find(&dump, "x").id)]);
+    assert_eq!(dump.nodes[3].sources, [(0, find(&dump, "w").id), (1, 2)]);
+}
+
+#[test]
+fn dump_formats() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let graph = build(&ctx);
+    let options = DumpOptions {
+        collapse_views: true,
+        collapse_reshapes: true,
+    };
+    let dump = graph.dump(options);
+    let (w, x) = (find(&dump, "w").id, find(&dump, "x").id);
+
+    let dot = graph.to_dot(options);
+    assert!(dot.starts_with("digraph G {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains(&format!(
+        "  n{x} [shape = box, style = filled, fillcolor = pink, label = \"x\\nNONE\\nf32 [6]\"];\n"
+    )));
+    assert!(dot.contains("label = \"out\\nRELU\\nf32 [2, 3]\"];\n"));
+    assert!(dot.contains(&format!("  n{w} -> n3 [label = \"src0\"];\n")));
+    assert!(dot.contains("  n2 -> n3 [label = \"src1\"];\n"));
+
+    let json = graph.to_json(options);
+    assert!(json.starts_with("{\"nodes\": [\n"));
+    assert!(json.contains(&format!(
+        "{{\"id\": {x}, \"name\": \"x\", \"op\": \"NONE\", \"type\": \"f32\", \"shape\": [6], \
+         \"leaf\": true, \"param\": false, \"sources\": []}}"
+    )));
+    assert!(json.contains(
+        "{\"id\": 4, \"name\": \"out\", \"op\": \"RELU\", \"type\": \"f32\", \"shape\": [2, 3], \
+         \"leaf\": false, \"param\": false, \"sources\": [{\"slot\": 0, \"id\": 3}]}\n]}\n"
+    ));
+
+    let mermaid = graph.to_mermaid(options);
+    assert!(mermaid.starts_with("flowchart TD\n"));
+    assert!(mermaid.contains(&format!("  n{x}(\"x<br/>NONE<br/>f32 [6]\")\n")));
+    assert!(mermaid.contains("  n4[\"out<br/>RELU<br/>f32 [2, 3]\"]\n"));
+    assert!(mermaid.contains("  n3 --> n4\n"));
+}
+
+#[test]
+fn dump_escapes_names() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, 2)
+        .unwrap()
+        .set_name("a \"b\" <c>\n#d\\");
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&a.sqr()).unwrap();
+    let options = DumpOptions::default();
+    assert!(graph
+        .to_dot(options)
+        .contains("label = \"a \\\"b\\\" <c>\\n#d\\\\\\nNONE"));
+    assert!(graph
+        .to_json(options)
+        .contains("\"name\": \"a \\\"b\\\" <c>\\n#d\\\\\""));
+    assert!(graph
+        .to_mermaid(options)
+        .contains("(\"a #quot;b#quot; #lt;c#gt;<br/>#35;d\\<br/>NONE"));
+}
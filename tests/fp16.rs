@@ -0,0 +1,52 @@
+#![cfg(feature = "half")]
+
+use ggml_sys_bleedingedge::{fp16, *};
+use half::{bf16, f16};
+
+#[test]
+fn fp16_scalar_conversions_are_bitwise() {
+    let val = f16::from_f32(1.5);
+    assert_eq!(fp16::f16_to_ggml(val), val.to_bits());
+    assert_eq!(fp16::f16_from_ggml(unsafe { ggml_fp32_to_fp16(1.5) }), val);
+
+    let val = bf16::from_f32(-2.25);
+    let ggml = ggml_bf16_t::from(val);
+    assert_eq!(ggml.bits, val.to_bits());
+    assert_eq!(bf16::from(ggml), val);
+    assert_eq!(unsafe { ggml_bf16_to_fp32(ggml) }, -2.25);
+
+    let raw = [ggml.bits; 3].map(|bits| ggml_bf16_t { bits });
+    assert_eq!(fp16::bf16_slice(&raw), &[val; 3]);
+    let bytes: &[u8] = bytemuck::cast_slice(&raw);
+    assert_eq!(bytes.len(), 6);
+}
+
+#[test]
+fn fp16_slice_conversions_match_half() {
+    // Long enough to go through the SIMD paths as well as the scalar tail.
+    let src = (0..1027)
+        .map(|i| (i as f32 * 0.37).sin() * 100.0)
+        .collect::<Vec<_>>();
+
+    let mut halfs = vec![f16::ZERO; src.len()];
+    fp16::f32_to_f16_slice(&src, &mut halfs);
+    assert!(halfs.iter().zip(&src).all(|(h, f)| *h == f16::from_f32(*f)));
+    let mut back = vec![0.0; src.len()];
+    fp16::f16_to_f32_slice(&halfs, &mut back);
+    assert!(halfs.iter().zip(&back).all(|(h, f)| h.to_f32() == *f));
+
+    let mut bhalfs = vec![bf16::ZERO; src.len()];
+    fp16::f32_to_bf16_slice(&src, &mut bhalfs);
+    assert!(bhalfs
+        .iter()
+        .zip(&src)
+        .all(|(h, f)| *h == bf16::from_f32(*f)));
+    fp16::bf16_to_f32_slice(&bhalfs, &mut back);
+    assert!(bhalfs.iter().zip(&back).all(|(h, f)| h.to_f32() == *f));
+}
+
+#[test]
+#[should_panic(expected = "different lengths")]
+fn fp16_slice_length_mismatch() {
+    fp16::f32_to_f16_slice(&[1.0, 2.0], &mut [f16::ZERO]);
+}
@@ -0,0 +1,347 @@
+use ggml_sys_bleedingedge::{gradcheck::*, ops::RopeParams, *};
+
+const MEM_SIZE: usize = 64 * 1024 * 1024;
+
+/// Deterministic values in `[lo, hi)`, different for each `seed`.
+fn values(len: usize, seed: usize, lo: f32, hi: f32) -> Vec<f32> {
+    (0..len)
+        .map(|i| {
+            let t = ((i * 7 + seed * 13) as f32 * 0.618).sin() * 0.5 + 0.5;
+            lo + t * (hi - lo)
+        })
+        .collect()
+}
+
+fn tensor<'ctx>(ctx: &'ctx Context, shape: &[i64], seed: usize, lo: f32, hi: f32) -> Tensor<'ctx> {
+    let t = ctx.new_tensor(ggml_type_GGML_TYPE_F32, shape).unwrap();
+    t.copy_from_slice(&values(t.nelements() as usize, seed, lo, hi))
+        .unwrap();
+    t
+}
+
+fn param<'ctx>(ctx: &'ctx Context, shape: &[i64], seed: usize) -> Tensor<'ctx> {
+    tensor(ctx, shape, seed, -1.0, 1.0).set_param().unwrap()
+}
+
+/// Values away from zero, for operations with a kink there or a pole.
+fn positive<'ctx>(ctx: &'ctx Context, shape: &[i64], seed: usize) -> Tensor<'ctx> {
+    tensor(ctx, shape, seed, 0.5, 2.0).set_param().unwrap()
+}
+
+/// Values away from zero with alternating signs, for operations with a kink or a step at
+/// zero.
+fn signed<'ctx>(ctx: &'ctx Context, shape: &[i64], seed: usize) -> Tensor<'ctx> {
+    let t = tensor(ctx, shape, seed, 0.5, 2.0);
+    let data = t.as_slice::<f32>().unwrap().to_vec();
+    let data = data
+        .iter()
+        .enumerate()
+        .map(|(i, x)| if i % 2 == 0 { *x } else { -x })
+        .collect::<Vec<_>>();
+    t.copy_from_slice(&data).unwrap();
+    t.set_param().unwrap()
+}
+
+/// `sum(y * w)` with constant weights, so every element of `y` gets a different gradient.
+fn weighted(y: Tensor) -> Tensor {
+    let w = tensor(y.context(), y.shape(), 99, -1.0, 1.0);
+    (y.cont() * w).sum()
+}
+
+fn assert_grads(loss: Tensor, check: GradCheck) -> GradReport {
+    let report = check.check(loss).unwrap();
+    assert!(report.passed(), "{report}");
+    assert!(report.entries.iter().all(|entry| entry.checked > 0));
+    report
+}
+
+#[test]
+fn gradcheck_binary() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let (a, b) = (param(&ctx, &[4, 3], 1), param(&ctx, &[4, 3], 2));
+    assert_grads(weighted(a + b), GradCheck::default());
+    assert_grads(weighted(a - b), GradCheck::default());
+    assert_grads(weighted(a * b), GradCheck::default());
+    let c = positive(&ctx, &[4, 3], 3);
+    assert_grads(weighted(a / c), GradCheck::default());
+    let s = param(&ctx, &[1], 4);
+    assert_grads(weighted(a.add1(s)), GradCheck::default());
+}
+
+#[test]
+fn gradcheck_unary() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[5, 2], 1);
+    let p = positive(&ctx, &[5, 2], 2);
+    assert_grads(weighted(a.sqr()), GradCheck::default());
+    assert_grads(weighted(p.sqrt()), GradCheck::default());
+    assert_grads(weighted(p.log()), GradCheck::default());
+    assert_grads(weighted(-a), GradCheck::default());
+    assert_grads(weighted(p.relu()), GradCheck::default());
+    assert_grads(weighted(a.silu()), GradCheck::default());
+    assert_grads(weighted(a.scale(0.7)), GradCheck::default());
+    let s = signed(&ctx, &[5, 2], 3);
+    assert_grads(weighted(s.abs()), GradCheck::default());
+    // The gradients of SGN and STEP are zero away from zero.
+    assert_grads(weighted(s.sgn()), GradCheck::default());
+    assert_grads(weighted(s.step()), GradCheck::default());
+}
+
+#[test]
+fn gradcheck_reductions() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[4, 3], 1);
+    assert_grads(weighted(a.sum_rows()), GradCheck::default());
+    let b = param(&ctx, &[4, 1], 2);
+    assert_grads(weighted(b.repeat(a)), GradCheck::default());
+    let shape = ctx.new_tensor(ggml_type_GGML_TYPE_F32, &[4, 1]).unwrap();
+    let report = assert_grads(weighted(a.repeat_back(shape)), GradCheck::default());
+    assert_eq!(report.entries[0].ops, ["REPEAT_BACK"]);
+}
+
+#[test]
+fn gradcheck_layout() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[4, 3, 2], 1);
+    assert_grads(weighted(a.dup()), GradCheck::default());
+    assert_grads(weighted(a.transpose().cont()), GradCheck::default());
+    assert_grads(weighted(a.permute([2, 0, 1, 3])), GradCheck::default());
+    assert_grads(weighted(a.reshape(&[6, 4])), GradCheck::default());
+    let nb = a.nb();
+    assert_grads(
+        weighted(a.view(&[2, 3], &[nb[1]], nb[0])),
+        GradCheck::default(),
+    );
+    let dst = ctx.new_tensor(ggml_type_GGML_TYPE_F32, &[4, 3, 2]).unwrap();
+    assert_grads(weighted(a.cpy(dst)), GradCheck::default());
+}
+
+#[test]
+fn gradcheck_acc_and_set() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let (a, b) = (param(&ctx, &[4, 3], 1), param(&ctx, &[2, 2], 2));
+    let nb = a.nb();
+    // `b` goes to rows 0 and 1, columns 1 and 2.
+    let (strides, offset) = ([nb[1], nb[2], nb[3]], nb[0]);
+    let report = assert_grads(weighted(a.acc(b, strides, offset)), GradCheck::default());
+    assert_eq!(report.entries[0].ops, ["ACC"]);
+    let report = assert_grads(weighted(a.set(b, strides, offset)), GradCheck::default());
+    assert_eq!(report.entries[1].ops, ["SET"]);
+}
+
+#[test]
+fn gradcheck_diag_mask() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[4, 4], 1);
+    assert_grads(weighted(a.diag_mask_zero(1)), GradCheck::default());
+    // The masked elements are `-inf`, so they only make sense followed by SOFT_MAX.
+    let report = assert_grads(weighted(a.diag_mask_inf(0).softmax()), GradCheck::default());
+    assert_eq!(report.entries[0].ops, ["DIAG_MASK_INF"]);
+}
+
+#[test]
+fn gradcheck_mul_mat() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let (a, b) = (param(&ctx, &[4, 3], 1), param(&ctx, &[4, 5], 2));
+    let report = assert_grads(weighted(a.matmul(b)), GradCheck::default());
+    assert_eq!(report.entries.len(), 2);
+    let by_op = report.by_op();
+    assert_eq!(by_op.len(), 1);
+    assert_eq!(by_op[0].op, "MUL_MAT");
+    assert!(by_op[0].passed);
+}
+
+#[test]
+fn gradcheck_rms_norm() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[8, 3], 1);
+    let check = GradCheck {
+        atol: 2e-3,
+        ..Default::default()
+    };
+    assert_grads(weighted(a.rms_norm(1e-5)), check);
+}
+
+#[test]
+fn gradcheck_soft_max() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[6, 3], 1);
+    let report = assert_grads(weighted(a.softmax()), GradCheck::default());
+    assert_eq!(report.entries[0].ops, ["SOFT_MAX"]);
+}
+
+#[test]
+fn gradcheck_rope() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[8, 2, 3], 1);
+    let pos = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 3).unwrap();
+    pos.copy_from_slice(&[0i32, 5, 11]).unwrap();
+    let check = GradCheck {
+        atol: 2e-3,
+        ..Default::default()
+    };
+    assert_grads(weighted(a.rope_ext(pos, None, &RopeParams::new(8))), check);
+    let neox = RopeParams {
+        mode: 2,
+        ..RopeParams::new(8)
+    };
+    assert_grads(weighted(a.rope_ext(pos, None, &neox)), check);
+}
+
+#[test]
+fn gradcheck_rope_back() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[8, 2, 3], 1);
+    let pos = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 3).unwrap();
+    pos.copy_from_slice(&[0i32, 5, 11]).unwrap();
+    let check = GradCheck {
+        atol: 2e-3,
+        ..Default::default()
+    };
+    let report = assert_grads(weighted(a.rope_back(pos, &RopeParams::new(8))), check);
+    assert_eq!(report.entries[0].ops, ["ROPE_BACK"]);
+}
+
+#[test]
+fn gradcheck_get_rows() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[4, 5], 1);
+    let rows = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 3).unwrap();
+    rows.copy_from_slice(&[4i32, 0, 2]).unwrap();
+    assert_grads(weighted(a.get_rows(rows)), GradCheck::default());
+}
+
+#[test]
+fn gradcheck_cross_entropy_loss_back() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let logits = param(&ctx, &[5, 3], 1);
+    // Each row of the targets is a probability distribution.
+    let mut targets = values(15, 2, 0.0, 1.0);
+    for row in targets.chunks_mut(5) {
+        let sum = row.iter().sum::<f32>();
+        row.iter_mut().for_each(|p| *p /= sum);
+    }
+    let b = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 5, 3).unwrap();
+    b.copy_from_slice(&targets).unwrap();
+    let loss = logits.cross_entropy_loss(b);
+    let report = assert_grads(loss, GradCheck::default());
+    assert_eq!(report.entries[0].ops, ["CROSS_ENTROPY_LOSS"]);
+}
+
+#[test]
+fn gradcheck_flash_attn_back() {
+    // `FLASH_ATTN_EXT` can't be differentiated by GGML, so the gradients `ggml_flash_attn_back`
+    // computes are checked against the forward operation directly.
+    let (d, n, m) = (8, 3, 4);
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let q = tensor(&ctx, &[d, n], 1, -1.0, 1.0);
+    let k = tensor(&ctx, &[d, m], 2, -1.0, 1.0);
+    // `ggml_flash_attn_back` takes V transposed.
+    let v = tensor(&ctx, &[m, d], 3, -1.0, 1.0);
+    let dy = tensor(&ctx, &[d, n], 4, -1.0, 1.0);
+
+    let f16 = ggml_type_GGML_TYPE_F16;
+    let y = q.flash_attn_ext(
+        k.cast(f16),
+        v.transpose().cont().cast(f16),
+        None,
+        1.0 / (d as f32).sqrt(),
+        0.0,
+    );
+    let loss = (y.reshape(&[d, n]) * dy).sum();
+
+    let grads = q.flash_attn_back(k, v, dy, false);
+    // The gradients of Q, K and V are stored one after the other, each padded to
+    // `GGML_MEM_ALIGN`.
+    let size = |t: Tensor| t.nbytes().next_multiple_of(GGML_MEM_ALIGN as usize);
+    let row = |ne0: i64| ne0 as usize * std::mem::size_of::<f32>();
+    let dq = grads.view(&[d, n], &[row(d)], 0);
+    let dk = grads.view(&[d, m], &[row(d)], size(q));
+    let dv = grads.view(&[m, d], &[row(m)], size(q) + size(k));
+
+    // The forward operation rounds K and V (and Q, internally) to F16.
+    let check = GradCheck {
+        eps: 1e-2,
+        atol: 5e-3,
+        rtol: 5e-2,
+        ..Default::default()
+    };
+    let report = check
+        .check_explicit(loss, &[(q, dq), (k, dk), (v, dv)])
+        .unwrap();
+    assert!(report.passed(), "{report}");
+}
+
+#[test]
+fn gradcheck_detects_wrong_gradient() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let x = tensor(&ctx, &[6], 1, 0.5, 1.0).set_name("x");
+    let loss = x.sqr().sum();
+    // The gradient is `2 x`.
+    let report = GradCheck::default()
+        .check_explicit(loss, &[(x, x.scale(1.0))])
+        .unwrap();
+    assert!(!report.passed());
+    let entry = &report.entries[0];
+    assert_eq!(entry.name, "x");
+    assert_eq!(entry.checked, 6);
+    let worst = entry.worst.unwrap();
+    assert!((worst.numeric - 2.0 * worst.analytic).abs() < 1e-2);
+    assert!(!report.by_op()[0].passed);
+    assert!(report.to_table().contains("SQR"));
+
+    let right = GradCheck::default()
+        .check_explicit(loss, &[(x, x.scale(2.0))])
+        .unwrap();
+    assert!(right.passed(), "{right}");
+}
+
+#[test]
+fn gradcheck_rejects_unsupported() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[4, 2], 1);
+    assert_eq!(
+        GradCheck::default().check(weighted(a.tanh())).map(|_| ()),
+        Err(Error::NotDifferentiable("TANH"))
+    );
+    assert_eq!(
+        GradCheck::default()
+            .check(weighted(a.norm(1e-5)))
+            .map(|_| ()),
+        Err(Error::NotDifferentiable("NORM"))
+    );
+    assert!(matches!(
+        GradCheck::default().check(a),
+        Err(Error::InvalidShape { .. })
+    ));
+
+    let b = tensor(&ctx, &[4, 2], 2, -1.0, 1.0);
+    assert_eq!(
+        GradCheck::default().check(b.sum()).map(|_| ()),
+        Err(Error::NoGradients)
+    );
+    assert!(has_backward(&a.softmax()));
+    assert!(!has_backward(&a.gelu()));
+
+    // An operand without data, from another context.
+    let no_data = Context::new_no_alloc(1024 * 1024).unwrap();
+    let c = no_data
+        .new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 2)
+        .unwrap();
+    assert_eq!(
+        GradCheck::default().check(weighted(a * c)).map(|_| ()),
+        Err(Error::NoData)
+    );
+}
+
+#[test]
+fn gradcheck_samples_large_inputs() {
+    let ctx = Context::new(MEM_SIZE).unwrap();
+    let a = param(&ctx, &[64, 10], 1);
+    let check = GradCheck {
+        max_elements: 32,
+        ..Default::default()
+    };
+    let report = assert_grads(weighted(a.sqr()), check);
+    assert_eq!(report.entries[0].checked, 32);
+}
@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ggml_sys_bleedingedge::*;
+
+#[test]
+fn graph_build_and_compute() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 64, 64).unwrap();
+    let b = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 64, 64).unwrap();
+    a.copy_from_slice(&[1.0f32; 64 * 64]).unwrap();
+    b.copy_from_slice(&[2.0f32; 64 * 64]).unwrap();
+    let c = a.matmul(b).sum();
+
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&c).unwrap();
+    assert_eq!(graph.n_nodes(), 2);
+    assert_eq!(graph.n_leafs(), 2);
+    assert_eq!(graph.nodes().last(), Some(c));
+
+    graph.compute(2).unwrap();
+    assert_eq!(c.get_f32([0]).unwrap(), (64 * 64 * 64 * 2) as f32);
+
+    // The work buffer is reused when running again.
+    let work_size = graph.work_size();
+    a.copy_from_slice(&[0.5f32; 64 * 64]).unwrap();
+    graph.compute(2).unwrap();
+    assert_eq!(graph.work_size(), work_size);
+    assert_eq!(c.get_f32([0]).unwrap(), (64 * 64 * 64) as f32);
+
+    // Tensors of a context without data can't be computed on the CPU.
+    let no_data = Context::new_no_alloc(1024 * 1024).unwrap();
+    let a = no_data.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let mut graph = Graph::new(&no_data).unwrap();
+    graph.build_forward(&a.sqr()).unwrap();
+    assert_eq!(graph.compute(1), Err(Error::NoData));
+}
+
+#[test]
+fn graph_full() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let mut graph = Graph::with_size(&ctx, 2, false).unwrap();
+    graph.build_forward(&a.sqr().sqr()).unwrap();
+    assert!(matches!(
+        graph.build_forward(&a.sqr().sqr().sqr()),
+        Err(Error::GraphFull { size: 2 })
+    ));
+    assert_eq!(graph.n_nodes(), 2);
+}
+
+fn square_loss(ctx: &Context) -> Graph<'_> {
+    let x = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 256).unwrap();
+    let loss = x.set_param().unwrap().sqr().sum();
+    let mut graph = Graph::with_size(ctx, 64, true).unwrap();
+    graph.build_forward(&loss).unwrap();
+    graph
+}
+
+#[test]
+fn graph_backward_checks_room() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let graph = square_loss(&ctx);
+    let backward = graph.backward(false).unwrap();
+    assert!(backward.n_nodes() > graph.n_nodes());
+
+    // Leave room for the graph but not the gradient operations, which GGML would abort on.
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let graph = square_loss(&ctx);
+    let graph_size = unsafe { ggml_graph_overhead_custom(64, true) };
+    let filler = ctx.mem_size() - ctx.used_mem() - graph_size - 1024;
+    let filler = filler - unsafe { ggml_tensor_overhead() } - GGML_MEM_ALIGN as usize;
+    ctx.new_tensor_1d(ggml_type_GGML_TYPE_I8, filler as i64)
+        .unwrap();
+    assert!(matches!(
+        graph.backward(false),
+        Err(Error::OutOfMemory { .. })
+    ));
+}
+
+#[test]
+fn graph_abort_callback() {
+    // The callback can borrow anything that outlives the context.
+    let calls = AtomicUsize::new(0);
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    a.copy_from_slice(&[1.0f32; 4]).unwrap();
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&a.sqr().sqrt().sum()).unwrap();
+
+    graph.set_abort_callback(|| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        true
+    });
+    assert_eq!(graph.compute(1), Err(Error::Aborted));
+    assert!(calls.load(Ordering::Relaxed) > 0);
+
+    graph.clear_abort_callback();
+    graph.compute(1).unwrap();
+}
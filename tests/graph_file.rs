@@ -0,0 +1,208 @@
+use std::{path::PathBuf, sync::Arc};
+
+use ggml_sys_bleedingedge::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("ggml-{name}-{}.ggml", std::process::id()))
+}
+
+fn values(len: usize, seed: f32) -> Vec<f32> {
+    (0..len).map(|i| ((i as f32 + seed) * 0.37).sin()).collect()
+}
+
+fn input<'ctx>(ctx: &'ctx Context, type_: ggml_type, shape: &[i64], seed: f32) -> Tensor<'ctx> {
+    let t = ctx.new_tensor(ggml_type_GGML_TYPE_F32, shape).unwrap();
+    t.copy_from_slice(&values(t.nelements() as usize, seed))
+        .unwrap();
+    if type_ == ggml_type_GGML_TYPE_F32 {
+        return t;
+    }
+    let q = quantize(&t.as_slice().unwrap(), shape, type_, None).unwrap();
+    let t = ctx.new_tensor(type_, shape).unwrap();
+    unsafe { std::ptr::copy_nonoverlapping(q.data().as_ptr(), t.data_ptr().cast(), t.nbytes()) };
+    t
+}
+
+/// A small attention-like block using views, reshapes, permutes and transposes, which
+/// `ggml_graph_import` handles differently from other operations.
+fn build(ctx: &Context) -> Tensor<'_> {
+    let w = input(ctx, ggml_type_GGML_TYPE_Q8_0, &[32, 8], 1.0).set_name("w");
+    let x = input(ctx, ggml_type_GGML_TYPE_F32, &[32, 6], 2.0).set_name("x");
+    let b = input(ctx, ggml_type_GGML_TYPE_F32, &[8], 3.0).set_name("b");
+    // [8, 6] projected, split into 2 heads of 4.
+    let h = (w.matmul(x) + b).set_name("projected");
+    let heads = h.reshape(&[4, 2, 6]).permute([0, 2, 1, 3]).cont();
+    let scores = heads.matmul(heads).softmax_ext(None, 0.5, 0.0);
+    let first = scores
+        .view(&[6, 6], &[scores.nb()[1]], 0)
+        .transpose()
+        .cont();
+    (first.rms_norm(1e-6) * 2.0).set_name("out")
+}
+
+fn compute(graph: &mut Graph) {
+    graph.compute(2).unwrap();
+}
+
+/// Every element in logical order, whatever the tensor's strides.
+fn elements(t: Tensor) -> Vec<f32> {
+    let ne = t.ne().map(|ne| ne as usize);
+    let mut out = Vec::new();
+    for i3 in 0..ne[3] {
+        for i2 in 0..ne[2] {
+            for i1 in 0..ne[1] {
+                out.extend((0..ne[0]).map(|i0| t.get_f32([i0, i1, i2, i3]).unwrap()));
+            }
+        }
+    }
+    out
+}
+
+#[test]
+fn graph_file_round_trip() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let out = build(&ctx);
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    compute(&mut graph);
+
+    let path = temp_path("round-trip");
+    graph.export(&path).unwrap();
+    // The file was just exported.
+    let imported = unsafe { ImportedGraph::import(&path) }.unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut copy = imported.graph();
+    assert_eq!(copy.n_nodes(), graph.n_nodes());
+    assert_eq!(copy.n_leafs(), graph.n_leafs());
+    compute(&mut copy);
+
+    // Every node, including the ones named while building the graph, has the same name, shape
+    // and values.
+    for (a, b) in graph.nodes().zip(copy.nodes()) {
+        assert_eq!(a.name(), b.name());
+        assert_eq!(a.op(), b.op());
+        assert_eq!(a.ne(), b.ne());
+        assert_eq!(elements(a), elements(b), "node {}", b.name());
+    }
+    let out2 = imported.get_tensor("out").unwrap();
+    assert_eq!(copy.nodes().last(), Some(out2));
+    assert_eq!(
+        out2.as_slice::<f32>().unwrap(),
+        out.as_slice::<f32>().unwrap()
+    );
+    assert_eq!(graph.get_tensor("out"), Some(out));
+    let w = imported.get_tensor("w").unwrap();
+    assert_eq!(w.type_(), ggml_type_GGML_TYPE_Q8_0);
+    assert_eq!(w.context().as_ptr(), imported.context().as_ptr());
+    assert!(imported.get_tensor("missing").is_none());
+}
+
+#[test]
+fn graph_file_new_inputs() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let out = build(&ctx);
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    let path = temp_path("inputs");
+    graph.export(&path).unwrap();
+    // The file was just exported.
+    let imported = unsafe { ImportedGraph::import(&path) }.unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    // Workers fill in the inputs and compute the graph as many times as they like.
+    let mut copy = imported.graph();
+    for seed in [5.0, 7.0] {
+        let x = values(32 * 6, seed);
+        ctx.get_tensor("x").unwrap().copy_from_slice(&x).unwrap();
+        imported
+            .get_tensor("x")
+            .unwrap()
+            .copy_from_slice(&x)
+            .unwrap();
+        compute(&mut graph);
+        compute(&mut copy);
+        let out2 = imported.get_tensor("out").unwrap();
+        assert_eq!(
+            out2.as_slice::<f32>().unwrap(),
+            out.as_slice::<f32>().unwrap()
+        );
+    }
+}
+
+#[test]
+fn graph_file_rejects_unsupported_graphs() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let path = temp_path("unsupported");
+    let x = input(&ctx, ggml_type_GGML_TYPE_F32, &[4], 1.0);
+    let export = |t: Tensor| {
+        let mut graph = Graph::with_size(&ctx, 64, true).unwrap();
+        graph.build_forward(&t).unwrap();
+        graph.export(&path)
+    };
+
+    let custom = x.map_custom1(Arc::new(|_, _, _, _| {}), None).unwrap();
+    assert!(matches!(export(custom), Err(Error::Shape(_))));
+
+    let param = input(&ctx, ggml_type_GGML_TYPE_F32, &[4], 2.0)
+        .set_param()
+        .unwrap();
+    assert!(matches!(export(param.sqr()), Err(Error::Shape(_))));
+
+    // The cache is written in place and read afterwards, which the import wouldn't see.
+    let cache = input(&ctx, ggml_type_GGML_TYPE_F32, &[8], 3.0);
+    let written = x.cpy(cache.view(&[4], &[], 0));
+    let read = cache.view(&[4], &[], 0) + written;
+    assert!(matches!(export(read), Err(Error::Shape(_))));
+    // Reading the copy itself is fine.
+    export(written.sqr()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let no_data = Context::new_no_alloc(1024 * 1024).unwrap();
+    let a = no_data.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let mut graph = Graph::new(&no_data).unwrap();
+    graph.build_forward(&a.sqr()).unwrap();
+    assert_eq!(graph.export(&path), Err(Error::NoData));
+}
+
+#[test]
+fn graph_file_rejects_invalid_files() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let out = build(&ctx);
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    let path = temp_path("invalid");
+    graph.export(&path).unwrap();
+    let data = std::fs::read(&path).unwrap();
+
+    let import = |data: &[u8]| {
+        std::fs::write(&path, data).unwrap();
+        match unsafe { ImportedGraph::import(&path) } {
+            Err(Error::GraphFile { msg, .. }) => msg,
+            Err(err) => panic!("unexpected error {err}"),
+            Ok(_) => panic!("invalid file imported"),
+        }
+    };
+    assert_eq!(import(&data[..0]), "file ends in the middle of the header");
+    assert!(import(&data[..data.len() - 1]).contains("file ends in the middle of node"));
+    assert_eq!(
+        import(&[&data[..], &[0u8][..]].concat()),
+        "trailing data after the last node"
+    );
+    let mut bad = data.clone();
+    bad[0] ^= 1;
+    assert_eq!(import(&bad), "not a GGML graph file");
+    // The type of the first leaf.
+    let mut bad = data.clone();
+    bad[24..28].copy_from_slice(&1000u32.to_ne_bytes());
+    assert!(import(&bad).contains("invalid type 1000"));
+    // The second dimension of the first leaf.
+    let mut bad = data.clone();
+    bad[48..56].copy_from_slice(&u64::MAX.to_ne_bytes());
+    assert!(import(&bad).contains("invalid shape"));
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(
+        unsafe { ImportedGraph::import(&path) },
+        Err(Error::GraphFile { .. })
+    ));
+}
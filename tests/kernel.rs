@@ -0,0 +1,94 @@
+use ggml_sys_bleedingedge::*;
+
+fn data(len: usize, seed: f32) -> Vec<f32> {
+    (0..len).map(|i| (i as f32 * 0.377 + seed).sin()).collect()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+#[test]
+fn kernel_q8_0_matches_f32() {
+    let n = 64;
+    let kernel = QuantKernel::new(ggml_type_GGML_TYPE_Q8_0, n).unwrap();
+    assert_eq!(kernel.vec_dot_type(), ggml_type_GGML_TYPE_Q8_0);
+
+    let src = data(n * 5, 0.0);
+    let rows = quantize(&src, &[n as i64, 5], ggml_type_GGML_TYPE_Q8_0, None).unwrap();
+    let query = data(n * 3, 1.0);
+    let queries = kernel.quantize_queries(&query).unwrap();
+    assert_eq!(queries.len(), 3);
+
+    let dots = kernel.dots(&rows, &queries).unwrap();
+    assert_eq!(dots.len(), 15);
+    for q in 0..3 {
+        for r in 0..5 {
+            let expected = dot(&src[r * n..(r + 1) * n], &query[q * n..(q + 1) * n]);
+            let single = kernel.dot(&rows, r, &queries, q).unwrap();
+            assert!((single - expected).abs() < 0.1, "{single} != {expected}");
+            assert!((dots[q * 5 + r] - single).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn kernel_k_quant_uses_q8_k() {
+    let n = 256;
+    let kernel = QuantKernel::new(ggml_type_GGML_TYPE_Q4_K, n).unwrap();
+    assert_eq!(kernel.vec_dot_type(), ggml_type_GGML_TYPE_Q8_K);
+
+    let src = data(n * 2, 0.5);
+    let rows = quantize(&src, &[n as i64, 2], ggml_type_GGML_TYPE_Q4_K, None).unwrap();
+    let query = data(n, 2.0);
+    let queries = kernel.quantize_queries(&query).unwrap();
+    let dequantized = dequantize(&rows);
+    for r in 0..2 {
+        let expected = dot(&dequantized[r * n..(r + 1) * n], &query);
+        let actual = kernel.dot(&rows, r, &queries, 0).unwrap();
+        assert!((actual - expected).abs() < 0.5, "{actual} != {expected}");
+    }
+}
+
+#[test]
+fn kernel_f32() {
+    let kernel = QuantKernel::new(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let rows = quantize(&[1.0, 2.0, 3.0, 4.0], &[4], ggml_type_GGML_TYPE_F32, None).unwrap();
+    let queries = kernel
+        .quantize_queries(&[1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 2.0])
+        .unwrap();
+    assert_eq!(kernel.dots(&rows, &queries).unwrap(), [10.0, 8.0]);
+}
+
+#[test]
+fn kernel_checks_arguments() {
+    assert_eq!(
+        QuantKernel::new(ggml_type_GGML_TYPE_Q8_0, 48).unwrap_err(),
+        Error::InvalidShape {
+            type_: ggml_type_GGML_TYPE_Q8_0,
+            shape: vec![48]
+        }
+    );
+    assert_eq!(
+        QuantKernel::new(ggml_type_GGML_TYPE_I32, 32).unwrap_err(),
+        Error::UnsupportedType(ggml_type_GGML_TYPE_I32)
+    );
+
+    let kernel = QuantKernel::new(ggml_type_GGML_TYPE_Q8_0, 32).unwrap();
+    assert_eq!(
+        kernel.quantize_queries(&[0.0; 40]).unwrap_err(),
+        Error::LengthMismatch {
+            expected: 64,
+            actual: 40
+        }
+    );
+    let queries = kernel.quantize_queries(&[1.0; 32]).unwrap();
+    let rows = quantize(&[1.0; 32], &[32], ggml_type_GGML_TYPE_Q4_0, None).unwrap();
+    assert_eq!(
+        kernel.dots(&rows, &queries).unwrap_err(),
+        Error::TypeMismatch {
+            expected: ggml_type_GGML_TYPE_Q8_0,
+            actual: ggml_type_GGML_TYPE_Q4_0
+        }
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "log")]
+
+use std::{ffi::CString, sync::Mutex};
+
+use ggml_sys_bleedingedge::{logging, *};
+
+static RECORDS: Mutex<Vec<(log::Level, String, String)>> = Mutex::new(Vec::new());
+
+struct TestLogger;
+
+impl log::Log for TestLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        RECORDS.lock().unwrap().push((
+            record.level(),
+            record.target().to_string(),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+// Everything is in one test since the logger and callback are global.
+#[test]
+fn logging_forwards_to_log() {
+    log::set_logger(&TestLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    // Fragments are joined into lines, with the level of the first one.
+    logging::log_message(ggml_log_level_GGML_LOG_LEVEL_WARN, "llm_load_tensors: ");
+    logging::log_message(ggml_log_level_GGML_LOG_LEVEL_INFO, "...");
+    logging::log_message(ggml_log_level_GGML_LOG_LEVEL_INFO, ".\nggml_init: two\n\n");
+    assert_eq!(
+        RECORDS.lock().unwrap().drain(..).collect::<Vec<_>>(),
+        [
+            (
+                log::Level::Warn,
+                String::from("llama"),
+                String::from("llm_load_tensors: ....")
+            ),
+            (
+                log::Level::Info,
+                String::from("ggml"),
+                String::from("ggml_init: two")
+            ),
+        ]
+    );
+
+    logging::init_log();
+    let path = CString::new("/nonexistent/model.gguf").unwrap();
+    let model = unsafe { llama_load_model_from_file(path.as_ptr(), llama_model_default_params()) };
+    assert!(model.is_null());
+    assert!(RECORDS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|(level, target, _)| *level == log::Level::Error && target == "llama"));
+}
@@ -0,0 +1,124 @@
+use ggml_sys_bleedingedge::*;
+
+fn compute(ctx: &Context, t: Tensor) {
+    unsafe {
+        let gf = ggml_new_graph(ctx.as_ptr());
+        ggml_build_forward_expand(gf, t.as_ptr());
+        assert_eq!(
+            ggml_graph_compute_with_ctx(ctx.as_ptr(), gf, 1),
+            ggml_status_GGML_STATUS_SUCCESS
+        );
+    }
+}
+
+#[test]
+fn ops_linear_layer() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    // 2x3 weights, 3 inputs, 2 biases.
+    let w = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 3, 2).unwrap();
+    let x = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 3).unwrap();
+    let b = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 2).unwrap();
+    w.copy_from_slice(&[1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0])
+        .unwrap();
+    x.copy_from_slice(&[1.0f32, 1.0, 1.0]).unwrap();
+    b.copy_from_slice(&[0.5f32, -0.5]).unwrap();
+
+    let y = -(w.matmul(x) + b) * 2.0;
+    assert_eq!(y.shape(), &[2]);
+    assert_eq!(y.op(), ggml_op_GGML_OP_SCALE);
+    compute(&ctx, y);
+    assert_eq!(y.as_slice::<f32>().unwrap(), &[-13.0, -29.0]);
+}
+
+#[test]
+fn ops_elementwise_and_inplace() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let b = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    a.copy_from_slice(&[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+    b.copy_from_slice(&[2.0f32, 2.0, 2.0, 2.0]).unwrap();
+
+    let c = (a - b) / b;
+    let d = a.mul_inplace(b);
+    // The in-place result is a view of a.
+    assert_eq!(d.view_src(), Some(a));
+    compute(&ctx, c);
+    compute(&ctx, d);
+    assert_eq!(c.as_slice::<f32>().unwrap(), &[-0.5, 0.0, 0.5, 1.0]);
+    assert_eq!(a.as_slice::<f32>().unwrap(), &[2.0, 4.0, 6.0, 8.0]);
+}
+
+#[test]
+fn ops_softmax_rms_norm() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 2, 2).unwrap();
+    a.copy_from_slice(&[0.0f32, 0.0, 3.0, 3.0]).unwrap();
+    let sm = a.softmax();
+    let rn = a.rms_norm(1e-6);
+    compute(&ctx, sm);
+    compute(&ctx, rn);
+    assert_eq!(sm.as_slice::<f32>().unwrap(), &[0.5, 0.5, 0.5, 0.5]);
+    let rn = rn.as_slice::<f32>().unwrap();
+    assert!((rn[2] - 1.0).abs() < 1e-4);
+}
+
+#[test]
+fn ops_shape_manipulation() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 3, 2).unwrap();
+    a.copy_from_slice(&[0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0])
+        .unwrap();
+
+    assert_eq!(a.reshape(&[2, 3]).shape(), &[2, 3]);
+    assert_eq!(a.reshape(&[6]).shape(), &[6]);
+
+    let t = a.permute([1, 0, 2, 3]);
+    assert_eq!(t.shape(), &[2, 3]);
+    assert!(!t.is_contiguous());
+    let t = t.cont();
+    compute(&ctx, t);
+    assert_eq!(
+        t.as_slice::<f32>().unwrap(),
+        &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]
+    );
+
+    // The second row.
+    let row = a.view(&[3], &[], a.nb()[1]);
+    assert_eq!(row.get_f32([0]).unwrap(), 3.0);
+}
+
+#[test]
+fn ops_rope_ext() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let x = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 4, 1, 2).unwrap();
+    let pos = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 2).unwrap();
+    x.copy_from_slice(&[1.0f32, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0])
+        .unwrap();
+    pos.copy_from_slice(&[0i32, 1]).unwrap();
+    let y = x.rope_ext(pos, None, &RopeParams::new(4));
+    compute(&ctx, y);
+    // Position 0 isn't rotated at all.
+    assert_eq!(&y.as_slice::<f32>().unwrap()[..4], &[1.0, 0.0, 1.0, 0.0]);
+    assert!((y.get_f32([0, 0, 1]).unwrap() - 1f32.cos()).abs() < 1e-5);
+}
+
+#[test]
+fn ops_arange_and_windows() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let r = ctx.arange(1.0, 4.0, 0.5);
+    compute(&ctx, r);
+    assert_eq!(
+        r.as_slice::<f32>().unwrap(),
+        &[1.0, 1.5, 2.0, 2.5, 3.0, 3.5]
+    );
+
+    // Splitting into windows and putting them back together is lossless.
+    let img = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 2, 3, 5).unwrap();
+    let values = (0..30).map(|i| i as f32).collect::<Vec<_>>();
+    img.copy_from_slice(&values).unwrap();
+    let parts = img.win_part(2);
+    assert_eq!(parts.ne(), [2, 2, 2, 6]);
+    let y = parts.win_unpart(3, 5, 2);
+    compute(&ctx, y);
+    assert_eq!(y.as_slice::<f32>().unwrap(), &values[..]);
+}
@@ -0,0 +1,141 @@
+use ggml_sys_bleedingedge::{quant, *};
+
+fn data(len: usize) -> Vec<f32> {
+    (0..len).map(|i| (i as f32 * 0.731).sin() * 2.0).collect()
+}
+
+fn rmse(a: &[f32], b: &[f32]) -> f32 {
+    let sum = a.iter().zip(b).map(|(a, b)| (a - b).powi(2)).sum::<f32>();
+    (sum / a.len() as f32).sqrt()
+}
+
+#[test]
+fn quant_round_trip_q8_0() {
+    let src = data(64 * 9);
+    let q = quantize(&src, &[64, 3, 3], ggml_type_GGML_TYPE_Q8_0, None).unwrap();
+    assert_eq!(q.ne(), [64, 3, 3, 1]);
+    assert_eq!(q.nrows(), 9);
+    assert_eq!(q.row_size(), unsafe {
+        ggml_row_size(ggml_type_GGML_TYPE_Q8_0, 64)
+    });
+    assert_eq!(q.data().len(), q.row_size() * 9);
+
+    let back = dequantize(&q);
+    assert_eq!(back.len(), src.len());
+    assert!(rmse(&src, &back) < 0.01);
+}
+
+#[test]
+fn quant_matches_single_threaded_chunk() {
+    // Enough rows to be split between threads: the result must not depend on the split.
+    let src = data(256 * 37);
+    let q = quantize(&src, &[256, 37], ggml_type_GGML_TYPE_Q4_K, None).unwrap();
+    let mut expected = vec![0u8; q.data().len()];
+    unsafe {
+        ggml_quantize_chunk(
+            ggml_type_GGML_TYPE_Q4_K,
+            src.as_ptr(),
+            expected.as_mut_ptr().cast(),
+            0,
+            37,
+            256,
+            std::ptr::null(),
+        )
+    };
+    assert_eq!(q.data(), expected);
+    assert_eq!(q.row(36), &expected[36 * q.row_size()..]);
+}
+
+#[test]
+fn quant_float_types_round_trip() {
+    let src = data(32 * 2);
+    let q = quantize(&src, &[32, 2], ggml_type_GGML_TYPE_F32, None).unwrap();
+    assert_eq!(dequantize(&q), src);
+    let q = quantize(&src, &[32, 2], ggml_type_GGML_TYPE_F16, None).unwrap();
+    assert!(rmse(&src, &dequantize(&q)) < 1e-3);
+}
+
+#[test]
+fn quant_checks_arguments() {
+    let src = data(64);
+    assert_eq!(
+        quantize(&src, &[48], ggml_type_GGML_TYPE_Q4_0, None),
+        Err(Error::LengthMismatch {
+            expected: 48,
+            actual: 64
+        })
+    );
+    assert_eq!(
+        quantize(&src[..40], &[40], ggml_type_GGML_TYPE_Q4_0, None),
+        Err(Error::InvalidShape {
+            type_: ggml_type_GGML_TYPE_Q4_0,
+            shape: vec![40]
+        })
+    );
+    assert_eq!(
+        quantize(&src, &[64], ggml_type_GGML_TYPE_Q8_K, None),
+        Err(Error::UnsupportedType(ggml_type_GGML_TYPE_Q8_K))
+    );
+    assert_eq!(
+        quantize(&src, &[64], ggml_type_GGML_TYPE_Q4_0, Some(&[1.0; 32])),
+        Err(Error::LengthMismatch {
+            expected: 64,
+            actual: 32
+        })
+    );
+    // Shapes whose size overflows are rejected rather than wrapping around.
+    for shape in [
+        &[32, i64::MAX][..],
+        &[32, 1 << 40, 1 << 40],
+        &[i64::MAX / 32 * 32],
+    ] {
+        assert_eq!(
+            quantize(&src, shape, ggml_type_GGML_TYPE_Q4_0, None),
+            Err(Error::InvalidShape {
+                type_: ggml_type_GGML_TYPE_Q4_0,
+                shape: shape.to_vec()
+            })
+        );
+        assert!(QuantizedBuffer::from_bytes(ggml_type_GGML_TYPE_Q4_0, shape, &[]).is_err());
+    }
+}
+
+#[test]
+fn quant_imatrix() {
+    let src = data(256 * 2);
+    let type_ = ggml_type_GGML_TYPE_IQ2_XXS;
+    assert!(unsafe { ggml_quantize_requires_imatrix(type_) });
+    assert_eq!(
+        quantize(&src, &[256, 2], type_, None),
+        Err(Error::MissingImatrix(type_))
+    );
+    let imatrix = vec![1.0; 256];
+    let q = quantize(&src, &[256, 2], type_, Some(&imatrix)).unwrap();
+    assert!(rmse(&src, &dequantize(&q)) < 1.0);
+}
+
+#[test]
+fn quant_validates_data() {
+    let mut src = data(32);
+    src[3] = f32::NAN;
+    assert_eq!(
+        quantize(&src, &[32], ggml_type_GGML_TYPE_F16, None),
+        Err(Error::InvalidData(ggml_type_GGML_TYPE_F16))
+    );
+
+    let q = quantize(&data(32), &[32], ggml_type_GGML_TYPE_Q8_0, None).unwrap();
+    let copy = QuantizedBuffer::from_bytes(ggml_type_GGML_TYPE_Q8_0, &[32], q.data()).unwrap();
+    assert_eq!(copy, q);
+    assert!(QuantizedBuffer::from_bytes(ggml_type_GGML_TYPE_Q8_0, &[32], &q.data()[1..]).is_err());
+}
+
+#[test]
+fn quant_type_info() {
+    assert!(quant::QUANTIZE_TYPES
+        .iter()
+        .all(|t| quant::can_quantize(*t)));
+    assert_eq!(quant::type_name(ggml_type_GGML_TYPE_Q4_K), "q4_K");
+    let traits = quant::type_traits(ggml_type_GGML_TYPE_Q8_0).unwrap();
+    assert_eq!(traits.blck_size, 32);
+    assert!(quant::type_traits(ggml_type_GGML_TYPE_COUNT).is_none());
+}
@@ -0,0 +1,631 @@
+//! Compares GGML's CPU operations with straightforward Rust implementations over randomized
+//! shapes and types, so changes to their numerics show up when `ggml-src` is updated.
+//!
+//! Inputs are stored in the tested type first and the references are computed (in `f64`) from
+//! the values the tensors actually hold, so the tolerances only cover what the operation does:
+//! converting operands to the type of its kernel (i.e. Q8_0 for quantized `mul_mat`) and the
+//! rounding of its result. Tolerances are relative to the sum of the absolute values of the
+//! terms for dot products and to `max(|expected|, 1)` for everything else.
+
+use ggml_sys_bleedingedge::{graph::Graph, ops::RopeParams, *};
+
+const MEM_SIZE: usize = 64 * 1024 * 1024;
+const CASES: usize = 8;
+
+/// SplitMix64, so every run tests the same cases.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `lo..=hi`.
+    fn int(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next() % (hi - lo + 1) as u64) as i64
+    }
+
+    fn f32s(&mut self, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|_| (self.next() >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0)
+            .collect()
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.next() as usize % items.len()]
+    }
+}
+
+/// A tensor of `type_` holding `src` as closely as the type allows, along with the values it
+/// actually holds.
+fn upload<'ctx>(
+    ctx: &'ctx Context,
+    type_: ggml_type,
+    shape: &[i64],
+    src: &[f32],
+) -> (Tensor<'ctx>, Vec<f32>) {
+    let t = ctx.new_tensor(type_, shape).unwrap();
+    let q = quantize(src, shape, type_, None).unwrap();
+    assert_eq!(q.data().len(), t.nbytes());
+    unsafe { std::ptr::copy_nonoverlapping(q.data().as_ptr(), t.data_ptr().cast(), t.nbytes()) };
+    (t, dequantize(&q))
+}
+
+fn indices<'ctx>(ctx: &'ctx Context, src: &[i32]) -> Tensor<'ctx> {
+    let t = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_I32, src.len() as i64)
+        .unwrap();
+    t.copy_from_slice(src).unwrap();
+    t
+}
+
+fn compute(ctx: &Context, t: Tensor) {
+    let mut graph = Graph::new(ctx).unwrap();
+    graph.build_forward(&t).unwrap();
+    graph.compute(2).unwrap();
+}
+
+/// Every element in logical order, whatever the tensor's type and strides.
+fn download(t: Tensor) -> Vec<f32> {
+    let ne = t.ne().map(|ne| ne as usize);
+    let mut out = Vec::with_capacity(t.nelements() as usize);
+    for i3 in 0..ne[3] {
+        for i2 in 0..ne[2] {
+            for i1 in 0..ne[1] {
+                out.extend((0..ne[0]).map(|i0| t.get_f32([i0, i1, i2, i3]).unwrap()));
+            }
+        }
+    }
+    out
+}
+
+fn download_i32(t: Tensor) -> Vec<i32> {
+    let ne = t.ne().map(|ne| ne as usize);
+    let mut out = Vec::with_capacity(t.nelements() as usize);
+    for i1 in 0..ne[1] {
+        out.extend((0..ne[0]).map(|i0| t.get_i32([i0, i1]).unwrap()));
+    }
+    out
+}
+
+/// Checks `|actual - expected| <= tol * scale` element by element.
+#[track_caller]
+fn assert_close(case: &str, actual: &[f32], expected: &[f64], scale: &[f64], tol: f64) {
+    assert_eq!(actual.len(), expected.len(), "{case}: length");
+    for (i, ((a, e), s)) in actual.iter().zip(expected).zip(scale).enumerate() {
+        let error = (*a as f64 - e).abs();
+        assert!(
+            error <= tol * s,
+            "{case}: element {i} is {a}, expected {e} (error {error:e}, allowed {:e})",
+            tol * s
+        );
+    }
+}
+
+#[track_caller]
+fn assert_close_values(case: &str, actual: &[f32], expected: &[f64], tol: f64) {
+    let scale = expected
+        .iter()
+        .map(|e| e.abs().max(1.0))
+        .collect::<Vec<_>>();
+    assert_close(case, actual, expected, &scale, tol);
+}
+
+/// Index of element `i` of dimension 0 and so on in a contiguous tensor of shape `ne`.
+fn at(ne: [i64; 4], i: [i64; 4]) -> usize {
+    (((i[3] * ne[2] + i[2]) * ne[1] + i[1]) * ne[0] + i[0]) as usize
+}
+
+#[test]
+fn reference_add() {
+    let mut rng = Rng(1);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let ne = [rng.int(1, 33), rng.int(1, 5), rng.int(1, 3), rng.int(1, 2)];
+        let (type_, tol) = rng.pick(&[
+            (ggml_type_GGML_TYPE_F32, 1e-6),
+            (ggml_type_GGML_TYPE_F16, 1e-3),
+        ]);
+        // `b` is broadcast along the dimensions where it has size 1, which only the F32 kernel
+        // supports.
+        let bne = if type_ == ggml_type_GGML_TYPE_F32 {
+            ne.map(|ne| if rng.int(0, 1) == 0 { 1 } else { ne })
+        } else {
+            ne
+        };
+        let n = ne.iter().product::<i64>() as usize;
+        let (a, av) = upload(&ctx, type_, &ne, &rng.f32s(n));
+        let bn = bne.iter().product::<i64>() as usize;
+        let (b, bv) = upload(&ctx, ggml_type_GGML_TYPE_F32, &bne, &rng.f32s(bn));
+        let c = a + b;
+        compute(&ctx, c);
+
+        let mut expected = Vec::with_capacity(n);
+        for i3 in 0..ne[3] {
+            for i2 in 0..ne[2] {
+                for i1 in 0..ne[1] {
+                    for i0 in 0..ne[0] {
+                        let i = [i0, i1, i2, i3];
+                        let bi = [0, 1, 2, 3].map(|d| i[d] % bne[d]);
+                        expected.push(av[at(ne, i)] as f64 + bv[at(bne, bi)] as f64);
+                    }
+                }
+            }
+        }
+        let name = format!("add case {case} {ne:?} + {bne:?} type {type_}");
+        assert_close_values(&name, &download(c), &expected, tol);
+    }
+}
+
+#[test]
+fn reference_mul_mat() {
+    let mut rng = Rng(2);
+    let types = [
+        (ggml_type_GGML_TYPE_F32, 32, 1e-5),
+        (ggml_type_GGML_TYPE_F16, 32, 2e-3),
+        (ggml_type_GGML_TYPE_Q4_0, 32, 2e-2),
+        (ggml_type_GGML_TYPE_Q4_1, 32, 2e-2),
+        (ggml_type_GGML_TYPE_Q5_0, 32, 2e-2),
+        (ggml_type_GGML_TYPE_Q8_0, 32, 2e-2),
+        (ggml_type_GGML_TYPE_Q4_K, 256, 2e-2),
+        (ggml_type_GGML_TYPE_Q6_K, 256, 2e-2),
+    ];
+    for (case, &(type_, block, tol)) in types.iter().cycle().take(2 * types.len()).enumerate() {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let k = block * rng.int(1, 3);
+        let (m, n) = (rng.int(1, 9), rng.int(1, 7));
+        // The batch dimensions of `a` are broadcast to those of `b`.
+        let (r2, r3) = (rng.int(1, 2), rng.int(1, 2));
+        let (a2, a3) = (rng.int(1, 2), 1);
+        let ane = [k, m, a2, a3];
+        let bne = [k, n, a2 * r2, a3 * r3];
+        let (a, av) = upload(&ctx, type_, &ane, &rng.f32s(at(ane, [0, 0, 0, a3])));
+        let (b, bv) = upload(
+            &ctx,
+            ggml_type_GGML_TYPE_F32,
+            &bne,
+            &rng.f32s(at(bne, [0, 0, 0, bne[3]])),
+        );
+        let c = a.matmul(b);
+        compute(&ctx, c);
+
+        let (mut expected, mut scale) = (Vec::new(), Vec::new());
+        for i3 in 0..bne[3] {
+            for i2 in 0..bne[2] {
+                for j in 0..n {
+                    for i in 0..m {
+                        let (mut sum, mut abs) = (0.0, 0.0);
+                        for l in 0..k {
+                            let term = av[at(ane, [l, i, i2 / r2, i3 / r3])] as f64
+                                * bv[at(bne, [l, j, i2, i3])] as f64;
+                            sum += term;
+                            abs += term.abs();
+                        }
+                        expected.push(sum);
+                        scale.push(abs.max(1e-3));
+                    }
+                }
+            }
+        }
+        let name = format!("mul_mat case {case} {ane:?} x {bne:?} type {type_}");
+        assert_close(&name, &download(c), &expected, &scale, tol);
+    }
+}
+
+#[test]
+fn reference_norm() {
+    let mut rng = Rng(3);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let ne = [rng.int(1, 100), rng.int(1, 6), rng.int(1, 2), 1];
+        let len = ne.iter().product::<i64>() as usize;
+        let (a, av) = upload(&ctx, ggml_type_GGML_TYPE_F32, &ne, &rng.f32s(len));
+        let eps = rng.pick(&[1e-6, 1e-5]);
+        let (norm, rms_norm) = (a.norm(eps), a.rms_norm(eps));
+        compute(&ctx, norm);
+        compute(&ctx, rms_norm);
+
+        let (mut expected_norm, mut expected_rms) = (Vec::new(), Vec::new());
+        for row in av.chunks(ne[0] as usize) {
+            let len = row.len() as f64;
+            let mean = row.iter().map(|x| *x as f64).sum::<f64>() / len;
+            let var = row.iter().map(|x| (*x as f64 - mean).powi(2)).sum::<f64>() / len;
+            let scale = 1.0 / (var + eps as f64).sqrt();
+            expected_norm.extend(row.iter().map(|x| (*x as f64 - mean) * scale));
+            let ms = row.iter().map(|x| (*x as f64).powi(2)).sum::<f64>() / len;
+            let scale = 1.0 / (ms + eps as f64).sqrt();
+            expected_rms.extend(row.iter().map(|x| *x as f64 * scale));
+        }
+        let name = format!("norm case {case} {ne:?}");
+        assert_close_values(&name, &download(norm), &expected_norm, 1e-4);
+        let name = format!("rms_norm case {case} {ne:?}");
+        assert_close_values(&name, &download(rms_norm), &expected_rms, 1e-5);
+    }
+}
+
+#[test]
+fn reference_soft_max() {
+    let mut rng = Rng(4);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let ne = [rng.int(1, 70), rng.int(1, 6), rng.int(1, 3), 1];
+        let len = ne.iter().product::<i64>() as usize;
+        let (a, av) = upload(&ctx, ggml_type_GGML_TYPE_F32, &ne, &rng.f32s(len));
+        let scale = rng.pick(&[1.0, 0.125, 3.0]);
+        // The mask has a row for each row of a matrix, shared between the matrices.
+        let mask_type = rng.pick(&[
+            None,
+            Some(ggml_type_GGML_TYPE_F32),
+            Some(ggml_type_GGML_TYPE_F16),
+        ]);
+        let mask_ne = [ne[0], ne[1]];
+        let mask = mask_type.map(|type_| {
+            let values = rng.f32s(at([ne[0], ne[1], 1, 1], [0, 0, 1, 0]));
+            upload(&ctx, type_, &mask_ne, &values)
+        });
+        let y = a.softmax_ext(mask.as_ref().map(|(t, _)| *t), scale, 0.0);
+        compute(&ctx, y);
+
+        let mut expected = Vec::new();
+        for (r, row) in av.chunks(ne[0] as usize).enumerate() {
+            let i1 = r % ne[1] as usize;
+            let z = row
+                .iter()
+                .enumerate()
+                .map(|(i0, x)| {
+                    let m = mask.as_ref().map_or(0.0, |(_, mv)| {
+                        mv[at([mask_ne[0], mask_ne[1], 1, 1], [i0 as i64, i1 as i64, 0, 0])]
+                    });
+                    *x as f64 * scale as f64 + m as f64
+                })
+                .collect::<Vec<_>>();
+            let max = z.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let sum = z.iter().map(|z| (z - max).exp()).sum::<f64>();
+            expected.extend(z.iter().map(|z| (z - max).exp() / sum));
+        }
+        let name = format!("soft_max case {case} {ne:?} scale {scale} mask {mask_type:?}");
+        assert_close_values(&name, &download(y), &expected, 1e-5);
+    }
+}
+
+#[test]
+fn reference_rope() {
+    let mut rng = Rng(5);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let head_dim = 2 * rng.int(1, 16);
+        let n_dims = 2 * rng.int(1, head_dim / 2);
+        let ne = [head_dim, rng.int(1, 4), rng.int(1, 5), 1];
+        let (type_, tol) = rng.pick(&[
+            (ggml_type_GGML_TYPE_F32, 1e-4),
+            (ggml_type_GGML_TYPE_F16, 2e-3),
+        ]);
+        let neox = rng.int(0, 1) == 1;
+        let len = ne.iter().product::<i64>() as usize;
+        let (a, av) = upload(&ctx, type_, &ne, &rng.f32s(len));
+        let positions = (0..ne[2])
+            .map(|_| rng.int(0, 64) as i32)
+            .collect::<Vec<_>>();
+        let pos = indices(&ctx, &positions);
+        let params = RopeParams {
+            mode: if neox { 2 } else { 0 },
+            ..RopeParams::new(n_dims as i32)
+        };
+        let y = a.rope_ext(pos, None, &params);
+        compute(&ctx, y);
+
+        let mut expected = av.iter().map(|x| *x as f64).collect::<Vec<_>>();
+        let base = params.freq_base as f64;
+        for (t, p) in positions.iter().enumerate() {
+            for h in 0..ne[1] {
+                let row = at(ne, [0, h, t as i64, 0]);
+                for i0 in (0..n_dims).step_by(2) {
+                    let theta = *p as f64 * base.powf(-(i0 as f64) / n_dims as f64);
+                    let (sin, cos) = theta.sin_cos();
+                    let (j0, j1) = if neox {
+                        (i0 / 2, i0 / 2 + n_dims / 2)
+                    } else {
+                        (i0, i0 + 1)
+                    };
+                    let (j0, j1) = (row + j0 as usize, row + j1 as usize);
+                    let (x0, x1) = (av[j0] as f64, av[j1] as f64);
+                    expected[j0] = x0 * cos - x1 * sin;
+                    expected[j1] = x0 * sin + x1 * cos;
+                }
+            }
+        }
+        let name = format!("rope case {case} {ne:?} n_dims {n_dims} neox {neox} type {type_}");
+        assert_close_values(&name, &download(y), &expected, tol);
+    }
+}
+
+#[test]
+fn reference_conv_1d() {
+    let mut rng = Rng(6);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let (k, ic, oc, batch) = (rng.int(1, 5), rng.int(1, 4), rng.int(1, 4), rng.int(1, 2));
+        let (s, p, d) = (rng.int(1, 3), rng.int(0, 2), rng.int(1, 2));
+        let len = d * (k - 1) + 1 + rng.int(0, 12);
+        let kernel_type = rng.pick(&[ggml_type_GGML_TYPE_F16, ggml_type_GGML_TYPE_F32]);
+        let kne = [k, ic, oc, 1];
+        let (kernel, kv) = upload(
+            &ctx,
+            kernel_type,
+            &kne[..3],
+            &rng.f32s(at(kne, [0, 0, 0, 1])),
+        );
+        let ine = [len, ic, batch, 1];
+        let values = rng.f32s(at(ine, [0, 0, 0, 1]));
+        let (input, _) = upload(&ctx, ggml_type_GGML_TYPE_F32, &ine[..3], &values);
+        // The input is converted to F16 by `im2col`.
+        let (_, iv) = upload(&ctx, ggml_type_GGML_TYPE_F16, &ine[..3], &values);
+        let y = kernel.conv_1d(input, s as i32, p as i32, d as i32);
+        compute(&ctx, y);
+
+        let out_len = (len + 2 * p - d * (k - 1) - 1) / s + 1;
+        assert_eq!(y.ne(), [out_len, oc, batch, 1]);
+        let (mut expected, mut scale) = (Vec::new(), Vec::new());
+        for b in 0..batch {
+            for o in 0..oc {
+                for x in 0..out_len {
+                    let (mut sum, mut abs) = (0.0, 0.0);
+                    for c in 0..ic {
+                        for j in 0..k {
+                            let pos = x * s + j * d - p;
+                            if (0..len).contains(&pos) {
+                                let term = kv[at(kne, [j, c, o, 0])] as f64
+                                    * iv[at(ine, [pos, c, b, 0])] as f64;
+                                sum += term;
+                                abs += term.abs();
+                            }
+                        }
+                    }
+                    expected.push(sum);
+                    scale.push(abs.max(1e-3));
+                }
+            }
+        }
+        let name = format!("conv_1d case {case} kernel {kne:?} input {ine:?} s {s} p {p} d {d}");
+        assert_close(&name, &download(y), &expected, &scale, 1e-3);
+    }
+}
+
+#[test]
+fn reference_conv_2d() {
+    let mut rng = Rng(7);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let (kw, kh, ic, oc) = (rng.int(1, 3), rng.int(1, 3), rng.int(1, 3), rng.int(1, 3));
+        let (s0, s1, p0, p1) = (rng.int(1, 2), rng.int(1, 2), rng.int(0, 1), rng.int(0, 1));
+        let (d0, d1) = (rng.int(1, 2), rng.int(1, 2));
+        let w = d0 * (kw - 1) + 1 + rng.int(0, 6);
+        let h = d1 * (kh - 1) + 1 + rng.int(0, 6);
+        let kne = [kw, kh, ic, oc];
+        let (kernel, kv) = upload(
+            &ctx,
+            ggml_type_GGML_TYPE_F16,
+            &kne,
+            &rng.f32s(at(kne, [0, 0, 0, oc])),
+        );
+        let ine = [w, h, ic, rng.int(1, 2)];
+        let values = rng.f32s(at(ine, [0, 0, 0, ine[3]]));
+        let (input, _) = upload(&ctx, ggml_type_GGML_TYPE_F32, &ine, &values);
+        let (_, iv) = upload(&ctx, ggml_type_GGML_TYPE_F16, &ine, &values);
+        let y = kernel.conv_2d(
+            input,
+            [s0 as i32, s1 as i32],
+            [p0 as i32, p1 as i32],
+            [d0 as i32, d1 as i32],
+        );
+        compute(&ctx, y);
+
+        let ow = (w + 2 * p0 - d0 * (kw - 1) - 1) / s0 + 1;
+        let oh = (h + 2 * p1 - d1 * (kh - 1) - 1) / s1 + 1;
+        assert_eq!(y.ne(), [ow, oh, oc, ine[3]]);
+        let (mut expected, mut scale) = (Vec::new(), Vec::new());
+        for b in 0..ine[3] {
+            for o in 0..oc {
+                for oy in 0..oh {
+                    for ox in 0..ow {
+                        let (mut sum, mut abs) = (0.0, 0.0);
+                        for c in 0..ic {
+                            for ky in 0..kh {
+                                for kx in 0..kw {
+                                    let (x, y) = (ox * s0 + kx * d0 - p0, oy * s1 + ky * d1 - p1);
+                                    if (0..w).contains(&x) && (0..h).contains(&y) {
+                                        let term = kv[at(kne, [kx, ky, c, o])] as f64
+                                            * iv[at(ine, [x, y, c, b])] as f64;
+                                        sum += term;
+                                        abs += term.abs();
+                                    }
+                                }
+                            }
+                        }
+                        expected.push(sum);
+                        scale.push(abs.max(1e-3));
+                    }
+                }
+            }
+        }
+        let name = format!("conv_2d case {case} kernel {kne:?} input {ine:?}");
+        assert_close(&name, &download(y), &expected, &scale, 1e-3);
+    }
+}
+
+#[test]
+fn reference_pool() {
+    let mut rng = Rng(8);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let (op, max) = rng.pick(&[
+            (ggml_op_pool_GGML_OP_POOL_MAX, true),
+            (ggml_op_pool_GGML_OP_POOL_AVG, false),
+        ]);
+        let reduce = |window: &[f64], size: i64| {
+            if max {
+                window.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+            } else {
+                window.iter().sum::<f64>() / size as f64
+            }
+        };
+
+        // 1D pooling doesn't support padding or overlapping windows.
+        let k = rng.int(1, 4);
+        let s = k;
+        let ne = [k + rng.int(0, 20), rng.int(1, 4)];
+        let (a, av) = upload(
+            &ctx,
+            ggml_type_GGML_TYPE_F32,
+            &ne,
+            &rng.f32s((ne[0] * ne[1]) as usize),
+        );
+        let y = a.pool_1d(op, k as i32, s as i32);
+        compute(&ctx, y);
+        let out_len = (ne[0] - k) / s + 1;
+        let mut expected = Vec::new();
+        for row in av.chunks(ne[0] as usize) {
+            for x in 0..out_len {
+                let start = (x * s) as usize;
+                let window = row[start..start + k as usize].iter().map(|v| *v as f64);
+                expected.push(reduce(&window.collect::<Vec<_>>(), k));
+            }
+        }
+        let name = format!("pool_1d case {case} {ne:?} k {k} s {s} max {max}");
+        assert_close_values(&name, &download(y), &expected, 1e-6);
+
+        // 2D pooling skips the padding for max and counts it as zero for the average.
+        let (k0, k1, s0, s1) = (rng.int(1, 3), rng.int(1, 3), rng.int(1, 2), rng.int(1, 2));
+        let (p0, p1) = (rng.int(0, k0 / 2), rng.int(0, k1 / 2));
+        let ne = [k0 + rng.int(0, 8), k1 + rng.int(0, 8), rng.int(1, 3), 1];
+        let (a, av) = upload(
+            &ctx,
+            ggml_type_GGML_TYPE_F32,
+            &ne,
+            &rng.f32s(at(ne, [0, 0, 0, 1])),
+        );
+        let y = a.pool_2d(
+            op,
+            [k0 as i32, k1 as i32],
+            [s0 as i32, s1 as i32],
+            [p0 as i32, p1 as i32],
+        );
+        compute(&ctx, y);
+        let ow = (ne[0] + 2 * p0 - k0) / s0 + 1;
+        let oh = (ne[1] + 2 * p1 - k1) / s1 + 1;
+        assert_eq!(y.ne(), [ow, oh, ne[2], 1]);
+        let mut expected = Vec::new();
+        for c in 0..ne[2] {
+            for oy in 0..oh {
+                for ox in 0..ow {
+                    let mut window = Vec::new();
+                    for ky in 0..k1 {
+                        for kx in 0..k0 {
+                            let (x, y) = (ox * s0 + kx - p0, oy * s1 + ky - p1);
+                            if (0..ne[0]).contains(&x) && (0..ne[1]).contains(&y) {
+                                window.push(av[at(ne, [x, y, c, 0])] as f64);
+                            }
+                        }
+                    }
+                    expected.push(reduce(&window, k0 * k1));
+                }
+            }
+        }
+        let name =
+            format!("pool_2d case {case} {ne:?} k {k0}x{k1} s {s0}x{s1} p {p0}x{p1} max {max}");
+        assert_close_values(&name, &download(y), &expected, 1e-6);
+    }
+}
+
+/// Distinct values, so the sort order is unambiguous.
+fn shuffled(rng: &mut Rng, len: usize) -> Vec<f32> {
+    let mut values = (0..len).map(|i| i as f32 * 0.25 - 3.0).collect::<Vec<_>>();
+    for i in (1..len).rev() {
+        values.swap(i, rng.int(0, i as i64) as usize);
+    }
+    values
+}
+
+#[test]
+fn reference_argsort_top_k() {
+    let mut rng = Rng(9);
+    for case in 0..CASES {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let ne = [rng.int(1, 40), rng.int(1, 5)];
+        let rows = (0..ne[1])
+            .flat_map(|_| shuffled(&mut rng, ne[0] as usize))
+            .collect::<Vec<_>>();
+        let (a, _) = upload(&ctx, ggml_type_GGML_TYPE_F32, &ne, &rows);
+        let asc = a.argsort(ggml_sort_order_GGML_SORT_ORDER_ASC);
+        let desc = a.argsort(ggml_sort_order_GGML_SORT_ORDER_DESC);
+        let k = rng.int(1, ne[0]);
+        let top_k = a.top_k(k as i32);
+        for t in [asc, desc, top_k] {
+            compute(&ctx, t);
+        }
+
+        let (mut expected_asc, mut expected_desc, mut expected_top_k) =
+            (Vec::new(), Vec::new(), Vec::new());
+        for row in rows.chunks(ne[0] as usize) {
+            let mut order = (0..row.len() as i32).collect::<Vec<_>>();
+            order.sort_by(|i, j| row[*i as usize].total_cmp(&row[*j as usize]));
+            expected_asc.extend(&order);
+            order.reverse();
+            expected_desc.extend(&order);
+            expected_top_k.extend(&order[..k as usize]);
+        }
+        assert_eq!(
+            download_i32(asc),
+            expected_asc,
+            "argsort asc case {case} {ne:?}"
+        );
+        assert_eq!(
+            download_i32(desc),
+            expected_desc,
+            "argsort desc case {case} {ne:?}"
+        );
+        assert_eq!(
+            download_i32(top_k),
+            expected_top_k,
+            "top_k case {case} {ne:?} k {k}"
+        );
+    }
+}
+
+#[test]
+fn reference_get_rows() {
+    let mut rng = Rng(10);
+    let types = [
+        (ggml_type_GGML_TYPE_F32, 1),
+        (ggml_type_GGML_TYPE_F16, 1),
+        (ggml_type_GGML_TYPE_Q4_0, 32),
+        (ggml_type_GGML_TYPE_Q8_0, 32),
+        (ggml_type_GGML_TYPE_Q5_K, 256),
+    ];
+    for (case, &(type_, block)) in types.iter().cycle().take(2 * types.len()).enumerate() {
+        let ctx = Context::new(MEM_SIZE).unwrap();
+        let ne = [block * rng.int(1, 4), rng.int(1, 10)];
+        let (a, av) = upload(&ctx, type_, &ne, &rng.f32s((ne[0] * ne[1]) as usize));
+        let rows = (0..rng.int(1, 12))
+            .map(|_| rng.int(0, ne[1] - 1) as i32)
+            .collect::<Vec<_>>();
+        let y = a.get_rows(indices(&ctx, &rows));
+        compute(&ctx, y);
+
+        // Rows are converted with the type's `to_float`, exactly like `dequantize`.
+        let expected = rows
+            .iter()
+            .flat_map(|r| av.chunks(ne[0] as usize).nth(*r as usize).unwrap())
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            download(y),
+            expected,
+            "get_rows case {case} {ne:?} rows {rows:?} type {type_}"
+        );
+    }
+}
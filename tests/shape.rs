@@ -0,0 +1,412 @@
+use ggml_sys_bleedingedge::*;
+
+fn shape_err<T: std::fmt::Debug>(result: Result<T>) -> ShapeError {
+    match result {
+        Err(Error::Shape(err)) => err,
+        other => panic!("Expected a shape error, got {other:?}"),
+    }
+}
+
+#[test]
+fn shape_broadcast() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 3).unwrap();
+    let row = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let bad = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 3).unwrap();
+
+    assert_eq!(a.try_add(row).unwrap().shape(), &[4, 3]);
+    let err = shape_err(a.try_mul(bad));
+    assert_eq!(err.op, "ggml_mul");
+    assert_eq!(
+        err.kind,
+        ShapeErrorKind::Broadcast {
+            src: [3, 1, 1, 1],
+            dst: [4, 3, 1, 1]
+        }
+    );
+    // Broadcasting only goes one way.
+    assert!(row.try_add(a).is_err());
+
+    // Other types are only added to without broadcasting, and only from some types.
+    let h = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 4, 3).unwrap();
+    assert!(h.try_add(a).is_ok());
+    assert!(h.try_add(h).is_ok());
+    assert!(matches!(
+        shape_err(h.try_add(row)).kind,
+        ShapeErrorKind::DimMismatch { .. }
+    ));
+    assert!(a.try_add(h).is_err());
+    let q = ctx.new_tensor_2d(ggml_type_GGML_TYPE_Q8_0, 32, 3).unwrap();
+    let x = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 32, 3).unwrap();
+    assert!(q.try_add(x).is_ok());
+    assert!(q.try_add(q).is_err());
+    let ints = ctx.new_tensor_2d(ggml_type_GGML_TYPE_I32, 4, 3).unwrap();
+    assert!(matches!(
+        shape_err(ints.try_add(ints)).kind,
+        ShapeErrorKind::Argument(_)
+    ));
+}
+
+#[test]
+fn shape_matmul() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let w = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 8, 4).unwrap();
+    let x = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 8, 2).unwrap();
+    assert_eq!(w.try_matmul(x).unwrap().shape(), &[4, 2]);
+
+    let err = shape_err(w.try_matmul(w.transpose()));
+    assert!(matches!(
+        err.kind,
+        ShapeErrorKind::DimMismatch {
+            a_ne: 8,
+            b_ne: 4,
+            ..
+        }
+    ));
+    let y = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 2).unwrap();
+    assert_eq!(
+        shape_err(w.transpose().try_matmul(y)).kind,
+        ShapeErrorKind::Transposed { operand: 0 }
+    );
+
+    let ints = ctx.new_tensor_2d(ggml_type_GGML_TYPE_I32, 8, 4).unwrap();
+    assert!(matches!(
+        shape_err(ints.try_matmul(x)).kind,
+        ShapeErrorKind::Argument(_)
+    ));
+    // F16 is only multiplied with F16 or F32, quantized types with F32.
+    let h = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 8, 2).unwrap();
+    let wh = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 8, 4).unwrap();
+    assert!(wh.try_matmul(h).is_ok());
+    assert!(wh.try_matmul(x).is_ok());
+    let q = ctx.new_tensor_2d(ggml_type_GGML_TYPE_Q4_0, 32, 4).unwrap();
+    let x32 = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 32, 2).unwrap();
+    let h32 = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 32, 2).unwrap();
+    assert!(q.try_matmul(x32).is_ok());
+    assert_eq!(
+        shape_err(q.try_matmul(h32)).kind,
+        ShapeErrorKind::Type {
+            operand: 1,
+            type_: ggml_type_GGML_TYPE_F16
+        }
+    );
+    assert_eq!(
+        shape_err(w.try_matmul(h)).kind,
+        ShapeErrorKind::Type {
+            operand: 1,
+            type_: ggml_type_GGML_TYPE_F16
+        }
+    );
+}
+
+#[test]
+fn shape_reshape_and_view() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 3).unwrap();
+
+    assert_eq!(a.try_reshape(&[2, 6]).unwrap().shape(), &[2, 6]);
+    assert_eq!(
+        shape_err(a.try_reshape(&[5, 2])).kind,
+        ShapeErrorKind::ElementCount {
+            expected: 12,
+            actual: 10
+        }
+    );
+    assert!(matches!(
+        shape_err(a.transpose().try_reshape(&[12])).kind,
+        ShapeErrorKind::NotContiguous { operand: 0, .. }
+    ));
+    assert!(a.try_reshape(&[1, 1, 1, 1, 12]).is_err());
+    assert!(matches!(
+        shape_err(a.try_reshape(&[i64::MAX, 4])).kind,
+        ShapeErrorKind::Argument(_)
+    ));
+    assert!(a.try_reshape(&[-2, -6]).is_err());
+    assert!(a.try_cont_shape(&[i64::MAX, i64::MAX]).is_err());
+
+    assert!(a.try_view(&[4], &[], 8 * 4).is_ok());
+    assert!(matches!(
+        shape_err(a.try_view(&[4], &[], 9 * 4)).kind,
+        ShapeErrorKind::ViewOutOfBounds { .. }
+    ));
+    // Dense size fits but the last row is past the end.
+    assert!(a.try_view(&[2, 3], &[6 * 4], 0).is_err());
+    assert!(a.try_view(&[2, 3], &[], 0).is_err());
+    // Sizes that overflow `usize` are out of bounds rather than wrapping around.
+    assert_eq!(
+        shape_err(a.try_view(&[4], &[], usize::MAX - 8)).kind,
+        ShapeErrorKind::ViewOutOfBounds {
+            end: usize::MAX,
+            nbytes: 48
+        }
+    );
+    assert!(a.try_view(&[2, 2], &[usize::MAX / 2], 0).is_err());
+    assert!(a.try_view(&[1, i64::MAX, 2], &[0, 0], 0).is_err());
+}
+
+#[test]
+fn shape_other_ops() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 8, 2, 3).unwrap();
+    let pos = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 2).unwrap();
+    assert!(a.try_rope_ext(pos, None, &RopeParams::new(8)).is_err());
+    assert!(a.try_permute([0, 1, 1, 3]).is_err());
+    assert!(a.try_concat(a, 4).is_err());
+    assert_eq!(a.try_concat(a, 2).unwrap().shape(), &[8, 2, 6]);
+
+    let rows = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 2).unwrap();
+    assert!(matches!(
+        shape_err(a.try_get_rows(rows)).kind,
+        ShapeErrorKind::Type { operand: 1, .. }
+    ));
+
+    let mask = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 2).unwrap();
+    assert!(a.try_softmax_ext(Some(mask), 1.0, 0.0).is_err());
+    assert!(a.try_softmax_ext(None, 1.0, 8.0).is_err());
+    assert_eq!(a.try_softmax_back_inplace(a).unwrap().view_src(), Some(a));
+    assert!(a.try_softmax_back_inplace(mask).is_err());
+    let i = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 4).unwrap();
+    assert!(i.try_relu().is_err());
+}
+
+#[test]
+fn shape_conv_and_pool() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let kernel = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F16, 3, 3, 4, 8)
+        .unwrap();
+    let input = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 10, 10, 4, 2)
+        .unwrap();
+    let y = kernel.try_conv_2d(input, [2, 1], [1, 0], [1, 1]).unwrap();
+    assert_eq!(y.ne(), [5, 8, 8, 2]);
+    // The kernel and input channels have to match, and the output can't be empty.
+    let other = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 10, 10, 3, 2)
+        .unwrap();
+    assert!(matches!(
+        shape_err(kernel.try_conv_2d(other, [1, 1], [0, 0], [1, 1])).kind,
+        ShapeErrorKind::DimMismatch {
+            a_ne: 4,
+            b_ne: 3,
+            ..
+        }
+    ));
+    assert!(kernel.try_conv_2d(input, [1, 1], [0, 0], [6, 1]).is_err());
+    assert!(kernel.try_conv_2d(input, [0, 1], [0, 0], [1, 1]).is_err());
+
+    let kernel = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F16, 3, 4, 8).unwrap();
+    let input = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 12, 4).unwrap();
+    assert_eq!(
+        kernel.try_conv_1d(input, 1, 1, 1).unwrap().ne(),
+        [12, 8, 1, 1]
+    );
+    assert!(kernel.try_conv_1d(input.transpose(), 1, 1, 1).is_err());
+
+    let max = ggml_op_pool_GGML_OP_POOL_MAX;
+    assert_eq!(input.try_pool_1d(max, 3, 3).unwrap().shape(), &[4, 4]);
+    // Overlapping windows aren't implemented.
+    assert!(input.try_pool_1d(max, 3, 2).is_err());
+    assert!(input
+        .try_pool_1d(ggml_op_pool_GGML_OP_POOL_COUNT, 3, 3)
+        .is_err());
+    let y = input.try_pool_2d(max, [2, 2], [2, 2], [1, 0]).unwrap();
+    assert_eq!(y.shape(), &[7, 2]);
+    assert!(input.try_pool_2d(max, [2, 8], [1, 1], [0, 0]).is_err());
+}
+
+#[test]
+fn shape_copies_and_sets() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 32, 4).unwrap();
+    let q = ctx.new_tensor_2d(ggml_type_GGML_TYPE_Q8_0, 32, 4).unwrap();
+    let i = ctx.new_tensor_2d(ggml_type_GGML_TYPE_I32, 32, 4).unwrap();
+    assert_eq!(a.try_cpy(q).unwrap().type_(), ggml_type_GGML_TYPE_Q8_0);
+    assert!(a.try_cast(ggml_type_GGML_TYPE_BF16).is_ok());
+    // Quantized tensors can't be converted back, and integers can't be converted at all.
+    assert!(q.try_cpy(a).is_err());
+    assert!(i.try_cast(ggml_type_GGML_TYPE_F32).is_err());
+    assert!(matches!(
+        shape_err(a.try_cpy(ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap())).kind,
+        ShapeErrorKind::ElementCount { .. }
+    ));
+    assert_eq!(a.try_cont_shape(&[16, 8]).unwrap().shape(), &[16, 8]);
+
+    let b = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 8, 2).unwrap();
+    let nb = a.nb();
+    assert!(a.try_set(b, [nb[1], nb[2], nb[3]], 24 * 4).is_ok());
+    assert!(a.try_acc(b, [nb[1], nb[2], nb[3]], 2 * nb[1]).is_ok());
+    assert!(matches!(
+        shape_err(a.try_acc(b, [nb[1], nb[2], nb[3]], 3 * nb[1] + 25 * 4)).kind,
+        ShapeErrorKind::ViewOutOfBounds { .. }
+    ));
+    assert!(a.try_set(b, [usize::MAX, 0, 0], 0).is_err());
+}
+
+#[test]
+fn shape_attention() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let q = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, 64, 4, 8)
+        .unwrap();
+    let k = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F16, 64, 16, 2)
+        .unwrap();
+    let y = q.try_flash_attn_ext(k, k, None, 0.125, 0.0).unwrap();
+    assert_eq!(y.shape(), &[64, 8, 4]);
+    // The mask is padded to `GGML_KQ_MASK_PAD` rows.
+    let mask = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 16, 4).unwrap();
+    assert!(q.try_flash_attn_ext(k, k, Some(mask), 0.125, 0.0).is_err());
+    let mask = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 16, 32).unwrap();
+    assert!(q.try_flash_attn_ext(k, k, Some(mask), 0.125, 0.0).is_ok());
+    assert!(q.try_flash_attn_ext(k, k, None, 0.125, 1.0).is_err());
+    let k3 = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F16, 64, 16, 3)
+        .unwrap();
+    assert!(matches!(
+        shape_err(q.try_flash_attn_ext(k3, k3, None, 0.125, 0.0)).kind,
+        ShapeErrorKind::Broadcast { .. }
+    ));
+
+    let experts = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, 64, 32, 4)
+        .unwrap();
+    let x = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, 64, 1, 5)
+        .unwrap();
+    let ids = ctx.new_tensor_2d(ggml_type_GGML_TYPE_I32, 2, 5).unwrap();
+    assert_eq!(experts.try_matmul_id(x, ids).unwrap().shape(), &[32, 2, 5]);
+    assert!(experts.try_matmul_id(x, x).is_err());
+
+    let logits = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 10).unwrap();
+    assert!(logits.try_top_k(11).is_err());
+    assert_eq!(logits.try_top_k(3).unwrap().ne(), [3, 1, 1, 1]);
+}
+
+#[test]
+fn shape_model_specific_ops() {
+    let ctx = Context::new(4 * 1024 * 1024).unwrap();
+    assert_eq!(ctx.try_arange(0.0, 10.0, 3.0).unwrap().shape(), &[4]);
+    assert!(ctx.try_arange(1.0, 0.0, 1.0).is_err());
+    assert!(ctx.try_arange(0.0, 1.0, 0.0).is_err());
+    assert!(ctx.try_arange(0.0, f32::INFINITY, 1.0).is_err());
+
+    let a = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 3).unwrap();
+    let y = a.try_unary(ggml_unary_op_GGML_UNARY_OP_RELU).unwrap();
+    assert_eq!(y.op(), ggml_op_GGML_OP_UNARY);
+    assert!(a.try_unary(ggml_unary_op_GGML_UNARY_OP_COUNT).is_err());
+
+    // 5 x 7 padded to 2 x 3 windows of 3 x 3.
+    let img = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 8, 5, 7).unwrap();
+    let parts = img.try_win_part(3).unwrap();
+    assert_eq!(parts.ne(), [8, 3, 3, 6]);
+    assert!(img.try_win_part(0).is_err());
+    assert_eq!(parts.try_win_unpart(5, 7, 3).unwrap().ne(), [8, 5, 7, 1]);
+    assert!(parts.try_win_unpart(5, 10, 3).is_err());
+    assert!(parts.try_win_unpart(5, 7, 2).is_err());
+
+    let table = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F16, 8, 7).unwrap();
+    assert_eq!(table.try_get_rel_pos(4, 4).unwrap().ne(), [8, 4, 4, 1]);
+    assert!(table.try_get_rel_pos(3, 3).is_err());
+    assert!(table.try_get_rel_pos(4, 3).is_err());
+
+    // Attention over 2 x 3 positions of 4 heads.
+    let attn = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 4, 6, 4).unwrap();
+    let pw = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 2, 2, 3, 4)
+        .unwrap();
+    assert_eq!(attn.try_add_rel_pos(pw, pw).unwrap().shape(), attn.shape());
+    assert!(attn.try_add_rel_pos(pw, attn).is_err());
+    let ph = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 2, 3, 2, 4)
+        .unwrap();
+    assert!(attn.try_add_rel_pos_inplace(ph, ph).is_ok());
+    assert!(attn.try_add_rel_pos(pw, ph).is_err());
+
+    let kernel = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F16, 3, 3, 1, 4)
+        .unwrap();
+    let input = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 10, 8, 4, 2)
+        .unwrap();
+    let y = kernel
+        .try_conv_depthwise_2d(input, [1, 1], [1, 1], [1, 1])
+        .unwrap();
+    assert_eq!(y.ne(), [10, 8, 4, 2]);
+    let other = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 10, 8, 3, 2)
+        .unwrap();
+    assert!(kernel
+        .try_conv_depthwise_2d(other, [1, 1], [1, 1], [1, 1])
+        .is_err());
+    let f32_kernel = ctx
+        .new_tensor_4d(ggml_type_GGML_TYPE_F32, 3, 3, 1, 4)
+        .unwrap();
+    assert!(matches!(
+        shape_err(f32_kernel.try_conv_depthwise_2d(input, [1, 1], [1, 1], [1, 1])).kind,
+        ShapeErrorKind::Type { operand: 0, .. }
+    ));
+
+    let grad = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 2).unwrap();
+    let rows = ctx.new_tensor_1d(ggml_type_GGML_TYPE_I32, 2).unwrap();
+    let table = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 10).unwrap();
+    // Nothing is computed.
+    unsafe {
+        assert_eq!(
+            grad.try_get_rows_back(rows, table).unwrap().ne(),
+            [4, 10, 1, 1]
+        );
+        assert!(grad.try_get_rows_back(table, table).is_err());
+        assert!(table.try_get_rows_back(rows, table).is_err());
+    }
+
+    // A Mamba layer with d_conv 4, d_inner 8, d_state 16, 2 sequences and 3 tokens.
+    let conv_states = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F32, 3, 8, 2).unwrap();
+    let x = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 8, 3).unwrap();
+    let conv = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 4, 8).unwrap();
+    let sq = ctx.new_tensor_2d(ggml_type_GGML_TYPE_I32, 2, 3).unwrap();
+    let states = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, 16, 8, 2)
+        .unwrap();
+    let a_ = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 16, 8).unwrap();
+    let b = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 16, 3).unwrap();
+    unsafe {
+        let y = conv_states.try_ssm_conv(x, conv, sq).unwrap();
+        assert_eq!(y.ne(), [8 * 3 + 3 * 8 * 2, 1, 1, 1]);
+        assert!(conv_states.try_ssm_conv(x, a_, sq).is_err());
+        assert!(conv_states.try_ssm_conv(x, conv, x).is_err());
+
+        let y = states.try_ssm_scan(x, x, a_, b, b, sq).unwrap();
+        assert_eq!(y.ne(), [8 * 3 + 16 * 8 * 2, 1, 1, 1]);
+        assert!(states.try_ssm_scan(x, x, a_, b, b, rows).is_err());
+        assert!(states.try_ssm_scan(x, b, a_, b, b, sq).is_err());
+        assert!(states.try_ssm_scan(x, x, conv, b, b, sq).is_err());
+    }
+}
+
+#[test]
+fn shape_checks_memory() {
+    let ctx = Context::new(unsafe { ggml_tensor_overhead() } * 2 + 256).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 16).unwrap();
+    // A view only needs room for the tensor metadata, the result of an op also needs data.
+    assert!(a.try_reshape(&[4, 4]).is_ok());
+    assert!(matches!(a.try_sqr(), Err(Error::OutOfMemory { .. })));
+
+    // Convolutions need room for their `im2col` and `mul_mat` too, not just the result.
+    let ctx = Context::new(unsafe { ggml_tensor_overhead() } * 8 + 2048).unwrap();
+    let kernel = ctx.new_tensor_3d(ggml_type_GGML_TYPE_F16, 4, 4, 1).unwrap();
+    let input = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 64, 4).unwrap();
+    assert!(matches!(
+        kernel.try_conv_1d(input, 1, 0, 1),
+        Err(Error::OutOfMemory { .. })
+    ));
+}
+
+#[test]
+#[should_panic(expected = "ggml_add: can't broadcast")]
+fn shape_operator_panics() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let b = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 3).unwrap();
+    let _ = a + b;
+}
@@ -0,0 +1,105 @@
+use ggml_sys_bleedingedge::*;
+
+#[test]
+fn tensor_shape_and_strides() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx
+        .new_tensor_3d(ggml_type_GGML_TYPE_F32, 4, 3, 1)
+        .unwrap()
+        .set_name("t");
+    assert_eq!(t.n_dims(), 2);
+    assert_eq!(t.shape(), &[4, 3]);
+    assert_eq!(t.ne(), [4, 3, 1, 1]);
+    assert_eq!(t.nb(), [4, 16, 48, 48]);
+    assert_eq!(t.nbytes(), 48);
+    assert_eq!(t.name(), "t");
+    assert_eq!(t.type_name(), "f32");
+    assert_eq!(t.op(), ggml_op_GGML_OP_NONE);
+    assert!(t.is_contiguous());
+    assert_eq!(ctx.get_tensor("t"), Some(t));
+}
+
+#[test]
+fn tensor_typed_access() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 3, 2).unwrap();
+    t.copy_from_slice(&[0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0])
+        .unwrap();
+    assert_eq!(
+        t.as_slice::<f32>().unwrap(),
+        &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+    );
+    assert_eq!(t.get_f32([2, 1]).unwrap(), 5.0);
+
+    t.set_f32([1, 0], 10.0).unwrap();
+    assert_eq!(t.as_slice::<f32>().unwrap()[1], 10.0);
+
+    assert_eq!(
+        t.as_slice::<i32>(),
+        Err(Error::TypeMismatch {
+            expected: ggml_type_GGML_TYPE_I32,
+            actual: ggml_type_GGML_TYPE_F32
+        })
+    );
+    assert!(matches!(
+        t.copy_from_slice(&[1.0f32]),
+        Err(Error::LengthMismatch { .. })
+    ));
+}
+
+#[test]
+fn tensor_f16_index_converts() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F16, 4).unwrap();
+    t.set_f32([3], 1.5).unwrap();
+    assert_eq!(t.get_f32([3]).unwrap(), 1.5);
+}
+
+#[test]
+fn tensor_index_unsupported_types() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    for type_ in [
+        ggml_type_GGML_TYPE_F64,
+        ggml_type_GGML_TYPE_I64,
+        ggml_type_GGML_TYPE_Q8_0,
+    ] {
+        let t = ctx.new_tensor_1d(type_, 32).unwrap();
+        assert_eq!(t.get_f32([0]), Err(Error::UnsupportedType(type_)));
+        assert_eq!(t.set_i32([0], 1), Err(Error::UnsupportedType(type_)));
+    }
+}
+
+#[test]
+fn tensor_borrowed_data_is_read_only() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    t.copy_from_slice(&[0.0f32; 4]).unwrap();
+    let row = t.view(&[2], &[], 8);
+    let data = row.as_slice::<f32>().unwrap();
+
+    // Handles are `Copy`, so the borrow is checked when writing.
+    assert_eq!(t.set_f32([3], 1.0), Err(Error::DataBorrowed));
+    assert_eq!(t.copy_from_slice(&[1.0f32; 4]), Err(Error::DataBorrowed));
+    t.set_f32([1], 1.0).unwrap();
+    assert_eq!(data, [0.0, 0.0]);
+    drop(data);
+    t.set_f32([3], 1.0).unwrap();
+
+    let out = t.sqr();
+    let mut graph = Graph::new(&ctx).unwrap();
+    graph.build_forward(&out).unwrap();
+    graph.compute(1).unwrap();
+    let data = out.as_slice::<f32>().unwrap();
+    assert_eq!(data, [0.0, 1.0, 0.0, 1.0]);
+    assert_eq!(graph.compute(1), Err(Error::DataBorrowed));
+    drop(data);
+    graph.compute(1).unwrap();
+}
+
+#[test]
+#[should_panic]
+fn tensor_index_out_of_bounds() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let t = ctx.new_tensor_2d(ggml_type_GGML_TYPE_F32, 3, 2).unwrap();
+    let _ = t.get_f32([0, 2]);
+}
@@ -0,0 +1,179 @@
+use std::ops::ControlFlow;
+
+use ggml_sys_bleedingedge::{train::*, *};
+
+/// `sum((x - target)^2)` with `x` as the parameter, starting at zero.
+fn quadratic<'ctx>(ctx: &'ctx Context, target: &[f32]) -> (Tensor<'ctx>, Tensor<'ctx>) {
+    let n = target.len() as i64;
+    let x = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, n)
+        .unwrap()
+        .set_param()
+        .unwrap();
+    x.copy_from_slice(&vec![0.0f32; target.len()]).unwrap();
+    let t = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, n).unwrap();
+    t.copy_from_slice(target).unwrap();
+    (x, (x - t).sqr().sum())
+}
+
+fn assert_close(actual: &[f32], expected: &[f32], tolerance: f32) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < tolerance, "{actual:?} != {expected:?}");
+    }
+}
+
+#[test]
+fn train_set_param() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let x = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    assert!(!x.is_param() && x.grad().is_none());
+    let x = x.set_param().unwrap();
+    assert!(x.is_param());
+    assert_eq!(x.grad().unwrap().shape(), &[4]);
+    let y = x.sqr();
+    assert!(y.grad().is_some());
+    assert!(y.set_param().is_err());
+}
+
+#[test]
+fn train_adam() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let target = [3.0, -1.0, 0.5, 2.0];
+    let (x, loss) = quadratic(&ctx, &target);
+    let config = OptConfig::adam(AdamConfig {
+        n_iter: 500,
+        alpha: 0.05,
+        ..Default::default()
+    });
+    let mut trainer = Trainer::new(loss, config).unwrap();
+    assert_eq!(trainer.params(), &[x]);
+    assert!(trainer.loss_after().is_nan());
+
+    trainer.train().unwrap();
+    assert!(trainer.iter() > 0);
+    assert!(trainer.loss_after() < trainer.loss_before());
+    assert_close(&x.as_slice::<f32>().unwrap(), &target, 0.05);
+}
+
+#[test]
+fn train_no_data() {
+    let ctx = Context::new_no_alloc(1024 * 1024).unwrap();
+    let x = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, 4)
+        .unwrap()
+        .set_param()
+        .unwrap();
+    let mut trainer = Trainer::new(x.sqr().sum(), OptConfig::adam(Default::default())).unwrap();
+    assert_eq!(trainer.train(), Err(Error::NoData));
+}
+
+#[test]
+fn train_lbfgs() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let target = [1.0, 2.0, -3.0];
+    let (x, loss) = quadratic(&ctx, &target);
+    let mut trainer = Trainer::new(loss, OptConfig::lbfgs(LbfgsConfig::default())).unwrap();
+    assert_eq!(trainer.train().unwrap(), Outcome::Converged);
+    assert_close(&x.as_slice::<f32>().unwrap(), &target, 1e-3);
+}
+
+#[test]
+fn train_callback() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (_, loss) = quadratic(&ctx, &[1.0, 1.0]);
+    let config = OptConfig::adam(AdamConfig {
+        n_iter: 100,
+        alpha: 0.01,
+        ..Default::default()
+    });
+    let mut trainer = Trainer::new(loss, config).unwrap();
+
+    let mut calls = Vec::new();
+    let outcome = trainer
+        .train_with(|it| {
+            calls.push(*it);
+            if it.iter == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+    assert_eq!(outcome, Outcome::Stopped);
+    assert_eq!(trainer.iter(), 5);
+    assert_eq!(calls.len(), 6);
+    assert!(calls.iter().enumerate().all(|(i, it)| it.iter == i));
+    assert!(calls[0].loss.is_nan());
+    assert_eq!(calls[1].loss, 2.0);
+    assert!(calls[5].loss < calls[1].loss);
+}
+
+#[test]
+fn train_schedule() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (x, loss) = quadratic(&ctx, &[1.0, 1.0]);
+    let mut trainer = Trainer::new(loss, OptConfig::adam(AdamConfig::default())).unwrap();
+
+    // A zero learning rate leaves the parameters alone, so the loss doesn't change and Adam
+    // considers that converged.
+    let outcome = trainer
+        .train_with(|it| {
+            it.sched = 0.0;
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+    assert_eq!(outcome, Outcome::Converged);
+    assert_eq!(trainer.iter(), 1);
+    assert_eq!(x.as_slice::<f32>().unwrap(), [0.0, 0.0]);
+}
+
+#[test]
+fn train_callback_borrows() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (x, loss) = quadratic(&ctx, &[1.0, 1.0]);
+    let mut trainer = Trainer::new(loss, OptConfig::adam(AdamConfig::default())).unwrap();
+
+    // Reading the parameters in the callback is fine, keeping them borrowed isn't.
+    let mut kept = None;
+    let result = trainer.train_with(|it| {
+        assert_eq!(x.as_slice::<f32>().unwrap().len(), 2);
+        if it.iter == 2 {
+            kept = Some(x.as_slice::<f32>().unwrap());
+        }
+        ControlFlow::Continue(())
+    });
+    assert_eq!(result, Err(Error::DataBorrowed));
+    assert_eq!(trainer.iter(), 2);
+    assert_eq!(trainer.train(), Err(Error::DataBorrowed));
+    drop(kept);
+    trainer.train().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "stop here")]
+fn train_callback_panic() {
+    let ctx = Context::new(16 * 1024 * 1024).unwrap();
+    let (_, loss) = quadratic(&ctx, &[1.0]);
+    let mut trainer = Trainer::new(loss, OptConfig::adam(AdamConfig::default())).unwrap();
+    let _ = trainer.train_with(|_| panic!("stop here"));
+}
+
+#[test]
+fn train_checks_loss() {
+    let ctx = Context::new(1024 * 1024).unwrap();
+    let a = ctx.new_tensor_1d(ggml_type_GGML_TYPE_F32, 4).unwrap();
+    let config = OptConfig::adam(AdamConfig::default());
+    assert_eq!(
+        Trainer::new(a.sum(), config).unwrap_err(),
+        Error::NoGradients
+    );
+    let x = ctx
+        .new_tensor_1d(ggml_type_GGML_TYPE_F32, 4)
+        .unwrap()
+        .set_param()
+        .unwrap();
+    assert!(matches!(
+        Trainer::new(x.sqr(), config),
+        Err(Error::InvalidShape { .. })
+    ));
+}
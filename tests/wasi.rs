@@ -0,0 +1,99 @@
+// Smoke tests for the wasm32-wasip1 build. They also run natively, but their main purpose is
+// `cargo test --target wasm32-wasip1` with a WASI runtime such as wasmtime as the test runner
+// (see `.cargo/config.toml`).
+
+use std::{ffi::CString, path::PathBuf, ptr};
+
+use ggml_sys_bleedingedge::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    if cfg!(target_os = "wasi") {
+        PathBuf::from("/tmp").join(name)
+    } else {
+        std::env::temp_dir().join(name)
+    }
+}
+
+#[test]
+#[cfg(all(target_os = "wasi", target_feature = "simd128"))]
+fn wasm_simd_enabled() {
+    assert_eq!(unsafe { ggml_cpu_has_wasm_simd() }, 1);
+}
+
+#[test]
+fn compute_single_threaded() {
+    unsafe {
+        let ctx = ggml_init(ggml_init_params {
+            mem_size: 16 * 1024 * 1024,
+            mem_buffer: ptr::null_mut(),
+            no_alloc: false,
+        });
+        assert!(!ctx.is_null());
+
+        let a = ggml_new_tensor_1d(ctx, ggml_type_GGML_TYPE_F32, 64);
+        let b = ggml_new_tensor_1d(ctx, ggml_type_GGML_TYPE_F32, 64);
+        for i in 0..64 {
+            ggml_set_f32_1d(a, i, i as f32);
+            ggml_set_f32_1d(b, i, 0.5);
+        }
+        let c = ggml_add(ctx, a, b);
+        let gf = ggml_new_graph(ctx);
+        ggml_build_forward_expand(gf, c);
+        assert_eq!(
+            ggml_graph_compute_with_ctx(ctx, gf, 1),
+            ggml_status_GGML_STATUS_SUCCESS
+        );
+        for i in 0..64 {
+            assert_eq!(ggml_get_f32_1d(c, i), i as f32 + 0.5);
+        }
+        ggml_free(ctx);
+    }
+}
+
+#[test]
+fn gguf_round_trip() {
+    let path = temp_path("ggml-sys-wasi-roundtrip.gguf");
+    let fname = CString::new(path.to_str().unwrap()).unwrap();
+    let key = CString::new("test.value").unwrap();
+    let tname = CString::new("test.tensor").unwrap();
+
+    unsafe {
+        let ctx = ggml_init(ggml_init_params {
+            mem_size: 1024 * 1024,
+            mem_buffer: ptr::null_mut(),
+            no_alloc: false,
+        });
+        let t = ggml_new_tensor_1d(ctx, ggml_type_GGML_TYPE_F32, 8);
+        ggml_set_name(t, tname.as_ptr());
+        ggml_set_f32(t, 1.25);
+
+        let gguf = gguf_init_empty();
+        gguf_set_val_u32(gguf, key.as_ptr(), 42);
+        gguf_add_tensor(gguf, t);
+        gguf_write_to_file(gguf, fname.as_ptr(), false);
+        gguf_free(gguf);
+        ggml_free(ctx);
+
+        let mut data_ctx = ptr::null_mut();
+        let gguf = gguf_init_from_file(
+            fname.as_ptr(),
+            gguf_init_params {
+                no_alloc: false,
+                ctx: &mut data_ctx,
+            },
+        );
+        assert!(!gguf.is_null());
+        let key_id = gguf_find_key(gguf, key.as_ptr());
+        assert!(key_id >= 0);
+        assert_eq!(gguf_get_val_u32(gguf, key_id), 42);
+        assert_eq!(gguf_get_n_tensors(gguf), 1);
+
+        let t = ggml_get_tensor(data_ctx, tname.as_ptr());
+        assert!(!t.is_null());
+        assert_eq!(ggml_get_f32_1d(t, 7), 1.25);
+
+        gguf_free(gguf);
+        ggml_free(data_ctx);
+    }
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,177 @@
+// Runs whisper.cpp on the CPU with a tiny model generated on the fly. The weights are meaningless
+// so this only checks that whisper.cpp links against our GGML and can get through inference.
+#![cfg(feature = "whisper_api")]
+
+use std::{
+    ffi::CString,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use ggml_sys_bleedingedge::*;
+
+const N_VOCAB: i32 = 51864;
+const N_AUDIO_CTX: i32 = 1500;
+const N_AUDIO_STATE: i32 = 8;
+const N_TEXT_CTX: i32 = 448;
+const N_TEXT_STATE: i32 = 8;
+const N_MELS: i32 = 80;
+const N_FFT: i32 = 201;
+
+struct ModelWriter(BufWriter<File>);
+
+impl ModelWriter {
+    fn i32(&mut self, val: i32) {
+        self.0.write_all(&val.to_le_bytes()).unwrap();
+    }
+
+    fn f32s(&mut self, count: i32, val: f32) {
+        (0..count).for_each(|_| self.0.write_all(&val.to_le_bytes()).unwrap());
+    }
+
+    fn tensor(&mut self, name: &str, ne: &[i32], val: f32) {
+        self.i32(ne.len() as i32);
+        self.i32(name.len() as i32);
+        self.i32(ggml_type_GGML_TYPE_F32 as i32);
+        ne.iter().for_each(|n| self.i32(*n));
+        self.0.write_all(name.as_bytes()).unwrap();
+        self.f32s(ne.iter().product(), val);
+    }
+
+    fn norm(&mut self, prefix: &str, n_state: i32) {
+        self.tensor(&format!("{prefix}.weight"), &[n_state], 1.0);
+        self.tensor(&format!("{prefix}.bias"), &[n_state], 0.0);
+    }
+
+    fn linear(&mut self, prefix: &str, n_in: i32, n_out: i32, bias: bool) {
+        self.tensor(&format!("{prefix}.weight"), &[n_in, n_out], 0.01);
+        if bias {
+            self.tensor(&format!("{prefix}.bias"), &[n_out], 0.0);
+        }
+    }
+
+    fn attention(&mut self, prefix: &str, n_state: i32) {
+        self.linear(&format!("{prefix}.query"), n_state, n_state, true);
+        self.linear(&format!("{prefix}.key"), n_state, n_state, false);
+        self.linear(&format!("{prefix}.value"), n_state, n_state, true);
+        self.linear(&format!("{prefix}.out"), n_state, n_state, true);
+    }
+
+    fn mlp(&mut self, prefix: &str, n_state: i32) {
+        self.norm(&format!("{prefix}.mlp_ln"), n_state);
+        self.linear(&format!("{prefix}.mlp.0"), n_state, 4 * n_state, true);
+        self.linear(&format!("{prefix}.mlp.2"), 4 * n_state, n_state, true);
+    }
+}
+
+/// Writes a model in whisper.cpp's legacy GGML format with one encoder and one decoder layer.
+fn write_tiny_model(path: &Path) {
+    let mut w = ModelWriter(BufWriter::new(File::create(path).unwrap()));
+
+    w.i32(0x67676d6c);
+    for hparam in [
+        N_VOCAB,
+        N_AUDIO_CTX,
+        N_AUDIO_STATE,
+        1, // n_audio_head
+        1, // n_audio_layer
+        N_TEXT_CTX,
+        N_TEXT_STATE,
+        1, // n_text_head
+        1, // n_text_layer
+        N_MELS,
+        0, // ftype (all F32)
+    ] {
+        w.i32(hparam);
+    }
+
+    w.i32(N_MELS);
+    w.i32(N_FFT);
+    w.f32s(N_MELS * N_FFT, 0.01);
+
+    // Only part of the vocabulary needs to be present, whisper.cpp fills in the rest.
+    w.i32(256);
+    for tok in 0..=255u8 {
+        w.i32(1);
+        w.0.write_all(&[tok]).unwrap();
+    }
+
+    w.tensor(
+        "encoder.positional_embedding",
+        &[N_AUDIO_STATE, N_AUDIO_CTX],
+        0.0,
+    );
+    w.tensor("encoder.conv1.weight", &[3, N_MELS, N_AUDIO_STATE], 0.01);
+    w.tensor("encoder.conv1.bias", &[1, N_AUDIO_STATE], 0.0);
+    w.tensor(
+        "encoder.conv2.weight",
+        &[3, N_AUDIO_STATE, N_AUDIO_STATE],
+        0.01,
+    );
+    w.tensor("encoder.conv2.bias", &[1, N_AUDIO_STATE], 0.0);
+    w.norm("encoder.ln_post", N_AUDIO_STATE);
+    w.mlp("encoder.blocks.0", N_AUDIO_STATE);
+    w.norm("encoder.blocks.0.attn_ln", N_AUDIO_STATE);
+    w.attention("encoder.blocks.0.attn", N_AUDIO_STATE);
+
+    w.tensor(
+        "decoder.positional_embedding",
+        &[N_TEXT_STATE, N_TEXT_CTX],
+        0.0,
+    );
+    w.tensor(
+        "decoder.token_embedding.weight",
+        &[N_TEXT_STATE, N_VOCAB],
+        0.01,
+    );
+    w.norm("decoder.ln", N_TEXT_STATE);
+    w.mlp("decoder.blocks.0", N_TEXT_STATE);
+    w.norm("decoder.blocks.0.attn_ln", N_TEXT_STATE);
+    w.attention("decoder.blocks.0.attn", N_TEXT_STATE);
+    w.norm("decoder.blocks.0.cross_attn_ln", N_TEXT_STATE);
+    w.attention("decoder.blocks.0.cross_attn", N_TEXT_STATE);
+
+    w.0.flush().unwrap();
+}
+
+#[test]
+fn whisper_tiny_model_cpu() {
+    let path = std::env::temp_dir().join("ggml-sys-whisper-tiny.bin");
+    write_tiny_model(&path);
+    let fname = CString::new(path.to_str().unwrap()).unwrap();
+
+    unsafe {
+        let mut cparams = whisper_context_default_params();
+        cparams.use_gpu = false;
+        let ctx = whisper_init_from_file_with_params(fname.as_ptr(), cparams);
+        assert!(!ctx.is_null(), "Failed to load generated model");
+        assert_eq!(whisper_model_n_vocab(ctx), N_VOCAB);
+        assert_eq!(whisper_model_n_audio_layer(ctx), 1);
+        assert_eq!(whisper_model_n_text_state(ctx), N_TEXT_STATE);
+
+        let mut params =
+            whisper_full_default_params(whisper_sampling_strategy_WHISPER_SAMPLING_GREEDY);
+        params.n_threads = 1;
+        params.max_tokens = 4;
+        params.no_context = true;
+        params.single_segment = true;
+        params.temperature_inc = 0.0;
+        params.print_progress = false;
+        params.print_realtime = false;
+        params.print_timestamps = false;
+
+        // One second of a 440Hz tone at whisper's 16kHz sample rate.
+        let samples = (0..16000)
+            .map(|i| (i as f32 * 440.0 * std::f32::consts::TAU / 16000.0).sin() * 0.5)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            whisper_full(ctx, params, samples.as_ptr(), samples.len() as i32),
+            0
+        );
+        assert!(whisper_full_n_segments(ctx) <= 1);
+
+        whisper_free(ctx);
+    }
+    let _ = std::fs::remove_file(path);
+}